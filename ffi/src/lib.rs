@@ -0,0 +1,244 @@
+// C ABI, 声明见 include/mrc.h
+use mrc::remove::{Action, Candidate};
+use mrc::{policy, Cleaner, CleanupOptions};
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+pub const MRC_ACTION_FILE: u32 = 0;
+pub const MRC_ACTION_WITH_CHECKSUMS: u32 = 1;
+pub const MRC_ACTION_VERSION_DIR: u32 = 2;
+
+pub struct MrcCleaner(Cleaner);
+
+pub struct MrcCandidates {
+    candidates: Vec<Candidate>,
+    paths: Vec<CString>,
+    reasons: Vec<CString>,
+}
+
+#[repr(C)]
+#[derive(Default)]
+pub struct MrcSummary {
+    pub deleted_size: u64,
+    pub files_deleted: u64,
+    pub dirs_removed: u64,
+    pub files_skipped: u64,
+    pub errors: u64,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_error(error: String) {
+    let error = CString::new(error).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(error));
+}
+
+unsafe fn path_arg(value: *const c_char) -> Option<PathBuf> {
+    if value.is_null() {
+        return None;
+    }
+    Some(PathBuf::from(
+        CStr::from_ptr(value).to_string_lossy().as_ref(),
+    ))
+}
+
+fn options(policy_path: Option<PathBuf>, threads: usize) -> Result<CleanupOptions, String> {
+    let mut options = CleanupOptions {
+        threads: threads.max(1),
+        ..Default::default()
+    };
+    if let Some(file) = policy_path {
+        let mut policies = policy::load(&file)?;
+        if policies.len() != 1 {
+            return Err(format!(
+                "'{}' must define exactly one policy",
+                file.display()
+            ));
+        }
+        let policy = policies.remove(0);
+        options.rules = policy.rules();
+        options.from_repo = policy.from_repo;
+    }
+    Ok(options)
+}
+
+/// 最近一次失败的描述, 属于当前线程, 下次调用前有效; 没有时返回 NULL
+#[no_mangle]
+pub extern "C" fn mrc_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |e| e.as_ptr()))
+}
+
+/// policy_path 可为 NULL; 失败时返回 NULL
+///
+/// # Safety
+/// repo_path 与 policy_path 必须是有效的 NUL 结尾字符串
+#[no_mangle]
+pub unsafe extern "C" fn mrc_cleaner_new(
+    repo_path: *const c_char,
+    policy_path: *const c_char,
+    threads: usize,
+) -> *mut MrcCleaner {
+    let repo_path = match path_arg(repo_path) {
+        Some(repo_path) if Path::new(&repo_path).is_dir() => repo_path,
+        Some(repo_path) => {
+            set_error(format!("'{}' is not a directory", repo_path.display()));
+            return ptr::null_mut();
+        }
+        None => {
+            set_error("repo_path is NULL".to_string());
+            return ptr::null_mut();
+        }
+    };
+    match options(path_arg(policy_path), threads) {
+        Ok(options) => Box::into_raw(Box::new(MrcCleaner(Cleaner::new(repo_path, options)))),
+        Err(e) => {
+            set_error(e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// # Safety
+/// cleaner 必须来自 mrc_cleaner_new 且只释放一次
+#[no_mangle]
+pub unsafe extern "C" fn mrc_cleaner_free(cleaner: *mut MrcCleaner) {
+    if !cleaner.is_null() {
+        drop(Box::from_raw(cleaner));
+    }
+}
+
+/// # Safety
+/// cleaner 必须来自 mrc_cleaner_new
+#[no_mangle]
+pub unsafe extern "C" fn mrc_scan(cleaner: *const MrcCleaner) -> *mut MrcCandidates {
+    let Some(cleaner) = cleaner.as_ref() else {
+        set_error("cleaner is NULL".to_string());
+        return ptr::null_mut();
+    };
+    let candidates = cleaner.0.scan();
+    let text = |value: String| CString::new(value).unwrap_or_default();
+    let paths = candidates
+        .iter()
+        .map(|c| text(c.path.display().to_string()))
+        .collect();
+    let reasons = candidates
+        .iter()
+        .map(|c| text(c.reason.to_string()))
+        .collect();
+    Box::into_raw(Box::new(MrcCandidates {
+        candidates,
+        paths,
+        reasons,
+    }))
+}
+
+/// # Safety
+/// candidates 必须来自 mrc_scan
+#[no_mangle]
+pub unsafe extern "C" fn mrc_candidates_len(candidates: *const MrcCandidates) -> usize {
+    candidates.as_ref().map_or(0, |c| c.candidates.len())
+}
+
+/// 返回的字符串在 mrc_candidates_free 前有效; 越界时返回 NULL
+///
+/// # Safety
+/// candidates 必须来自 mrc_scan
+#[no_mangle]
+pub unsafe extern "C" fn mrc_candidate_path(
+    candidates: *const MrcCandidates,
+    index: usize,
+) -> *const c_char {
+    candidates
+        .as_ref()
+        .and_then(|c| c.paths.get(index))
+        .map_or(ptr::null(), |path| path.as_ptr())
+}
+
+/// # Safety
+/// candidates 必须来自 mrc_scan
+#[no_mangle]
+pub unsafe extern "C" fn mrc_candidate_reason(
+    candidates: *const MrcCandidates,
+    index: usize,
+) -> *const c_char {
+    candidates
+        .as_ref()
+        .and_then(|c| c.reasons.get(index))
+        .map_or(ptr::null(), |reason| reason.as_ptr())
+}
+
+/// 越界时返回 UINT32_MAX
+///
+/// # Safety
+/// candidates 必须来自 mrc_scan
+#[no_mangle]
+pub unsafe extern "C" fn mrc_candidate_action(
+    candidates: *const MrcCandidates,
+    index: usize,
+) -> u32 {
+    match candidates.as_ref().and_then(|c| c.candidates.get(index)) {
+        Some(candidate) => match candidate.action {
+            Action::File => MRC_ACTION_FILE,
+            Action::WithChecksums => MRC_ACTION_WITH_CHECKSUMS,
+            Action::VersionDir => MRC_ACTION_VERSION_DIR,
+        },
+        None => u32::MAX,
+    }
+}
+
+/// 删除 indices 指定的候选, indices 为 NULL 时删除全部; 成功返回 0
+///
+/// # Safety
+/// cleaner 与 candidates 必须有效, indices 指向 count 个元素
+#[no_mangle]
+pub unsafe extern "C" fn mrc_delete(
+    cleaner: *const MrcCleaner,
+    candidates: *const MrcCandidates,
+    indices: *const usize,
+    count: usize,
+    summary: *mut MrcSummary,
+) -> i32 {
+    let (Some(cleaner), Some(candidates)) = (cleaner.as_ref(), candidates.as_ref()) else {
+        set_error("cleaner or candidates is NULL".to_string());
+        return -1;
+    };
+    let selected: Vec<Candidate> = if indices.is_null() {
+        candidates.candidates.clone()
+    } else {
+        let mut selected = Vec::new();
+        for &index in std::slice::from_raw_parts(indices, count) {
+            match candidates.candidates.get(index) {
+                Some(candidate) => selected.push(candidate.clone()),
+                None => {
+                    set_error(format!("candidate index {} out of range", index));
+                    return -1;
+                }
+            }
+        }
+        selected
+    };
+    let result = cleaner.0.clean(selected);
+    if let Some(summary) = summary.as_mut() {
+        *summary = MrcSummary {
+            deleted_size: result.deleted_size as u64,
+            files_deleted: result.files_deleted as u64,
+            dirs_removed: result.dirs_removed as u64,
+            files_skipped: result.files_skipped as u64,
+            errors: result.errors as u64,
+        };
+    }
+    0
+}
+
+/// # Safety
+/// candidates 必须来自 mrc_scan 且只释放一次
+#[no_mangle]
+pub unsafe extern "C" fn mrc_candidates_free(candidates: *mut MrcCandidates) {
+    if !candidates.is_null() {
+        drop(Box::from_raw(candidates));
+    }
+}
@@ -0,0 +1,315 @@
+// 桌面前端: 浏览仓库树, 勾选版本后执行清理
+use eframe::egui;
+use mrc::remove::{self, Action, Candidate};
+use mrc::summary::Summary;
+use mrc::{gav, human_size, Cleaner, CleanupOptions};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+static REASON_SELECTED: &str = "selected";
+
+struct Version {
+    name: String,
+    path: PathBuf,
+    size: usize,
+    selected: bool,
+}
+
+struct Artifact {
+    coordinates: String,
+    versions: Vec<Version>,
+}
+
+impl Artifact {
+    fn size(&self) -> usize {
+        self.versions.iter().map(|v| v.size).sum()
+    }
+}
+
+#[derive(Default)]
+struct Tree {
+    artifacts: Vec<Artifact>,
+    candidates: Vec<Candidate>,
+    candidates_size: usize,
+}
+
+#[derive(Default)]
+struct Run {
+    done: usize,
+    total: usize,
+    summary: Summary,
+    finished: bool,
+}
+
+enum State {
+    Idle,
+    Scanning(Arc<Mutex<Option<Tree>>>),
+    Cleaning(Arc<Mutex<Run>>),
+}
+
+struct App {
+    repo_path: String,
+    tree: Tree,
+    include_candidates: bool,
+    state: State,
+    last_run: Option<Summary>,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        let repo_path = std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(|home| PathBuf::from(home).join(".m2").join("repository"))
+            .unwrap_or_default();
+        App {
+            repo_path: repo_path.display().to_string(),
+            tree: Tree::default(),
+            include_candidates: true,
+            state: State::Idle,
+            last_run: None,
+        }
+    }
+}
+
+fn scan(repo_path: PathBuf) -> Tree {
+    let mut artifacts: BTreeMap<String, Vec<Version>> = BTreeMap::new();
+    for version in gav::scan_versions(&repo_path) {
+        artifacts
+            .entry(format!(
+                "{}:{}",
+                version.gav.group_id, version.gav.artifact_id
+            ))
+            .or_default()
+            .push(Version {
+                name: version.gav.version,
+                size: remove::dir_size(&version.path),
+                path: version.path,
+                selected: false,
+            });
+    }
+    // 按默认规则得到的候选 (过期快照, 本地元数据)
+    let candidates = Cleaner::new(repo_path, CleanupOptions::default()).scan();
+    let candidates_size = remove::planned_files(&mrc::fs::RealFs, &candidates)
+        .iter()
+        .map(|(_, size)| size)
+        .sum();
+    Tree {
+        artifacts: artifacts
+            .into_iter()
+            .map(|(coordinates, versions)| Artifact {
+                coordinates,
+                versions,
+            })
+            .collect(),
+        candidates,
+        candidates_size,
+    }
+}
+
+impl App {
+    fn selected(&self) -> Vec<Candidate> {
+        let mut candidates: Vec<Candidate> = self
+            .tree
+            .artifacts
+            .iter()
+            .flat_map(|artifact| &artifact.versions)
+            .filter(|version| version.selected)
+            .map(|version| {
+                Candidate::new(version.path.clone(), Action::VersionDir, REASON_SELECTED)
+            })
+            .collect();
+        if self.include_candidates {
+            // 已勾选整个版本目录时跳过其中的单个文件
+            let dirs: Vec<PathBuf> = candidates.iter().map(|c| c.path.clone()).collect();
+            candidates.extend(
+                self.tree
+                    .candidates
+                    .iter()
+                    .filter(|c| !dirs.iter().any(|dir| c.path.starts_with(dir)))
+                    .cloned(),
+            );
+        }
+        candidates
+    }
+
+    fn start_scan(&mut self) {
+        let tree = Arc::new(Mutex::new(None));
+        let result = Arc::clone(&tree);
+        let repo_path = PathBuf::from(&self.repo_path);
+        thread::spawn(move || {
+            let scanned = scan(repo_path);
+            *result.lock().unwrap() = Some(scanned);
+        });
+        self.state = State::Scanning(tree);
+    }
+
+    fn start_clean(&mut self) {
+        let candidates = self.selected();
+        let run = Arc::new(Mutex::new(Run {
+            total: candidates.len(),
+            ..Default::default()
+        }));
+        let progress = Arc::clone(&run);
+        let repo_path = PathBuf::from(&self.repo_path);
+        thread::spawn(move || {
+            let options = CleanupOptions::default();
+            for candidate in &candidates {
+                let mut summary = Summary::default();
+                remove::remove_candidate(&repo_path, candidate, &options, &mut summary);
+                let mut run = progress.lock().unwrap();
+                run.summary.merge(summary);
+                run.done += 1;
+            }
+            progress.lock().unwrap().finished = true;
+        });
+        self.state = State::Cleaning(run);
+    }
+
+    fn poll(&mut self) {
+        match &self.state {
+            State::Scanning(tree) => {
+                if let Some(tree) = tree.lock().unwrap().take() {
+                    self.tree = tree;
+                    self.state = State::Idle;
+                }
+            }
+            State::Cleaning(run) => {
+                let mut run = run.lock().unwrap();
+                if run.finished {
+                    self.last_run = Some(std::mem::take(&mut run.summary));
+                    drop(run);
+                    // 重新扫描以显示清理后的大小
+                    self.start_scan();
+                }
+            }
+            State::Idle => {}
+        }
+    }
+
+    fn tree_view(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for artifact in &mut self.tree.artifacts {
+                let size = artifact.size();
+                let mut all = artifact.versions.iter().all(|v| v.selected);
+                ui.horizontal(|ui| {
+                    if ui.checkbox(&mut all, "").changed() {
+                        for version in &mut artifact.versions {
+                            version.selected = all;
+                        }
+                    }
+                    egui::CollapsingHeader::new(format!(
+                        "{} ({})",
+                        artifact.coordinates,
+                        human_size(size)
+                    ))
+                    .id_salt(&artifact.coordinates)
+                    .show(ui, |ui| {
+                        for version in &mut artifact.versions {
+                            ui.checkbox(
+                                &mut version.selected,
+                                format!("{} ({})", version.name, human_size(version.size)),
+                            );
+                        }
+                    });
+                });
+            }
+        });
+    }
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll();
+        let busy = !matches!(self.state, State::Idle);
+        egui::TopBottomPanel::top("repository").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Repository:");
+                ui.text_edit_singleline(&mut self.repo_path);
+                if ui.add_enabled(!busy, egui::Button::new("Scan")).clicked() {
+                    self.start_scan();
+                }
+            });
+        });
+        egui::TopBottomPanel::bottom("actions").show(ctx, |ui| {
+            ui.checkbox(
+                &mut self.include_candidates,
+                format!(
+                    "Include stale snapshots and local metadata ({} candidates, {})",
+                    self.tree.candidates.len(),
+                    human_size(self.tree.candidates_size)
+                ),
+            );
+            let selected = self.selected();
+            // 近似值: 勾选版本内的候选不会重复删除
+            let size: usize = self
+                .tree
+                .artifacts
+                .iter()
+                .flat_map(|artifact| &artifact.versions)
+                .filter(|version| version.selected)
+                .map(|version| version.size)
+                .sum::<usize>()
+                + if self.include_candidates {
+                    self.tree.candidates_size
+                } else {
+                    0
+                };
+            ui.horizontal(|ui| {
+                let clean = egui::Button::new(format!(
+                    "Clean {} selected ({})",
+                    selected.len(),
+                    human_size(size)
+                ));
+                if ui
+                    .add_enabled(!busy && !selected.is_empty(), clean)
+                    .clicked()
+                {
+                    self.start_clean();
+                }
+                match &self.state {
+                    State::Scanning(_) => {
+                        ui.spinner();
+                        ui.label("Scanning...");
+                    }
+                    State::Cleaning(run) => {
+                        let run = run.lock().unwrap();
+                        let fraction = run.done as f32 / run.total.max(1) as f32;
+                        ui.add(egui::ProgressBar::new(fraction).text(format!(
+                            "{}/{}, {} deleted",
+                            run.done,
+                            run.total,
+                            human_size(run.summary.deleted_size)
+                        )));
+                    }
+                    State::Idle => {
+                        if let Some(summary) = &self.last_run {
+                            ui.label(format!(
+                                "Last run: {} files, {} directories, {} deleted, {} errors",
+                                summary.files_deleted,
+                                summary.dirs_removed,
+                                human_size(summary.deleted_size),
+                                summary.errors
+                            ));
+                        }
+                    }
+                }
+            });
+        });
+        egui::CentralPanel::default().show(ctx, |ui| self.tree_view(ui));
+        if busy {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+    }
+}
+
+fn main() -> eframe::Result {
+    simple_logger::init_with_level(log::Level::Info).unwrap();
+    eframe::run_native(
+        "Maven Repository Cleaner",
+        eframe::NativeOptions::default(),
+        Box::new(|_cc| Ok(Box::new(App::default()))),
+    )
+}
@@ -0,0 +1,51 @@
+/* Embedding API for mrc, built from ffi/ as libmrc_ffi (see ffi/src/lib.rs). */
+#ifndef MRC_H
+#define MRC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define MRC_ACTION_FILE 0
+#define MRC_ACTION_WITH_CHECKSUMS 1
+#define MRC_ACTION_VERSION_DIR 2
+
+typedef struct MrcCleaner MrcCleaner;
+typedef struct MrcCandidates MrcCandidates;
+
+typedef struct {
+    uint64_t deleted_size;
+    uint64_t files_deleted;
+    uint64_t dirs_removed;
+    uint64_t files_skipped;
+    uint64_t errors;
+} MrcSummary;
+
+/* Description of the last failure on this thread, or NULL. */
+const char *mrc_last_error(void);
+
+/* policy_path may be NULL. Returns NULL on failure. */
+MrcCleaner *mrc_cleaner_new(const char *repo_path, const char *policy_path, size_t threads);
+void mrc_cleaner_free(MrcCleaner *cleaner);
+
+MrcCandidates *mrc_scan(const MrcCleaner *cleaner);
+size_t mrc_candidates_len(const MrcCandidates *candidates);
+/* Strings stay valid until mrc_candidates_free; NULL when index is out of range. */
+const char *mrc_candidate_path(const MrcCandidates *candidates, size_t index);
+const char *mrc_candidate_reason(const MrcCandidates *candidates, size_t index);
+/* One of MRC_ACTION_*, or UINT32_MAX when index is out of range. */
+uint32_t mrc_candidate_action(const MrcCandidates *candidates, size_t index);
+void mrc_candidates_free(MrcCandidates *candidates);
+
+/* Deletes the selected candidates (all of them when indices is NULL). summary may be NULL. Returns 0 on success. */
+int32_t mrc_delete(const MrcCleaner *cleaner, const MrcCandidates *candidates,
+                   const size_t *indices, size_t count, MrcSummary *summary);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
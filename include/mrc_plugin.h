@@ -0,0 +1,38 @@
+/* Rule plugin ABI for mrc (see src/plugins.rs). */
+#ifndef MRC_PLUGIN_H
+#define MRC_PLUGIN_H
+
+#include <stdint.h>
+
+#define MRC_PLUGIN_ABI_VERSION 1
+
+#define MRC_VERDICT_PASS 0
+#define MRC_VERDICT_KEEP 1
+#define MRC_VERDICT_DELETE 2
+#define MRC_VERDICT_ARCHIVE 3
+
+/* Fields outside a version directory are empty strings. */
+typedef struct {
+    const char *path;
+    const char *group;
+    const char *artifact;
+    const char *version;
+    const char *file_name;
+} MrcFile;
+
+/* reason must stay valid after returning; NULL uses the plugin name. */
+typedef struct {
+    uint32_t action;
+    const char *reason;
+} MrcVerdict;
+
+typedef struct {
+    uint32_t abi_version;
+    const char *name;
+    MrcVerdict (*evaluate)(const MrcFile *file);
+} MrcPlugin;
+
+/* Every plugin exports this symbol. evaluate may be called from several threads. */
+const MrcPlugin *mrc_plugin_v1(void);
+
+#endif
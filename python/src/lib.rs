@@ -0,0 +1,138 @@
+use mrc::remove::{self, Action};
+use mrc::{policy, Cleaner, CleanupOptions};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::path::{Path, PathBuf};
+
+#[pyclass(name = "Candidate", frozen)]
+#[derive(Clone)]
+struct PyCandidate {
+    inner: remove::Candidate,
+}
+
+#[pymethods]
+impl PyCandidate {
+    #[getter]
+    fn path(&self) -> PathBuf {
+        self.inner.path.clone()
+    }
+
+    // "file", "with-checksums" 或 "version-dir"
+    #[getter]
+    fn action(&self) -> &'static str {
+        match self.inner.action {
+            Action::File => "file",
+            Action::WithChecksums => "with-checksums",
+            Action::VersionDir => "version-dir",
+        }
+    }
+
+    #[getter]
+    fn reason(&self) -> &'static str {
+        self.inner.reason.code()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Candidate(path={:?}, action={:?}, reason={:?})",
+            self.inner.path.display().to_string(),
+            self.action(),
+            self.inner.reason.code()
+        )
+    }
+}
+
+fn options(
+    policy: Option<&str>,
+    from_repo: Option<String>,
+    threads: usize,
+) -> PyResult<CleanupOptions> {
+    let mut options = CleanupOptions {
+        from_repo,
+        threads: threads.max(1),
+        ..Default::default()
+    };
+    if let Some(file) = policy {
+        let mut policies =
+            policy::load(Path::new(file)).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        if policies.len() != 1 {
+            return Err(PyValueError::new_err(format!(
+                "'{}' must define exactly one policy",
+                file
+            )));
+        }
+        let policy = policies.remove(0);
+        options.rules = policy.rules();
+        options.from_repo = options.from_repo.or(policy.from_repo);
+    }
+    Ok(options)
+}
+
+fn cleaner(
+    repo: PathBuf,
+    policy: Option<&str>,
+    from_repo: Option<String>,
+    threads: usize,
+) -> PyResult<Cleaner> {
+    Ok(Cleaner::new(repo, options(policy, from_repo, threads)?))
+}
+
+/// Scan a repository and return the deletion candidates.
+#[pyfunction]
+#[pyo3(signature = (repo, policy=None, from_repo=None))]
+fn scan(
+    py: Python<'_>,
+    repo: PathBuf,
+    policy: Option<&str>,
+    from_repo: Option<String>,
+) -> PyResult<Vec<PyCandidate>> {
+    let cleaner = cleaner(repo, policy, from_repo, 1)?;
+    let candidates = py.allow_threads(|| cleaner.scan());
+    Ok(candidates
+        .into_iter()
+        .map(|inner| PyCandidate { inner })
+        .collect())
+}
+
+/// Expand candidates into the (path, bytes) files they would remove.
+#[pyfunction]
+fn plan(py: Python<'_>, candidates: Vec<PyCandidate>) -> Vec<(PathBuf, usize)> {
+    let candidates: Vec<remove::Candidate> = candidates.into_iter().map(|c| c.inner).collect();
+    py.allow_threads(|| remove::planned_files(&mrc::fs::RealFs, &candidates))
+}
+
+/// Delete the given candidates (or scan first) and return the same summary as --report.
+#[pyfunction]
+#[pyo3(signature = (repo, candidates=None, policy=None, from_repo=None, threads=1))]
+fn clean<'py>(
+    py: Python<'py>,
+    repo: PathBuf,
+    candidates: Option<Vec<PyCandidate>>,
+    policy: Option<&str>,
+    from_repo: Option<String>,
+    threads: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    let cleaner = cleaner(repo, policy, from_repo, threads)?;
+    let summary = py.allow_threads(|| {
+        let candidates = match candidates {
+            Some(candidates) => candidates.into_iter().map(|c| c.inner).collect(),
+            None => cleaner.scan(),
+        };
+        cleaner.clean(candidates)
+    });
+    let json = serde_json::to_string(&summary).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    py.import("json")?
+        .call_method1("loads", (json,))?
+        .downcast_into::<PyDict>()
+        .map_err(PyErr::from)
+}
+
+#[pymodule]
+fn maven_repo_cleaner(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyCandidate>()?;
+    m.add_function(wrap_pyfunction!(scan, m)?)?;
+    m.add_function(wrap_pyfunction!(plan, m)?)?;
+    m.add_function(wrap_pyfunction!(clean, m)?)?;
+    Ok(())
+}
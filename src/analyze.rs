@@ -0,0 +1,77 @@
+use crate::gav::{self, VersionDir};
+use crate::remove::dir_size;
+use crate::{human_size, i18n, pom};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+static UNKNOWN_LICENSE: &str = "Unknown";
+
+// 许可证可能声明在父 POM 中
+const MAX_PARENT_DEPTH: usize = 8;
+
+// 按 group、许可证或版本目录汇总; 版本目录是计量的最小单位
+pub fn analyze(repo_path: PathBuf, licenses: bool, by_version: bool) {
+    let versions = gav::scan_versions(&repo_path);
+    let mut summary: HashMap<String, (usize, usize)> = HashMap::new();
+    for version in &versions {
+        let size = dir_size(&version.path);
+        let keys = if licenses {
+            find_licenses(&repo_path, version)
+        } else if by_version {
+            vec![version.gav.to_string()]
+        } else {
+            vec![version.gav.group_id.clone()]
+        };
+        for key in keys {
+            let entry = summary.entry(key).or_default();
+            entry.0 += 1;
+            entry.1 += size;
+        }
+    }
+
+    let mut rows: Vec<(String, (usize, usize))> = summary.into_iter().collect();
+    rows.sort_by(|a, b| b.1 .1.cmp(&a.1 .1).then_with(|| a.0.cmp(&b.0)));
+    let title = match (licenses, by_version) {
+        (true, _) => "License",
+        (false, true) => "Version",
+        (false, false) => "Group",
+    };
+    println!(
+        "{:<60} {:>10} {:>12} {:>14}",
+        i18n::translate(title),
+        i18n::translate("Versions"),
+        i18n::translate("Size"),
+        i18n::translate("Bytes")
+    );
+    for (key, (count, size)) in rows {
+        println!(
+            "{:<60} {:>10} {:>12} {:>14}",
+            key,
+            count,
+            human_size(size),
+            size
+        );
+    }
+}
+
+fn find_licenses(repo_path: &Path, version: &VersionDir) -> Vec<String> {
+    let mut gav = version.gav.clone();
+    for _ in 0..MAX_PARENT_DEPTH {
+        let pom_path = pom::path_in_repo(repo_path, &gav);
+        if !pom_path.is_file() {
+            break;
+        }
+        let pom = match pom::read(&pom_path) {
+            Some(pom) => pom,
+            None => break,
+        };
+        if !pom.licenses.is_empty() {
+            return pom.licenses;
+        }
+        match pom.parent {
+            Some(parent) => gav = parent,
+            None => break,
+        }
+    }
+    vec![UNKNOWN_LICENSE.to_string()]
+}
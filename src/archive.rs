@@ -0,0 +1,37 @@
+use crate::gav;
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+
+static ARCHIVE_SUFFIX: &str = ".tar.zst";
+
+const COMPRESSION_LEVEL: i32 = 9;
+
+// 归档内路径相对于仓库根目录, 解压到仓库根目录即可恢复
+pub fn archive_version(repo_path: &Path, path: &Path, archive_dir: &Path) -> io::Result<PathBuf> {
+    let gav = gav::from_version_dir(repo_path, path)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a version directory"))?;
+    let relative_path = path
+        .strip_prefix(repo_path)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let mut target = archive_dir.to_path_buf();
+    if let Some(artifact_path) = relative_path.parent() {
+        target.push(artifact_path);
+    }
+    std::fs::create_dir_all(&target)?;
+    target.push(format!(
+        "{}_{}_{}{}",
+        gav.group_id, gav.artifact_id, gav.version, ARCHIVE_SUFFIX
+    ));
+
+    let output = File::create(&target)?;
+    let encoder = zstd::Encoder::new(output, COMPRESSION_LEVEL)?;
+    let mut builder = tar::Builder::new(encoder);
+    // 记录属主、权限和修改时间, 以 tar -p --same-owner 解压即可原样恢复
+    builder.mode(tar::HeaderMode::Complete);
+    builder.append_dir_all(relative_path, path)?;
+    let output = builder.into_inner()?.finish()?;
+    output.sync_all()?;
+    Ok(target)
+}
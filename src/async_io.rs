@@ -0,0 +1,131 @@
+use crate::progress::Progress;
+use crate::remove::{self, Action, Candidate};
+use crate::summary::Summary;
+use crate::throttle::Throttle;
+use crate::{CleanupOptions, CHECKSUM_SUFFIXIES};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+// 高延迟文件系统上同时发起多个 stat/删除请求, 远程校验也并发进行
+pub fn execute(
+    repo_path: &Path,
+    candidates: Vec<Candidate>,
+    options: &CleanupOptions,
+    summary: &mut Summary,
+) {
+    let runtime = match tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            log::error!("Failed to start async runtime: {}", e);
+            return;
+        }
+    };
+    runtime.block_on(async {
+        #[cfg(feature = "remote")]
+        if let Some(verifier) = &options.verifier {
+            verify(repo_path, &candidates, verifier, options).await;
+        }
+        let mut progress = Progress::new(candidates.len());
+        let throttle = options.throttle.map(Throttle::new);
+        let mut tasks = JoinSet::new();
+        let mut done: usize = 0;
+        let mut candidates = candidates.into_iter();
+        loop {
+            while tasks.len() < options.io_depth {
+                let candidate = match candidates.next() {
+                    Some(candidate) => candidate,
+                    None => break,
+                };
+                // 移动, 归档和目录删除仍走同步实现
+                if options.move_to.is_some()
+                    || candidate.quarantine
+                    || candidate.action == Action::VersionDir
+                {
+                    remove::remove_candidate(repo_path, &candidate, options, summary);
+                    done += 1;
+                    continue;
+                }
+                let batch = match remove::begin_batch(&candidate, options) {
+                    Ok(batch) => batch,
+                    Err(_) => {
+                        summary.error();
+                        done += 1;
+                        continue;
+                    }
+                };
+                tasks.spawn(remove_file(candidate, batch));
+            }
+            let local = match tasks.join_next().await {
+                Some(Ok((batch, local))) => {
+                    remove::commit_batch(batch, options, &local);
+                    local
+                }
+                Some(Err(e)) => {
+                    log::error!("Deletion task failed: {}", e);
+                    summary.error();
+                    continue;
+                }
+                None => break,
+            };
+            summary.merge(local);
+            done += 1;
+            progress.update(done, summary);
+            if let Some(throttle) = &throttle {
+                throttle.pace(done, summary.deleted_size);
+            }
+        }
+        progress.finish(summary);
+    });
+}
+
+async fn remove_file(candidate: Candidate, batch: Option<usize>) -> (Option<usize>, Summary) {
+    let mut summary = Summary::default();
+    let mut paths = vec![candidate.path.clone()];
+    if candidate.action == Action::WithChecksums {
+        for suffix in CHECKSUM_SUFFIXIES {
+            let mut checksum = candidate.path.as_os_str().to_owned();
+            checksum.push(suffix);
+            paths.push(PathBuf::from(checksum));
+        }
+    }
+    for path in paths {
+        // 同一文件可能被多条规则选中, 校验文件也可能不存在
+        let size = match tokio::fs::metadata(&path).await {
+            Ok(metadata) if metadata.is_file() => metadata.len() as usize,
+            _ => continue,
+        };
+        remove::log_deleting(&path);
+        match tokio::fs::remove_file(&path).await {
+            Ok(_) => summary.record(candidate.reason, &path, size),
+            Err(e) => {
+                log::error!("Failed to delete file '{}': {}", path.display(), e);
+                summary.error();
+            }
+        }
+    }
+    (batch, summary)
+}
+
+#[cfg(feature = "remote")]
+async fn verify(
+    repo_path: &Path,
+    candidates: &[Candidate],
+    verifier: &crate::remote::RemoteVerifier,
+    options: &CleanupOptions,
+) {
+    let repo_path = Arc::new(repo_path.to_path_buf());
+    let mut tasks = JoinSet::new();
+    for path in remove::verification_targets(&*options.fs, candidates) {
+        if tasks.len() >= options.io_depth {
+            tasks.join_next().await;
+        }
+        let verifier = verifier.clone();
+        let repo_path = repo_path.clone();
+        tasks.spawn_blocking(move || verifier.warn_if_unresolvable(&repo_path, &path));
+    }
+    while tasks.join_next().await.is_some() {}
+}
@@ -0,0 +1,128 @@
+use crate::gav::{self, VersionDir};
+use crate::remove::{self, Action, Candidate};
+use crate::settings::Settings;
+use crate::summary::{Reason, Summary};
+use crate::CleanupOptions;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+static OSV_BATCH_URL: &str = "https://api.osv.dev/v1/querybatch";
+
+static OSV_ECOSYSTEM: &str = "Maven";
+
+const OSV_BATCH_SIZE: usize = 1000;
+
+#[derive(Serialize)]
+struct BatchQuery<'a> {
+    queries: Vec<Query<'a>>,
+}
+
+#[derive(Serialize)]
+struct Query<'a> {
+    package: Package,
+    version: &'a str,
+}
+
+#[derive(Serialize)]
+struct Package {
+    name: String,
+    ecosystem: &'static str,
+}
+
+#[derive(Deserialize)]
+struct BatchResponse {
+    results: Vec<QueryResult>,
+}
+
+#[derive(Deserialize)]
+struct QueryResult {
+    #[serde(default)]
+    vulns: Vec<Vulnerability>,
+}
+
+#[derive(Deserialize)]
+struct Vulnerability {
+    id: String,
+}
+
+pub fn audit(
+    repo_path: PathBuf,
+    purge: bool,
+    settings: &Settings,
+    options: &CleanupOptions,
+) -> bool {
+    let agent = match settings.agent(Duration::from_secs(30)) {
+        Ok(agent) => agent,
+        Err(e) => {
+            log::error!("Failed to query OSV: {}", e);
+            return false;
+        }
+    };
+    let versions = gav::scan_versions(&repo_path);
+    log::info!("Querying OSV for {} versions", versions.len());
+
+    let mut vulnerable: usize = 0;
+    let mut candidates = Vec::new();
+    for batch in versions.chunks(OSV_BATCH_SIZE) {
+        let results = match query(&agent, batch) {
+            Ok(results) => results,
+            Err(e) => {
+                log::error!("Failed to query OSV: {}", e);
+                return false;
+            }
+        };
+        for (version, result) in batch.iter().zip(results) {
+            if result.vulns.is_empty() {
+                continue;
+            }
+            vulnerable += 1;
+            let ids: Vec<String> = result.vulns.into_iter().map(|v| v.id).collect();
+            log::warn!("Vulnerable {}: {}", version.gav, ids.join(", "));
+            if purge {
+                candidates.push(Candidate::new(
+                    version.path.clone(),
+                    Action::VersionDir,
+                    Reason::Vulnerable,
+                ));
+            }
+        }
+    }
+
+    log::info!("Vulnerable versions: {}", vulnerable);
+    if purge {
+        let mut summary = Summary::default();
+        remove::execute(&repo_path, candidates, options, &mut summary);
+        summary.finish(&repo_path, options);
+    }
+    vulnerable == 0
+}
+
+fn query(agent: &ureq::Agent, batch: &[VersionDir]) -> Result<Vec<QueryResult>, String> {
+    let body = BatchQuery {
+        queries: batch
+            .iter()
+            .map(|version| Query {
+                package: Package {
+                    name: format!("{}:{}", version.gav.group_id, version.gav.artifact_id),
+                    ecosystem: OSV_ECOSYSTEM,
+                },
+                version: &version.gav.version,
+            })
+            .collect(),
+    };
+    let body = serde_json::to_string(&body).map_err(|e| e.to_string())?;
+    let response = agent
+        .post(OSV_BATCH_URL)
+        .header("Content-Type", "application/json")
+        .send(body)
+        .map_err(|e| e.to_string())?
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| e.to_string())?;
+    let response: BatchResponse = serde_json::from_str(&response).map_err(|e| e.to_string())?;
+    if response.results.len() != batch.len() {
+        return Err("unexpected number of results".to_string());
+    }
+    Ok(response.results)
+}
@@ -0,0 +1,147 @@
+use crate::gav;
+use crate::remove::{dir_size, planned_files};
+use crate::{duration, human_size, i18n, Cleaner, CleanupOptions};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub static BASELINE_FILE: &str = ".mrc-baseline.json";
+
+static OTHER_GROUP: &str = "(other)";
+
+// 清理后的仓库状态, 供下次 compare 对比
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub created: u64,
+    pub groups: BTreeMap<String, usize>,
+}
+
+impl Baseline {
+    pub fn total(&self) -> usize {
+        self.groups.values().sum()
+    }
+}
+
+pub fn measure(repo_path: &Path) -> Baseline {
+    let mut groups = BTreeMap::new();
+    for version in gav::scan_versions(repo_path) {
+        *groups.entry(version.gav.group_id).or_default() += dir_size(&version.path);
+    }
+    Baseline {
+        created: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default(),
+        groups,
+    }
+}
+
+pub fn save(repo_path: &Path) -> Baseline {
+    let path = repo_path.join(BASELINE_FILE);
+    let baseline = measure(repo_path);
+    match serde_json::to_string(&baseline) {
+        Ok(content) => {
+            if let Err(e) = std::fs::write(&path, content) {
+                log::error!("Failed to write baseline '{}': {}", path.display(), e);
+            }
+        }
+        Err(e) => log::error!("Failed to serialize baseline: {}", e),
+    }
+    baseline
+}
+
+pub fn load(repo_path: &Path) -> Result<Baseline, String> {
+    let content =
+        std::fs::read_to_string(repo_path.join(BASELINE_FILE)).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+// 对比上次清理后的基线: 每个 group 的增长量以及新产生的可清理内容
+pub fn compare(repo_path: &Path, options: &CleanupOptions) -> bool {
+    let baseline = match load(repo_path) {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            log::error!("No baseline, run a cleanup first: {}", e);
+            return false;
+        }
+    };
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs().saturating_sub(baseline.created))
+        .unwrap_or_default();
+    log::info!(
+        "Baseline from {} ago",
+        duration::format(Duration::from_secs(elapsed))
+    );
+
+    let versions = gav::scan_versions(repo_path);
+    let mut current: BTreeMap<String, usize> = BTreeMap::new();
+    // 版本目录和构件目录下的文件都归属到对应 group
+    let mut owners: HashMap<PathBuf, String> = HashMap::new();
+    for version in versions {
+        *current.entry(version.gav.group_id.clone()).or_default() += dir_size(&version.path);
+        if let Some(artifact_dir) = version.path.parent() {
+            owners.insert(artifact_dir.to_path_buf(), version.gav.group_id.clone());
+        }
+        owners.insert(version.path, version.gav.group_id);
+    }
+    let candidates = Cleaner::new(repo_path.to_path_buf(), options.clone()).scan();
+    let mut garbage: BTreeMap<String, usize> = BTreeMap::new();
+    for (path, size) in planned_files(&*options.fs, &candidates) {
+        let group = path
+            .ancestors()
+            .skip(1)
+            .find_map(|folder| owners.get(folder))
+            .map(String::as_str)
+            .unwrap_or(OTHER_GROUP);
+        *garbage.entry(group.to_string()).or_default() += size;
+    }
+
+    let mut rows: Vec<(&str, usize, i64, usize)> = current
+        .keys()
+        .chain(baseline.groups.keys())
+        .chain(garbage.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|group| {
+            let size = current.get(group).copied().unwrap_or_default();
+            let before = baseline.groups.get(group).copied().unwrap_or_default();
+            let new_garbage = garbage.get(group).copied().unwrap_or_default();
+            (
+                group.as_str(),
+                size,
+                size as i64 - before as i64,
+                new_garbage,
+            )
+        })
+        .filter(|(_, _, growth, new_garbage)| *growth != 0 || *new_garbage > 0)
+        .collect();
+    rows.sort_by(|a, b| b.3.cmp(&a.3).then(b.2.cmp(&a.2)).then(a.0.cmp(b.0)));
+    println!(
+        "{:<60} {:>12} {:>12} {:>12}",
+        "Group",
+        i18n::translate("Size"),
+        i18n::translate("Growth"),
+        i18n::translate("New garbage")
+    );
+    for (group, size, growth, new_garbage) in &rows {
+        let sign = if *growth < 0 { "-" } else { "+" };
+        println!(
+            "{:<60} {:>12} {:>12} {:>12}",
+            group,
+            human_size(*size),
+            format!("{}{}", sign, human_size(growth.unsigned_abs() as usize)),
+            human_size(*new_garbage)
+        );
+    }
+    let total_growth: i64 = rows.iter().map(|(_, _, growth, _)| growth).sum();
+    let total_garbage: usize = rows.iter().map(|(_, _, _, garbage)| garbage).sum();
+    log::info!(
+        "Total growth: {}{}",
+        if total_growth < 0 { "-" } else { "+" },
+        human_size(total_growth.unsigned_abs() as usize)
+    );
+    log::info!("New garbage: {}", human_size(total_garbage));
+    true
+}
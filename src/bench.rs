@@ -0,0 +1,156 @@
+use crate::fixture::{self, Fixture};
+use crate::fs::{FsHandle, Metadata, RealFs, RepoFs};
+use crate::{Cleaner, CleanupOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// 统计文件系统调用次数
+#[derive(Default)]
+struct CountingFs {
+    ops: AtomicUsize,
+    entries: AtomicUsize,
+}
+
+impl CountingFs {
+    fn count(&self) {
+        self.ops.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl RepoFs for CountingFs {
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<io::Result<PathBuf>>> {
+        self.count();
+        let entries = RealFs.read_dir(path)?;
+        self.entries.fetch_add(entries.len(), Ordering::Relaxed);
+        Ok(entries)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        self.count();
+        RealFs.metadata(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.count();
+        RealFs.read_to_string(path)
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        self.count();
+        RealFs.write(path, contents)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.count();
+        RealFs.remove_file(path)
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        self.count();
+        RealFs.remove_dir(path)
+    }
+}
+
+struct Row {
+    strategy: &'static str,
+    threads: usize,
+    scan: Duration,
+    clean: Option<Duration>,
+    entries: usize,
+    deleted: usize,
+    ops: usize,
+}
+
+// 指定仓库时只测扫描, 否则为每种配置生成相同的合成仓库并完整清理
+pub fn run(repo_path: Option<&Path>, thread_counts: &[usize], fixture: &Fixture) -> bool {
+    let mut rows = Vec::new();
+    if let Some(repo_path) = repo_path {
+        rows.push(measure(repo_path, "scan", 1, false));
+    } else {
+        let work_dir = std::env::temp_dir().join(format!("mrc-bench-{}", std::process::id()));
+        for &threads in thread_counts {
+            for strategy in strategies() {
+                let _ = std::fs::remove_dir_all(&work_dir);
+                log::info!("Generating fixture: {}", work_dir.display());
+                if let Err(e) = fixture::generate(&work_dir, fixture) {
+                    log::error!("Failed to generate fixture '{}': {}", work_dir.display(), e);
+                    let _ = std::fs::remove_dir_all(&work_dir);
+                    return false;
+                }
+                rows.push(measure(&work_dir, strategy, threads, true));
+            }
+        }
+        let _ = std::fs::remove_dir_all(&work_dir);
+    }
+
+    println!(
+        "{:<8} {:>8} {:>10} {:>10} {:>12} {:>10} {:>10}",
+        "Strategy", "Threads", "Scan ms", "Clean ms", "Entries/s", "Files/s", "FS ops"
+    );
+    for row in rows {
+        let (clean_ms, files_per_second) = match row.clean {
+            Some(clean) => (
+                clean.as_millis().to_string(),
+                format!("{:.0}", row.deleted as f64 / clean.as_secs_f64().max(0.001)),
+            ),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        println!(
+            "{:<8} {:>8} {:>10} {:>10} {:>12.0} {:>10} {:>10}",
+            row.strategy,
+            row.threads,
+            row.scan.as_millis(),
+            clean_ms,
+            row.entries as f64 / row.scan.as_secs_f64().max(0.001),
+            files_per_second,
+            row.ops
+        );
+    }
+    true
+}
+
+fn strategies() -> Vec<&'static str> {
+    let mut strategies = vec!["threads"];
+    if cfg!(feature = "async") {
+        strategies.push("async");
+    }
+    strategies
+}
+
+fn measure(repo_path: &Path, strategy: &'static str, threads: usize, clean: bool) -> Row {
+    let fs = Arc::new(CountingFs::default());
+    #[allow(unused_mut)]
+    let mut options = CleanupOptions {
+        threads,
+        fs: FsHandle::from(fs.clone()),
+        ..Default::default()
+    };
+    #[cfg(feature = "async")]
+    if strategy == "async" {
+        options.io_depth = threads.max(2);
+    }
+    let cleaner = Cleaner::new(repo_path.to_path_buf(), options);
+    let start = Instant::now();
+    let candidates = cleaner.scan();
+    let scan = start.elapsed();
+    let entries = fs.entries.load(Ordering::Relaxed);
+    let (clean, deleted) = if clean {
+        let start = Instant::now();
+        let summary = cleaner.clean(candidates);
+        (Some(start.elapsed()), summary.files_deleted)
+    } else {
+        (None, 0)
+    };
+    Row {
+        strategy,
+        threads,
+        scan,
+        clean,
+        entries,
+        deleted,
+        ops: fs.ops.load(Ordering::Relaxed),
+    }
+}
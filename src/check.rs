@@ -0,0 +1,53 @@
+use crate::remove::{planned_files, planned_versions};
+use crate::{format_size, gav, Cleaner, CleanupOptions};
+use std::path::Path;
+
+// CI 守卫: 可回收空间超过阈值时返回 false, 不删除任何文件
+pub fn check(repo_path: &Path, options: &CleanupOptions, max_garbage: usize) -> bool {
+    let candidates = Cleaner::new(repo_path.to_path_buf(), options.clone()).scan();
+    let files = planned_files(&*options.fs, &candidates);
+    let size: usize = files.iter().map(|(_, size)| size).sum();
+    log::info!("Reclaimable files: {}", files.len());
+    log::info!("Reclaimable size: {}", format_size(size));
+    for (dir, stat) in planned_versions(&files) {
+        log::info!(
+            "  {}: {} across {} files",
+            gav::label(repo_path, &dir),
+            format_size(stat.size),
+            stat.files
+        );
+    }
+    if size > max_garbage {
+        log::error!(
+            "Reclaimable size exceeds --max-garbage {}",
+            format_size(max_garbage)
+        );
+        return false;
+    }
+    true
+}
+
+// 仓库在当前策略下已无可清理内容时返回 true; 扫描出错时无法确认, 视为不合规
+pub fn verify(repo_path: &Path, options: &CleanupOptions) -> bool {
+    let mut candidates = Vec::new();
+    let (summary, _) = crate::pipeline::run(repo_path, options, None, &mut |candidate| {
+        candidates.push(candidate)
+    });
+    for candidate in &candidates {
+        log::warn!(
+            "Not compliant ({}): {}",
+            candidate.reason,
+            candidate.path.display()
+        );
+    }
+    if summary.aborted || summary.errors > 0 {
+        log::error!("Scan failed, compliance could not be verified");
+        return false;
+    }
+    if !candidates.is_empty() {
+        log::error!("Repository is not clean: {} candidates", candidates.len());
+        return false;
+    }
+    log::info!("Repository is clean");
+    true
+}
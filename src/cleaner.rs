@@ -0,0 +1,94 @@
+use crate::remove::{self, Candidate};
+use crate::summary::Summary;
+use crate::CleanupOptions;
+use std::path::PathBuf;
+
+// 供其他程序嵌入使用: 先扫描得到候选, 再决定删除哪些
+#[derive(Clone)]
+pub struct Cleaner {
+    repo_path: PathBuf,
+    options: CleanupOptions,
+}
+
+#[derive(Debug)]
+pub struct Removal {
+    pub candidate: Candidate,
+    pub summary: Summary,
+}
+
+impl Cleaner {
+    pub fn new(repo_path: PathBuf, options: CleanupOptions) -> Self {
+        Cleaner { repo_path, options }
+    }
+
+    pub fn scan(&self) -> Vec<Candidate> {
+        let mut candidates = Vec::new();
+        let (summary, _) =
+            crate::pipeline::run(&self.repo_path, &self.options, None, &mut |candidate| {
+                candidates.push(candidate)
+            });
+        // 严格模式下中止时不返回部分结果
+        if summary.aborted {
+            candidates.clear();
+        }
+        candidates
+    }
+
+    pub fn clean(&self, candidates: Vec<Candidate>) -> Summary {
+        let mut summary = Summary::default();
+        remove::execute(&self.repo_path, candidates, &self.options, &mut summary);
+        summary
+    }
+
+    // 需在 tokio 运行时中调用, 扫描在阻塞线程池中进行
+    #[cfg(feature = "async")]
+    pub fn scan_async(&self) -> tokio_stream::wrappers::ReceiverStream<Candidate> {
+        let (sender, receiver) = tokio::sync::mpsc::channel(CHANNEL_CAPACITY);
+        let cleaner = self.clone();
+        tokio::task::spawn_blocking(move || {
+            crate::pipeline::run(
+                &cleaner.repo_path,
+                &cleaner.options,
+                None,
+                &mut |candidate| {
+                    let _ = sender.blocking_send(candidate);
+                },
+            );
+        });
+        tokio_stream::wrappers::ReceiverStream::new(receiver)
+    }
+
+    // 每删除一个候选产出一条结果, 接收方关闭后停止删除
+    #[cfg(feature = "async")]
+    pub fn clean_async(
+        &self,
+        candidates: Vec<Candidate>,
+    ) -> tokio_stream::wrappers::ReceiverStream<Removal> {
+        let (sender, receiver) = tokio::sync::mpsc::channel(CHANNEL_CAPACITY);
+        let cleaner = self.clone();
+        tokio::task::spawn_blocking(move || {
+            for candidate in candidates {
+                if sender.is_closed() {
+                    break;
+                }
+                let mut summary = Summary::default();
+                remove::remove_candidate(
+                    &cleaner.repo_path,
+                    &candidate,
+                    &cleaner.options,
+                    &mut summary,
+                );
+                if sender
+                    .blocking_send(Removal { candidate, summary })
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+        tokio_stream::wrappers::ReceiverStream::new(receiver)
+    }
+}
+
+#[cfg(feature = "async")]
+const CHANNEL_CAPACITY: usize = 256;
@@ -0,0 +1,151 @@
+use crate::fs::RealFs;
+use crate::{format_size, get_file_name, is_artifact, is_walkable_dir, read_dir_sorted, relocate};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+pub static COMPRESSED_SUFFIX: &str = ".mrc.zst";
+
+const COMPRESSION_LEVEL: i32 = 19;
+
+pub fn compress_older_than(repo_path: &Path, age: Duration) {
+    // 比纪元还早的时间点没有文件能早于它
+    let threshold = SystemTime::now().checked_sub(age);
+    let mut compressed: usize = 0;
+    let mut saved_size: usize = 0;
+    for path in walk_files(repo_path) {
+        let file_name = match get_file_name(&path) {
+            Some(file_name) => file_name,
+            None => continue,
+        };
+        if !is_artifact(&file_name) || file_name.ends_with(COMPRESSED_SUFFIX) {
+            continue;
+        }
+        let metadata = match std::fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                log::error!("Failed to read metadata '{}': {}", path.display(), e);
+                continue;
+            }
+        };
+        // 优先使用访问时间, 文件系统不支持时退回修改时间
+        let last_used = metadata.accessed().or_else(|_| metadata.modified());
+        if threshold.is_none_or(|threshold| last_used.is_ok_and(|last_used| last_used > threshold))
+        {
+            continue;
+        }
+        match compress(&path) {
+            Ok(Some(size)) => {
+                compressed += 1;
+                saved_size += metadata.len().saturating_sub(size) as usize;
+            }
+            Ok(None) => log::debug!("Not compressible: {}", path.display()),
+            Err(e) => log::error!("Failed to compress '{}': {}", path.display(), e),
+        }
+    }
+    log::info!("Compressed files: {}", compressed);
+    log::info!("Compressed size saved: {}", format_size(saved_size));
+}
+
+pub fn restore(path: &Path) {
+    let mut restored: usize = 0;
+    let files = if path.is_dir() {
+        walk_files(path)
+    } else {
+        vec![path.to_path_buf()]
+    };
+    for path in files {
+        if !path.to_string_lossy().ends_with(COMPRESSED_SUFFIX) {
+            continue;
+        }
+        match decompress(&path) {
+            Ok(_) => restored += 1,
+            Err(e) => log::error!("Failed to restore '{}': {}", path.display(), e),
+        }
+    }
+    log::info!("Restored files: {}", restored);
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file = path.as_os_str().to_owned();
+    file.push(suffix);
+    PathBuf::from(file)
+}
+
+// 压缩后不更小时保留原文件, 返回 None
+fn compress(path: &Path) -> io::Result<Option<u64>> {
+    let target = with_suffix(path, COMPRESSED_SUFFIX);
+    let temp = with_suffix(&target, ".tmp");
+    let metadata = std::fs::metadata(path)?;
+    let result = (|| {
+        let mut input = File::open(path)?;
+        let output = File::create(&temp)?;
+        let mut encoder = zstd::Encoder::new(output, COMPRESSION_LEVEL)?;
+        io::copy(&mut input, &mut encoder)?;
+        let output = encoder.finish()?;
+        output.sync_all()?;
+        let size = output.metadata()?.len();
+        relocate::copy_attributes(&metadata, &temp)?;
+        Ok(size)
+    })();
+    let size = match result {
+        Ok(size) => size,
+        Err(e) => {
+            let _ = std::fs::remove_file(&temp);
+            return Err(e);
+        }
+    };
+    if size >= metadata.len() {
+        std::fs::remove_file(&temp)?;
+        return Ok(None);
+    }
+    log::info!("Compressing: {}", path.display());
+    std::fs::rename(&temp, &target)?;
+    std::fs::remove_file(path)?;
+    Ok(Some(size))
+}
+
+fn decompress(path: &Path) -> io::Result<()> {
+    let path_text = path.to_string_lossy();
+    let target = PathBuf::from(&path_text[..path_text.len() - COMPRESSED_SUFFIX.len()]);
+    let temp = with_suffix(&target, ".tmp");
+    let metadata = std::fs::metadata(path)?;
+    let result = (|| {
+        let input = File::open(path)?;
+        let mut output = File::create(&temp)?;
+        zstd::stream::copy_decode(input, &mut output)?;
+        output.sync_all()?;
+        relocate::copy_attributes(&metadata, &temp)
+    })();
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&temp);
+        return Err(e);
+    }
+    log::info!("Restoring: {}", target.display());
+    std::fs::rename(&temp, &target)?;
+    std::fs::remove_file(path)
+}
+
+fn walk_files(path: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(path.to_path_buf());
+    while let Some(path) = queue.pop_front() {
+        match read_dir_sorted(&path) {
+            Ok(folder) => {
+                for entry in folder.flatten() {
+                    let entry_path = entry.path();
+                    if is_walkable_dir(&RealFs, &entry_path) {
+                        queue.push_back(entry_path);
+                    } else {
+                        files.push(entry_path);
+                    }
+                }
+            }
+            Err(e) => log::error!("Failed to read directory '{}': {}", path.display(), e),
+        }
+    }
+    files
+}
@@ -0,0 +1,170 @@
+use crate::policy::{self, Policy, Rule};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+// 用户级配置, 对每次运行都生效
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    // 组织范围内始终保留的 groupId 前缀, 不受命令行参数影响
+    #[serde(default)]
+    pub always_keep: Vec<String>,
+    // 以 --profile 选择, 如 [profile.ci]、[profile.laptop]
+    #[serde(default)]
+    pub profile: BTreeMap<String, Profile>,
+}
+
+// 一套命名的清理设置: 仓库路径、保留规则和 daemon 的清理间隔
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    // 未在命令行指定仓库时清理这些路径
+    #[serde(default)]
+    pub paths: Vec<String>,
+    // 与顶层的 always_keep 合并
+    #[serde(default)]
+    pub always_keep: Vec<String>,
+    pub interval: Option<String>,
+    #[serde(default)]
+    pub purge_decommissioned: bool,
+    pub from_repo: Option<String>,
+    // 与策略文件中 [[policy.rule]] 的格式相同
+    #[serde(default)]
+    pub rule: Vec<Rule>,
+}
+
+impl Config {
+    pub fn profile(&self, name: &str) -> Result<&Profile, String> {
+        self.profile.get(name).ok_or_else(|| {
+            let known: Vec<&str> = self.profile.keys().map(String::as_str).collect();
+            format!("unknown profile '{}' (defined: {})", name, known.join(", "))
+        })
+    }
+}
+
+impl Profile {
+    // 没有设置规则时使用默认规则, 返回 None
+    pub fn policy(&self, name: &str) -> Option<Policy> {
+        let configured =
+            !self.rule.is_empty() || self.purge_decommissioned || self.from_repo.is_some();
+        configured.then(|| Policy {
+            name: name.to_string(),
+            purge_decommissioned: self.purge_decommissioned,
+            from_repo: self.from_repo.clone(),
+            rule: self.rule.clone(),
+        })
+    }
+}
+
+pub fn default_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .or_else(|| std::env::var_os("USERPROFILE"))
+                .map(|home| PathBuf::from(home).join(".config"))
+        })
+        .map(|config| config.join("mrc").join("config.toml"))
+}
+
+// 显式指定的配置文件必须存在, 默认位置不存在时使用空配置
+pub fn load(path: Option<&Path>) -> Result<Config, String> {
+    let path = match path {
+        Some(path) => Some(path.to_path_buf()),
+        None => default_path().filter(|path| path.is_file()),
+    };
+    let mut config = match path {
+        Some(path) => {
+            log::debug!("Loading config: {}", path.display());
+            let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+            parse(&content)?
+        }
+        None => Config::default(),
+    };
+    // 逗号分隔, 追加到配置文件中的列表
+    if let Ok(always_keep) = std::env::var("MRC_ALWAYS_KEEP") {
+        config.always_keep.extend(
+            always_keep
+                .split(',')
+                .map(str::trim)
+                .filter(|prefix| !prefix.is_empty())
+                .map(String::from),
+        );
+    }
+    Ok(config)
+}
+
+// init 生成的配置: 一个使用默认规则的 profile, 其余设置以注释说明
+pub fn skeleton(repo_path: &Path) -> String {
+    let repo = toml::Value::String(repo_path.display().to_string());
+    format!(
+        r#"# mrc configuration, read by every run (see `mrc --help`).
+
+# groupId prefixes that are never cleaned, whatever policy or flags a run uses.
+always_keep = []
+
+# Select with `mrc --profile default`; add more sections such as [profile.ci].
+[profile.default]
+# Repositories cleaned when no path is given on the command line.
+paths = [{repo}]
+# Extra prefixes kept by this profile only.
+always_keep = []
+# Time between cleanups for `mrc daemon --profile default`.
+interval = "1d"
+# Also delete artifacts whose source repository is no longer in settings.xml.
+purge_decommissioned = false
+
+# Without rules the built-in policy applies: it only removes superseded
+# snapshot builds, local download metadata, empty metadata-only versions and
+# stale lock files, never a release. Rules replace it and use the same format as a --policy
+# file, e.g.:
+#
+# [[profile.default.rule]]
+# name = "old-snapshots"
+# snapshot = true
+# older_than = "30d"
+# action = "delete"
+#
+# [[profile.default.rule]]
+# name = "release-history"
+# snapshot = false
+# action = "keep-latest"
+# count = 3
+"#
+    )
+}
+
+// 已存在的配置文件只有在 force 时才覆盖
+pub fn init(path: &Path, repo_path: &Path, force: bool) -> bool {
+    if path.exists() && !force {
+        log::error!(
+            "Config '{}' already exists, use --force to overwrite",
+            path.display()
+        );
+        return false;
+    }
+    let result = match path.parent() {
+        Some(parent) => std::fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+    .and_then(|_| std::fs::write(path, skeleton(repo_path)));
+    match result {
+        Ok(()) => {
+            log::info!("Wrote config: {}", path.display());
+            true
+        }
+        Err(e) => {
+            log::error!("Failed to write config '{}': {}", path.display(), e);
+            false
+        }
+    }
+}
+
+pub fn parse(content: &str) -> Result<Config, String> {
+    let mut config: Config = toml::from_str(content).map_err(|e| e.to_string())?;
+    for (name, profile) in &mut config.profile {
+        policy::prepare(&mut profile.rule).map_err(|e| format!("profile {}: {}", name, e))?;
+    }
+    Ok(config)
+}
@@ -0,0 +1,150 @@
+use crate::dashboard::{self, Shared};
+use crate::index::{self, INDEX_FILE};
+use crate::{cleanup, compress, format_size, CleanupOptions};
+use notify::event::ModifyKind;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+enum Message {
+    Event(notify::Result<notify::Event>),
+    Trigger,
+}
+
+// 仓库大小: 各目录(不含子目录)文件大小之和, 随文件事件更新
+struct Gauge {
+    sizes: HashMap<String, u64>,
+}
+
+impl Gauge {
+    fn load(repo_path: &Path) -> Self {
+        let sizes = index::load(&repo_path.join(INDEX_FILE))
+            .dirs
+            .into_iter()
+            .map(|(key, entry)| (key, entry.size))
+            .collect();
+        Gauge { sizes }
+    }
+
+    fn update(&mut self, repo_path: &Path, path: &Path) {
+        let key = index::key(repo_path, path);
+        match index::read_entry(path) {
+            Some(entry) => {
+                self.sizes.insert(key, entry.size);
+            }
+            None => {
+                let prefix = format!("{}/", key);
+                self.sizes
+                    .retain(|k, _| k != &key && !k.starts_with(&prefix));
+            }
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.sizes.values().sum::<u64>() as usize
+    }
+}
+
+pub fn run(
+    repo_path: PathBuf,
+    options: &CleanupOptions,
+    interval: Duration,
+    compress_age: Option<Duration>,
+    dashboard_address: Option<&str>,
+) {
+    let (tx, rx) = mpsc::channel();
+    let events = tx.clone();
+    let mut watcher = match notify::recommended_watcher(move |event| {
+        let _ = events.send(Message::Event(event));
+    }) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            log::error!("Failed to create watcher: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = watcher.watch(&repo_path, RecursiveMode::Recursive) {
+        log::error!("Failed to watch '{}': {}", repo_path.display(), e);
+        return;
+    }
+
+    let shared = Shared::default();
+    if let Some(address) = dashboard_address {
+        let (trigger, triggered) = mpsc::channel();
+        if let Err(e) =
+            dashboard::serve(address, repo_path.clone(), options, shared.clone(), trigger)
+        {
+            log::error!("Failed to start dashboard on '{}': {}", address, e);
+            return;
+        }
+        // 网页请求的清理与定时清理走同一条消息通道
+        thread::spawn(move || {
+            while triggered.recv().is_ok() {
+                if tx.send(Message::Trigger).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    let index_path = repo_path.join(INDEX_FILE);
+    // 首次运行基于 mtime 校验索引, 之后只重新检查监听到变化的目录
+    let mut changes: Option<HashSet<PathBuf>> = None;
+    let mut gauge = Gauge {
+        sizes: HashMap::new(),
+    };
+    // 间隔过大无法表示时只响应手动触发
+    let mut next_run = Some(Instant::now());
+    loop {
+        let timeout = next_run.map_or(Duration::MAX, |next_run| {
+            next_run.saturating_duration_since(Instant::now())
+        });
+        match rx.recv_timeout(timeout) {
+            Ok(Message::Event(Ok(event))) => {
+                // 只关心增删改, 读取目录本身也会产生访问事件
+                if matches!(
+                    event.kind,
+                    EventKind::Access(_) | EventKind::Modify(ModifyKind::Metadata(_))
+                ) {
+                    continue;
+                }
+                if event.need_rescan() {
+                    log::warn!("Watcher lost events, next run rescans the repository");
+                    changes = None;
+                    continue;
+                }
+                for path in event.paths.iter().filter(|p| **p != index_path) {
+                    let mut dirs = vec![path.as_path()];
+                    dirs.extend(path.parent());
+                    for dir in dirs.into_iter().filter(|d| d.starts_with(&repo_path)) {
+                        if let Some(changes) = &mut changes {
+                            changes.insert(dir.to_path_buf());
+                        }
+                        if !dir.is_file() {
+                            gauge.update(&repo_path, dir);
+                        }
+                    }
+                }
+                log::debug!("Repository size: {}", format_size(gauge.total()));
+            }
+            Ok(Message::Event(Err(e))) => log::error!("Watch error: {}", e),
+            Ok(Message::Trigger) | Err(RecvTimeoutError::Timeout) => {
+                log::info!("Cleaning up: {}", repo_path.display());
+                shared.started();
+                let summary = cleanup(repo_path.clone(), options, changes.take());
+                if let Some(age) = compress_age {
+                    compress::compress_older_than(&repo_path, age);
+                }
+                changes = Some(HashSet::new());
+                gauge = Gauge::load(&repo_path);
+                log::info!("Repository size: {}", format_size(gauge.total()));
+                shared.finished(summary, gauge.total());
+                next_run = Instant::now().checked_add(interval);
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+}
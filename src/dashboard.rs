@@ -0,0 +1,172 @@
+// 守护进程的本地网页: 仓库大小历史, 最近一次结果, 预览与立即清理
+use crate::fs::RepoFs;
+use crate::remove::{self, Candidate};
+use crate::summary::Summary;
+use crate::{Cleaner, CleanupOptions};
+use serde_json::json;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const HISTORY_LIMIT: usize = 1000;
+const PREVIEW_LIMIT: usize = 500;
+
+static PAGE: &str = include_str!("dashboard.html");
+
+#[derive(Default)]
+pub struct Status {
+    history: Vec<(u64, usize)>,
+    last_run: Option<(u64, Summary)>,
+    running: bool,
+}
+
+#[derive(Clone, Default)]
+pub struct Shared(Arc<Mutex<Status>>);
+
+impl Shared {
+    pub fn started(&self) {
+        self.0.lock().unwrap().running = true;
+    }
+
+    pub fn finished(&self, summary: Summary, size: usize) {
+        let mut status = self.0.lock().unwrap();
+        let now = now();
+        status.running = false;
+        status.last_run = Some((now, summary));
+        status.history.push((now, size));
+        if status.history.len() > HISTORY_LIMIT {
+            status.history.remove(0);
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_secs())
+}
+
+// trigger 用于通知守护进程立即执行一次清理
+pub fn serve(
+    address: &str,
+    repo_path: PathBuf,
+    options: &CleanupOptions,
+    shared: Shared,
+    trigger: Sender<()>,
+) -> io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    log::info!("Dashboard: http://{}", listener.local_addr()?);
+    // 预览不写入增量索引, 结果与完整扫描一致
+    let options = CleanupOptions {
+        incremental: false,
+        ..options.clone()
+    };
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let result =
+                stream.and_then(|stream| handle(stream, &repo_path, &options, &shared, &trigger));
+            if let Err(e) = result {
+                log::debug!("Dashboard request failed: {}", e);
+            }
+        }
+    });
+    Ok(())
+}
+
+fn handle(
+    mut stream: TcpStream,
+    repo_path: &Path,
+    options: &CleanupOptions,
+    shared: &Shared,
+    trigger: &Sender<()>,
+) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // 忽略请求头和请求体
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+    match (method, target) {
+        ("GET", "/") => respond(&mut stream, "200 OK", "text/html; charset=utf-8", PAGE),
+        ("GET", "/api/status") => {
+            let status = shared.0.lock().unwrap();
+            let body = json!({
+                "repository": repo_path,
+                "running": status.running,
+                "history": status
+                    .history
+                    .iter()
+                    .map(|(time, size)| json!({"time": time, "size": size}))
+                    .collect::<Vec<_>>(),
+                "last_run": status
+                    .last_run
+                    .as_ref()
+                    .map(|(time, summary)| json!({"time": time, "summary": summary})),
+            });
+            drop(status);
+            respond_json(&mut stream, &body)
+        }
+        ("POST", "/api/preview") => {
+            let candidates = Cleaner::new(repo_path.to_path_buf(), options.clone()).scan();
+            let body = preview(&candidates, &*options.fs);
+            respond_json(&mut stream, &body)
+        }
+        ("POST", "/api/run") => {
+            if shared.0.lock().unwrap().running {
+                return respond(&mut stream, "409 Conflict", "text/plain", "already running");
+            }
+            let _ = trigger.send(());
+            respond(&mut stream, "202 Accepted", "text/plain", "started")
+        }
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "not found"),
+    }
+}
+
+fn preview(candidates: &[Candidate], fs: &dyn RepoFs) -> serde_json::Value {
+    let files = remove::planned_files(fs, candidates);
+    let total_size: usize = files.iter().map(|(_, size)| size).sum();
+    let shown: Vec<_> = candidates
+        .iter()
+        .take(PREVIEW_LIMIT)
+        .map(|candidate| {
+            let size: usize = remove::planned_files(fs, std::slice::from_ref(candidate))
+                .iter()
+                .map(|(_, size)| size)
+                .sum();
+            json!({"path": candidate.path, "reason": candidate.reason, "size": size})
+        })
+        .collect();
+    json!({
+        "candidates": shown,
+        "count": candidates.len(),
+        "files": files.len(),
+        "total_size": total_size,
+    })
+}
+
+fn respond_json(stream: &mut TcpStream, body: &serde_json::Value) -> io::Result<()> {
+    respond(stream, "200 OK", "application/json", &body.to_string())
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,254 @@
+use crate::gav::Gav;
+use crate::{format_size, get_file_name, hash, is_artifact, is_walkable_dir, read_dir_sorted};
+use crate::{fs, layout};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+static JAR_SUFFIX: &str = ".jar";
+
+// 以不同坐标发布的相同 jar, 如 relocation 后的新旧坐标或 shade 进其他构件的副本
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdenticalSet {
+    pub size: u64,
+    pub digest: String,
+    pub files: Vec<(Gav, PathBuf)>,
+}
+
+impl IdenticalSet {
+    // 只保留一份时可以省下的空间
+    pub fn waste(&self) -> u64 {
+        self.size * (self.files.len() as u64 - 1)
+    }
+}
+
+pub fn dedup(repo_paths: Vec<PathBuf>, dry_run: bool) {
+    let mut linked: usize = 0;
+    let mut saved_size: usize = 0;
+    let mut unlinkable_size: usize = 0;
+    for (size, _, paths) in group_identical(scan_artifacts(repo_paths)) {
+        // 硬链接不能跨文件系统, 每个文件系统各保留一份
+        let mut by_device: BTreeMap<Option<u64>, Vec<PathBuf>> = BTreeMap::new();
+        for path in paths {
+            by_device.entry(fs::device(&path)).or_default().push(path);
+        }
+        if by_device.len() > 1 {
+            let originals: Vec<String> = by_device
+                .values()
+                .map(|paths| paths[0].display().to_string())
+                .collect();
+            log::info!(
+                "Identical across filesystems (report only): {}",
+                originals.join(", ")
+            );
+            unlinkable_size += size as usize * (by_device.len() - 1);
+        }
+        for paths in by_device.values() {
+            let original = &paths[0];
+            for duplicate in &paths[1..] {
+                if is_same_file(original, duplicate) {
+                    continue;
+                }
+                log::info!("Linking: {} -> {}", duplicate.display(), original.display());
+                if dry_run {
+                    linked += 1;
+                    saved_size += size as usize;
+                    continue;
+                }
+                match replace_with_link(original, duplicate) {
+                    Ok(_) => {
+                        linked += 1;
+                        saved_size += size as usize;
+                    }
+                    Err(e) => log::error!("Failed to link '{}': {}", duplicate.display(), e),
+                }
+            }
+        }
+    }
+
+    log::info!("Linked files: {}", linked);
+    log::info!("Saved size: {}", format_size(saved_size));
+    if unlinkable_size > 0 {
+        log::info!(
+            "Duplicated across filesystems: {}",
+            format_size(unlinkable_size)
+        );
+    }
+}
+
+// 同一坐标下的相同文件 (如快照的时间戳构建与本地副本) 不算在内, 按浪费的空间从大到小排列
+pub fn identical_artifacts(repo_path: &Path) -> Vec<IdenticalSet> {
+    let jars = scan_artifacts(vec![repo_path.to_path_buf()])
+        .into_iter()
+        .filter(|(path, _)| get_file_name(path).is_some_and(|name| name.ends_with(JAR_SUFFIX)))
+        .collect();
+    let mut sets: Vec<IdenticalSet> = group_identical(jars)
+        .into_iter()
+        .filter_map(|(size, digest, paths)| {
+            let files: Vec<(Gav, PathBuf)> = paths
+                .into_iter()
+                .filter_map(|path| Some((layout::gav(repo_path, path.parent()?)?, path)))
+                .collect();
+            let coordinates: BTreeSet<String> =
+                files.iter().map(|(gav, _)| gav.to_string()).collect();
+            (coordinates.len() > 1).then_some(IdenticalSet {
+                size,
+                digest,
+                files,
+            })
+        })
+        .collect();
+    sets.sort_by_key(|set| std::cmp::Reverse(set.waste()));
+    sets
+}
+
+pub fn report_identical(repo_path: &Path) {
+    let sets = identical_artifacts(repo_path);
+    for set in &sets {
+        log::info!(
+            "Identical {} jars, {} each, {} wasted (sha256 {}):",
+            set.files.len(),
+            format_size(set.size as usize),
+            format_size(set.waste() as usize),
+            &set.digest[..12]
+        );
+        for (gav, path) in &set.files {
+            log::info!("  {} ({})", gav, get_file_name(path).unwrap_or_default());
+        }
+    }
+    let waste: u64 = sets.iter().map(IdenticalSet::waste).sum();
+    log::info!("Identical sets: {}", sets.len());
+    log::info!("Combined waste: {}", format_size(waste as usize));
+}
+
+// 先按大小分组, 只对大小相同的文件计算哈希; 每组中的路径已排序
+fn group_identical(artifacts: Vec<(PathBuf, u64)>) -> Vec<(u64, String, Vec<PathBuf>)> {
+    let mut by_size: BTreeMap<u64, Vec<PathBuf>> = BTreeMap::new();
+    for (path, size) in artifacts {
+        by_size.entry(size).or_default().push(path);
+    }
+    let mut groups = Vec::new();
+    for (size, paths) in by_size.into_iter().filter(|(_, paths)| paths.len() > 1) {
+        let mut by_hash: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+        for path in paths {
+            match hash::sha256_file(&path) {
+                Ok(digest) => by_hash.entry(digest).or_default().push(path),
+                Err(e) => log::error!("Failed to hash '{}': {}", path.display(), e),
+            }
+        }
+        for (digest, mut paths) in by_hash.into_iter().filter(|(_, paths)| paths.len() > 1) {
+            paths.sort();
+            groups.push((size, digest, paths));
+        }
+    }
+    groups
+}
+
+// 实验性: 构件内容移入按哈希寻址的存储, 仓库中只保留硬链接
+pub fn compact(repo_paths: Vec<PathBuf>, store_path: PathBuf, dry_run: bool) {
+    let mut stored: usize = 0;
+    let mut linked: usize = 0;
+    let mut saved_size: usize = 0;
+    for (path, size) in scan_artifacts(repo_paths) {
+        if path.starts_with(&store_path) {
+            continue;
+        }
+        let digest = match hash::sha256_file(&path) {
+            Ok(digest) => digest,
+            Err(e) => {
+                log::error!("Failed to hash '{}': {}", path.display(), e);
+                continue;
+            }
+        };
+        let blob = store_path.join(&digest[..2]).join(&digest);
+        if !blob.exists() {
+            log::debug!("Storing: {} -> {}", path.display(), blob.display());
+            stored += 1;
+            if dry_run {
+                continue;
+            }
+            let result = blob
+                .parent()
+                .map(std::fs::create_dir_all)
+                .unwrap_or(Ok(()))
+                .and_then(|_| std::fs::hard_link(&path, &blob));
+            if let Err(e) = result {
+                log::error!("Failed to store '{}': {}", path.display(), e);
+            }
+            continue;
+        }
+        if is_same_file(&blob, &path) {
+            continue;
+        }
+        log::info!("Linking: {} -> {}", path.display(), blob.display());
+        if !dry_run {
+            if let Err(e) = replace_with_link(&blob, &path) {
+                log::error!("Failed to link '{}': {}", path.display(), e);
+                continue;
+            }
+        }
+        linked += 1;
+        saved_size += size as usize;
+    }
+
+    log::info!("Stored files: {}", stored);
+    log::info!("Linked files: {}", linked);
+    log::info!("Saved size: {}", format_size(saved_size));
+}
+
+fn scan_artifacts(repo_paths: Vec<PathBuf>) -> Vec<(PathBuf, u64)> {
+    let mut artifacts = Vec::new();
+    let mut queue: VecDeque<PathBuf> = repo_paths.into_iter().collect();
+    while let Some(path) = queue.pop_front() {
+        let folder = match read_dir_sorted(&path) {
+            Ok(folder) => folder,
+            Err(e) => {
+                log::error!("Failed to read directory '{}': {}", path.display(), e);
+                continue;
+            }
+        };
+        log::debug!("Scanning: {}", path.display());
+        for entry in folder.flatten() {
+            let entry_path = entry.path();
+            if is_walkable_dir(&fs::RealFs, &entry_path) {
+                queue.push_back(entry_path);
+                continue;
+            }
+            if !get_file_name(&entry_path).is_some_and(|f| is_artifact(&f)) {
+                continue;
+            }
+            match entry.metadata() {
+                Ok(metadata) if metadata.len() > 0 => artifacts.push((entry_path, metadata.len())),
+                Ok(_) => {}
+                Err(e) => log::error!("Failed to read metadata '{}': {}", entry_path.display(), e),
+            }
+        }
+    }
+    artifacts
+}
+
+// 先在同目录创建临时硬链接再 rename 覆盖, 避免中途失败丢失文件
+fn replace_with_link(original: &Path, duplicate: &Path) -> std::io::Result<()> {
+    let mut temp = duplicate.as_os_str().to_owned();
+    temp.push(".mrc-link");
+    let temp = PathBuf::from(temp);
+    std::fs::hard_link(original, &temp)?;
+    if let Err(e) = std::fs::rename(&temp, duplicate) {
+        let _ = std::fs::remove_file(&temp);
+        return Err(e);
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (std::fs::metadata(a), std::fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_same_file(_a: &Path, _b: &Path) -> bool {
+    false
+}
@@ -0,0 +1,76 @@
+use crate::human_size;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+// --report 写出的 JSON, 只读取删除清单
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    deleted: Vec<Entry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Entry {
+    path: PathBuf,
+    reason: String,
+}
+
+pub fn load(path: &Path) -> Result<Vec<(PathBuf, String)>, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let manifest: Manifest = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    Ok(manifest
+        .deleted
+        .into_iter()
+        .map(|entry| (entry.path, entry.reason))
+        .collect())
+}
+
+// 上次清理删除的文件又出现, 说明有构建在反复拉取被策略淘汰的内容
+pub fn diff(manifest: &Path) -> bool {
+    let deleted = match load(manifest) {
+        Ok(deleted) => deleted,
+        Err(e) => {
+            log::error!("Invalid manifest '{}': {}", manifest.display(), e);
+            return false;
+        }
+    };
+    let mut reappeared: BTreeMap<PathBuf, Vec<(String, String, usize)>> = BTreeMap::new();
+    for (path, reason) in &deleted {
+        let size = match std::fs::metadata(path) {
+            Ok(metadata) if metadata.is_file() => metadata.len() as usize,
+            _ => continue,
+        };
+        let (folder, file_name) = match (path.parent(), path.file_name()) {
+            (Some(folder), Some(file_name)) => (folder, file_name),
+            _ => continue,
+        };
+        reappeared.entry(folder.to_path_buf()).or_default().push((
+            file_name.to_string_lossy().to_string(),
+            reason.clone(),
+            size,
+        ));
+    }
+    let mut files = 0;
+    let mut total_size = 0;
+    for (folder, entries) in &reappeared {
+        println!("{}", folder.display());
+        for (file_name, reason, size) in entries {
+            println!(
+                "  {:<60} {:<26} {:>12}",
+                file_name,
+                reason,
+                human_size(*size)
+            );
+            files += 1;
+            total_size += size;
+        }
+    }
+    log::info!("Deleted files in manifest: {}", deleted.len());
+    log::info!(
+        "Reappeared files: {} ({}) in {} directories",
+        files,
+        human_size(total_size),
+        reappeared.len()
+    );
+    true
+}
@@ -0,0 +1,253 @@
+use crate::fs::{RealFs, RepoFs};
+use crate::layout::{self, CHECKSUM_SUFFIXES};
+use crate::policy::LOCK_AGE;
+use crate::remote_repositories::{self, REMOTE_REPOSITORIES_FILE};
+use crate::{get_file_name, integrity, is_artifact, is_walkable_dir, read_dir_sorted};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+pub static LAST_UPDATED_SUFFIX: &str = ".lastUpdated";
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Problem {
+    // 从远程仓库下载, 却没有任何校验文件
+    MissingChecksum,
+    Corrupt(String),
+    // 对应的构件不存在的校验文件、下载失败标记和 _remote.repositories
+    Dangling,
+    // 属主与仓库根目录不同, 或属主不可写
+    Permissions(String),
+    AbandonedLock,
+    // 没有列在快照元数据中的时间戳构建
+    StaleSnapshot,
+}
+
+impl Problem {
+    fn kind(&self) -> &'static str {
+        match self {
+            Problem::MissingChecksum => "missing-checksum",
+            Problem::Corrupt(_) => "corrupt",
+            Problem::Dangling => "dangling-metadata",
+            Problem::Permissions(_) => "permissions",
+            Problem::AbandonedLock => "abandoned-lock",
+            Problem::StaleSnapshot => "stale-snapshot",
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Corrupt(detail) | Problem::Permissions(detail) => {
+                write!(f, "{} ({})", self.kind(), detail)
+            }
+            _ => f.write_str(self.kind()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub path: PathBuf,
+    pub problem: Problem,
+}
+
+// 只读取, 不修改仓库
+pub fn diagnose(repo_path: &Path) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let owner = std::fs::metadata(repo_path).ok().and_then(|m| owner(&m));
+    let mut queue = VecDeque::new();
+    queue.push_back(repo_path.to_path_buf());
+    while let Some(path) = queue.pop_front() {
+        log::debug!("Scanning: {}", path.display());
+        let folder = match read_dir_sorted(&path) {
+            Ok(folder) => folder,
+            Err(e) => {
+                log::error!("Failed to read directory '{}': {}", path.display(), e);
+                continue;
+            }
+        };
+        let mut files = Vec::new();
+        for entry in folder {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    log::error!("Failed to read directory entry: {:?}", e);
+                    continue;
+                }
+            };
+            let entry_path = entry.path();
+            if let Some(detail) = entry_path
+                .symlink_metadata()
+                .ok()
+                .and_then(|metadata| permissions(&metadata, owner))
+            {
+                findings.push(Finding {
+                    path: entry_path.clone(),
+                    problem: Problem::Permissions(detail),
+                });
+            }
+            if is_walkable_dir(&RealFs, &entry_path) {
+                queue.push_back(entry_path);
+            } else if let Some(name) = get_file_name(&entry_path) {
+                files.push(name);
+            }
+        }
+        diagnose_folder(repo_path, &path, &files, &mut findings);
+    }
+    findings
+}
+
+fn diagnose_folder(repo_path: &Path, path: &Path, files: &[String], findings: &mut Vec<Finding>) {
+    let mut found = |name: &str, problem| {
+        findings.push(Finding {
+            path: path.join(name),
+            problem,
+        })
+    };
+    let has = |name: &str| files.iter().any(|file| file == name);
+    let sources = match has(REMOTE_REPOSITORIES_FILE) {
+        true => remote_repositories::read(&RealFs, &path.join(REMOTE_REPOSITORIES_FILE)),
+        false => Default::default(),
+    };
+    let gav = layout::gav(repo_path, path);
+    let published = layout::published(&RealFs, path);
+    let ignore_case = RealFs.is_case_insensitive(repo_path);
+    let mut artifacts = 0;
+    for name in files {
+        let file = path.join(name);
+        if layout::is_lock(repo_path, &file) {
+            if is_older(&file, LOCK_AGE) && !RealFs.is_locked(&file) {
+                found(name, Problem::AbandonedLock);
+            }
+            continue;
+        }
+        if name.ends_with(LAST_UPDATED_SUFFIX) {
+            found(name, Problem::Dangling);
+            continue;
+        }
+        if let Some(target) = CHECKSUM_SUFFIXES
+            .iter()
+            .find_map(|suffix| name.strip_suffix(suffix))
+        {
+            if !has(target) {
+                found(name, Problem::Dangling);
+            }
+            continue;
+        }
+        if !is_artifact(name) || layout::is_metadata(name) {
+            continue;
+        }
+        artifacts += 1;
+        if gav
+            .as_ref()
+            .is_some_and(|gav| layout::is_stale_build(gav, name, ignore_case, &published))
+        {
+            found(name, Problem::StaleSnapshot);
+            continue;
+        }
+        if let Some(Err(e)) = integrity::check_file(&path.join(name), name) {
+            found(name, Problem::Corrupt(e.to_string()));
+        }
+        // 本地安装的构件 (来源为空) 本来就没有校验文件
+        let downloaded = sources.get(name).is_some_and(|source| !source.is_empty());
+        if downloaded
+            && !CHECKSUM_SUFFIXES
+                .iter()
+                .any(|suffix| has(&format!("{}{}", name, suffix)))
+        {
+            found(name, Problem::MissingChecksum);
+        }
+    }
+    if artifacts == 0 && has(REMOTE_REPOSITORIES_FILE) {
+        found(REMOTE_REPOSITORIES_FILE, Problem::Dangling);
+    }
+}
+
+fn is_older(path: &Path, age: Duration) -> bool {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|elapsed| elapsed > age)
+}
+
+#[cfg(unix)]
+fn owner(metadata: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.uid())
+}
+
+#[cfg(not(unix))]
+fn owner(_metadata: &Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn permissions(metadata: &Metadata, repo_owner: Option<u32>) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+    if metadata.is_symlink() {
+        return None;
+    }
+    if let Some(repo_owner) = repo_owner.filter(|&uid| uid != metadata.uid()) {
+        return Some(format!(
+            "owned by uid {}, repository by uid {}",
+            metadata.uid(),
+            repo_owner
+        ));
+    }
+    // 目录还需要可进入
+    let required = if metadata.is_dir() { 0o700 } else { 0o600 };
+    (metadata.mode() & required != required).then(|| format!("mode {:o}", metadata.mode() & 0o7777))
+}
+
+#[cfg(not(unix))]
+fn permissions(metadata: &Metadata, _repo_owner: Option<u32>) -> Option<String> {
+    metadata
+        .permissions()
+        .readonly()
+        .then(|| "read-only".to_string())
+}
+
+pub fn doctor(repo_path: &Path) -> bool {
+    let findings = diagnose(repo_path);
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for finding in &findings {
+        log::warn!("{}: {}", finding.problem, finding.path.display());
+        *counts.entry(finding.problem.kind()).or_default() += 1;
+    }
+    for (kind, count) in counts {
+        match kind {
+            "missing-checksum" => log::info!(
+                "Artifacts without checksums: {}, delete them so Maven downloads and verifies them again",
+                count
+            ),
+            "corrupt" => log::info!(
+                "Corrupt files: {}, run `mrc integrity --delete` so Maven refetches them",
+                count
+            ),
+            "dangling-metadata" => log::info!(
+                "Dangling metadata: {}, safe to delete, Maven recreates it when needed",
+                count
+            ),
+            "permissions" => log::info!(
+                "Files with mixed ownership or permissions: {}, fix them with chown/chmod so builds and cleanups can replace them",
+                count
+            ),
+            "abandoned-lock" => log::info!(
+                "Abandoned locks: {}, delete them when no Maven build is running",
+                count
+            ),
+            "stale-snapshot" => log::info!(
+                "Snapshot builds no longer listed in their metadata: {}, run `mrc` to delete them",
+                count
+            ),
+            _ => {}
+        }
+    }
+    log::info!("Findings: {}", findings.len());
+    findings.is_empty()
+}
@@ -0,0 +1,55 @@
+use crate::fs::RepoFs;
+use crate::get_file_name;
+use crate::layout::LOCK_SUFFIX;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+static PART_SUFFIX: &str = ".part";
+
+// 下载过程中 .part 文件持续写入; 更久未修改的是中断后遗留的, 修改时间未知时按正在下载处理
+pub const ACTIVE_AGE: Duration = Duration::from_secs(10 * 60);
+
+// 目录中有最近写入的 .part 文件或仍被持有的锁文件时, 构建可能正在下载到该目录
+pub fn in_progress(fs: &dyn RepoFs, folder: &Path) -> bool {
+    let entries = match fs.read_dir(folder) {
+        Ok(entries) => entries,
+        Err(_) => return false,
+    };
+    entries.into_iter().flatten().any(|path| {
+        let Some(file_name) = get_file_name(&path) else {
+            return false;
+        };
+        if file_name.ends_with(PART_SUFFIX) {
+            fs.metadata(&path)
+                .ok()
+                .and_then(|metadata| metadata.modified)
+                .and_then(|modified| modified.elapsed().ok())
+                .is_none_or(|elapsed| elapsed < ACTIVE_AGE)
+        } else {
+            file_name.ends_with(LOCK_SUFFIX) && fs.is_locked(&path)
+        }
+    })
+}
+
+// 同一目录的文件连续到达, 只需检查一次
+#[derive(Default)]
+pub struct ActiveDownloads {
+    folder: Option<PathBuf>,
+    active: bool,
+}
+
+impl ActiveDownloads {
+    pub fn contains(&mut self, fs: &dyn RepoFs, path: &Path) -> bool {
+        let Some(folder) = path.parent() else {
+            return false;
+        };
+        if self.folder.as_deref() != Some(folder) {
+            self.active = in_progress(fs, folder);
+            if self.active {
+                log::info!("Download in progress, skipping: {}", folder.display());
+            }
+            self.folder = Some(folder.to_path_buf());
+        }
+        self.active
+    }
+}
@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+// 支持: 30s, 10m, 12h, 7d, 2w, 1y
+pub fn parse(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| format!("missing unit: {}", text))?;
+    let (number, unit) = text.split_at(split);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid number: {}", text))?;
+    let seconds = match unit {
+        "ms" => return Ok(Duration::from_millis(number)),
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        "y" => 365 * 24 * 60 * 60,
+        _ => return Err(format!("invalid unit: {}", unit)),
+    };
+    number
+        .checked_mul(seconds)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("duration too large: {}", text))
+}
+
+pub fn format(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    match seconds {
+        s if s >= 60 * 60 => format!("{}h{}m{}s", s / 3600, s % 3600 / 60, s % 60),
+        s if s >= 60 => format!("{}m{}s", s / 60, s % 60),
+        s if s > 0 => format!("{}s", s),
+        _ => format!("{}ms", duration.as_millis()),
+    }
+}
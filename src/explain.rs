@@ -0,0 +1,109 @@
+use crate::policy::{Decision, LatestVersions};
+use crate::remote_repositories::{SourceCache, REMOTE_REPOSITORIES_FILE};
+use crate::ttl::ExpiryCache;
+use crate::{downloads, get_file_name, layout, pins, CleanupOptions};
+use std::path::Path;
+
+// 只对一个文件运行判定逻辑, 逐条输出规则的匹配情况和最终结果, 不扫描仓库
+pub fn explain_path(repo_path: &Path, path: &Path, options: &CleanupOptions) -> bool {
+    let options = &*pins::apply(repo_path, options);
+    let fs = &*options.fs;
+    match fs.metadata(path) {
+        Ok(metadata) if !metadata.is_dir => {}
+        Ok(_) => {
+            log::error!("Not a file: {}", path.display());
+            return false;
+        }
+        Err(e) => {
+            log::error!("Failed to read '{}': {}", path.display(), e);
+            return false;
+        }
+    }
+    if path.strip_prefix(repo_path).is_err() {
+        log::error!(
+            "'{}' is not inside the repository '{}'",
+            path.display(),
+            repo_path.display()
+        );
+        return false;
+    }
+    println!("{}", path.display());
+    let ignore_case = fs.is_case_insensitive(repo_path);
+    match layout::locate(repo_path, path, ignore_case, &[]).and_then(|entry| entry.gav) {
+        Some(gav) => println!("  coordinates: {}", gav),
+        None => println!("  coordinates: (none, not inside a version directory)"),
+    }
+    let outcome = |decision: &dyn std::fmt::Display, by: &str| {
+        println!("  outcome: {} ({})", decision, by);
+        true
+    };
+    if options.protected_groups.is_protected(repo_path, path) {
+        return outcome(&"keep", "protected group");
+    }
+    if options.workspace.is_kept(repo_path, path) {
+        return outcome(&"keep", "declared by a --workspace project");
+    }
+    if options.pins.is_pinned(fs, repo_path, path) {
+        return outcome(&"keep", "pinned by .mvnclean-pins");
+    }
+    if let Some(expiry) = ExpiryCache::default().active(fs, path) {
+        return outcome(&"keep", &expiry.to_string());
+    }
+    if path
+        .parent()
+        .is_some_and(|folder| downloads::in_progress(fs, folder))
+    {
+        return outcome(&"keep", "download in progress");
+    }
+    if options.known_repositories.is_some()
+        && get_file_name(path).is_some_and(|name| name == REMOTE_REPOSITORIES_FILE)
+    {
+        return outcome(&"keep", "checked for decommissioned repositories instead");
+    }
+    if let Some(from_repo) = &options.from_repo {
+        let mut sources = SourceCache::default();
+        match sources.source(fs, path) {
+            Some(source) if source == from_repo => {
+                println!("  source: {}", source);
+            }
+            source => {
+                println!("  source: {}", source.map_or("(unknown)", String::as_str));
+                return outcome(&"keep", &format!("not from --from-repo {}", from_repo));
+            }
+        }
+    }
+    #[cfg(feature = "plugins")]
+    if let Some(decision) = options.plugins.evaluate(repo_path, path) {
+        return filtered(options, decision, "plugin");
+    }
+    let mut latest = LatestVersions::default();
+    let (decision, steps) = options.rules.trace(fs, repo_path, path, &mut latest);
+    for step in &steps {
+        match step.mismatch {
+            Some(condition) => println!(
+                "  rule {} ({}): no match, {} differs",
+                step.rule, step.action, condition
+            ),
+            None => println!("  rule {} ({}): match", step.rule, step.action),
+        }
+    }
+    match steps.last().filter(|step| step.mismatch.is_none()) {
+        Some(step) => filtered(options, decision, &format!("rule {}", step.rule)),
+        None => outcome(&decision, "no rule matched"),
+    }
+}
+
+fn filtered(options: &CleanupOptions, decision: Decision, by: &str) -> bool {
+    match decision {
+        Decision::Delete(reason) | Decision::Archive(reason) | Decision::DeleteDir(reason)
+            if !options.reasons.allows(reason) =>
+        {
+            println!(
+                "  outcome: keep ({} as {}, filtered by --only-reason/--skip-reason)",
+                by, reason
+            );
+        }
+        decision => println!("  outcome: {} ({})", decision, by),
+    }
+    true
+}
@@ -0,0 +1,183 @@
+use crate::hash;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// 生成用于测试和基准的合成仓库
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    pub groups: usize,
+    pub artifacts: usize,
+    pub versions: usize,
+    pub snapshots: usize,
+    pub builds: usize,
+    pub corrupt: usize,
+    pub size: usize,
+    pub seed: u64,
+}
+
+pub fn generate(repo_path: &Path, fixture: &Fixture) -> io::Result<()> {
+    let mut random = Random(fixture.seed.max(1));
+    let mut jars = Vec::new();
+    let mut poms = Vec::new();
+    for g in 0..fixture.groups {
+        let group_path = repo_path
+            .join("com")
+            .join("fixture")
+            .join(format!("g{}", g));
+        for a in 0..fixture.artifacts {
+            let artifact_id = format!("a{}", a);
+            let artifact_path = group_path.join(&artifact_id);
+            for v in 0..fixture.versions {
+                let version = format!("1.{}.0", v);
+                let version_path = artifact_path.join(&version);
+                std::fs::create_dir_all(&version_path)?;
+                let base = format!("{}-{}", artifact_id, version);
+                jars.push(write_jar(&version_path, &base, fixture.size, &mut random)?);
+                poms.push(write_pom(&version_path, &base, g, &artifact_id, &version)?);
+                let remote = format!("{0}.jar>central=\n{0}.pom>central=\n", base);
+                std::fs::write(version_path.join("_remote.repositories"), remote)?;
+            }
+            for s in 0..fixture.snapshots {
+                let version = format!("2.{}-SNAPSHOT", s);
+                let version_path = artifact_path.join(&version);
+                std::fs::create_dir_all(&version_path)?;
+                for b in 1..=fixture.builds {
+                    let base = format!(
+                        "{}-2.{}-202401{:02}.120000-{}",
+                        artifact_id,
+                        s,
+                        b.min(28),
+                        b
+                    );
+                    write_jar(&version_path, &base, fixture.size, &mut random)?;
+                    write_pom(&version_path, &base, g, &artifact_id, &version)?;
+                }
+                let base = format!("{}-{}", artifact_id, version);
+                write_jar(&version_path, &base, fixture.size, &mut random)?;
+                write_pom(&version_path, &base, g, &artifact_id, &version)?;
+                std::fs::write(
+                    version_path.join("maven-metadata-local.xml"),
+                    "<metadata/>\n",
+                )?;
+            }
+            if fixture.snapshots > 0 {
+                std::fs::write(
+                    artifact_path.join("maven-metadata-local.xml"),
+                    "<metadata/>\n",
+                )?;
+            }
+        }
+    }
+
+    // 损坏的 jar 被截断, 损坏的 POM 缺少结束标签
+    for i in 0..fixture.corrupt.min(jars.len() + poms.len()) {
+        if i % 2 == 0 && !jars.is_empty() {
+            let jar = jars.swap_remove(random.below(jars.len()));
+            let content = std::fs::read(&jar)?;
+            std::fs::write(&jar, &content[..content.len() / 2])?;
+            log::debug!("Corrupted: {}", jar.display());
+        } else if !poms.is_empty() {
+            let pom = poms.swap_remove(random.below(poms.len()));
+            std::fs::write(&pom, "<project>\n")?;
+            log::debug!("Corrupted: {}", pom.display());
+        }
+    }
+    Ok(())
+}
+
+fn write_jar(dir: &Path, base: &str, size: usize, random: &mut Random) -> io::Result<PathBuf> {
+    let path = dir.join(format!("{}.jar", base));
+    let content: Vec<u8> = (0..size).map(|_| random.next() as u8).collect();
+    std::fs::write(&path, stored_zip("data.bin", &content))?;
+    write_sha1(&path)?;
+    Ok(path)
+}
+
+fn write_pom(
+    dir: &Path,
+    base: &str,
+    group: usize,
+    artifact_id: &str,
+    version: &str,
+) -> io::Result<PathBuf> {
+    let path = dir.join(format!("{}.pom", base));
+    let content = format!(
+        "<project>\n  <groupId>com.fixture.g{}</groupId>\n  <artifactId>{}</artifactId>\n  <version>{}</version>\n</project>\n",
+        group, artifact_id, version
+    );
+    std::fs::write(&path, content)?;
+    write_sha1(&path)?;
+    Ok(path)
+}
+
+fn write_sha1(path: &Path) -> io::Result<()> {
+    let mut checksum = path.as_os_str().to_owned();
+    checksum.push(".sha1");
+    std::fs::write(PathBuf::from(checksum), hash::sha1_file(path)?)
+}
+
+// 只含一个未压缩条目的最小 zip
+fn stored_zip(name: &str, content: &[u8]) -> Vec<u8> {
+    let crc = crc32(content);
+    let mut zip = Vec::new();
+    let header = |zip: &mut Vec<u8>, signature: u32, central: bool| {
+        zip.extend(signature.to_le_bytes());
+        if central {
+            zip.extend(20u16.to_le_bytes());
+        }
+        zip.extend(20u16.to_le_bytes());
+        zip.extend([0u8; 4]);
+        zip.extend([0u8; 4]);
+        zip.extend(crc.to_le_bytes());
+        zip.extend((content.len() as u32).to_le_bytes());
+        zip.extend((content.len() as u32).to_le_bytes());
+        zip.extend((name.len() as u16).to_le_bytes());
+        zip.extend(0u16.to_le_bytes());
+    };
+    header(&mut zip, 0x04034b50, false);
+    zip.extend(name.as_bytes());
+    zip.extend(content);
+    let cd_offset = zip.len() as u32;
+    header(&mut zip, 0x02014b50, true);
+    // 注释长度, 磁盘号, 内部属性, 外部属性, 本地文件头偏移
+    zip.extend([0u8; 6]);
+    zip.extend([0u8; 4]);
+    zip.extend(0u32.to_le_bytes());
+    zip.extend(name.as_bytes());
+    let cd_size = zip.len() as u32 - cd_offset;
+    zip.extend(0x06054b50u32.to_le_bytes());
+    zip.extend([0u8; 4]);
+    zip.extend(1u16.to_le_bytes());
+    zip.extend(1u16.to_le_bytes());
+    zip.extend(cd_size.to_le_bytes());
+    zip.extend(cd_offset.to_le_bytes());
+    zip.extend(0u16.to_le_bytes());
+    zip
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB88320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+// xorshift64, 相同种子生成相同仓库
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
@@ -0,0 +1,259 @@
+use crate::read_dir_sorted;
+use std::collections::HashMap;
+use std::io;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Metadata {
+    pub is_dir: bool,
+    pub is_symlink: bool,
+    pub len: u64,
+    // 所在文件系统的设备号, 平台不支持时为 None
+    pub device: Option<u64>,
+    pub modified: Option<SystemTime>,
+}
+
+// 清理逻辑通过该接口访问文件系统, 便于用替身测试
+pub trait RepoFs: Send + Sync {
+    // 按文件名排序, 读取失败的条目排在最后
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<io::Result<PathBuf>>>;
+
+    fn metadata(&self, path: &Path) -> io::Result<Metadata>;
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+
+    // 创建或覆盖文件, 如改写元数据
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()>;
+
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()>;
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok_and(|metadata| metadata.is_dir)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok_and(|metadata| !metadata.is_dir)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok()
+    }
+
+    // 扩展属性, 不存在或不支持时为 None
+    fn xattr(&self, _path: &Path, _name: &str) -> io::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    // 是否有进程持有该文件的记录锁 (Java 的 FileChannel.lock), 无法判断时为 false
+    fn is_locked(&self, _path: &Path) -> bool {
+        false
+    }
+
+    // 仓库所在文件系统是否不区分大小写 (Windows, 默认的 macOS)
+    fn is_case_insensitive(&self, _repo_path: &Path) -> bool {
+        false
+    }
+}
+
+pub struct RealFs;
+
+impl RepoFs for RealFs {
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<io::Result<PathBuf>>> {
+        Ok(read_dir_sorted(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect())
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        // 符号链接报告目标的类型和大小, 悬空链接按链接本身处理
+        let link = std::fs::symlink_metadata(path)?;
+        let is_symlink = link.file_type().is_symlink();
+        let metadata = match is_symlink {
+            true => std::fs::metadata(path).unwrap_or(link),
+            false => link,
+        };
+        Ok(Metadata {
+            is_dir: metadata.is_dir(),
+            is_symlink,
+            len: metadata.len(),
+            device: metadata_device(&metadata),
+            modified: metadata.modified().ok(),
+        })
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        std::fs::write(path, contents)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        std::fs::remove_file(path)
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        std::fs::remove_dir_all(path)
+    }
+
+    fn xattr(&self, path: &Path, name: &str) -> io::Result<Option<Vec<u8>>> {
+        match crate::xattr::get(path, name) {
+            Err(e) if e.kind() == io::ErrorKind::Unsupported => Ok(None),
+            result => result,
+        }
+    }
+
+    fn is_locked(&self, path: &Path) -> bool {
+        is_locked(path)
+    }
+
+    fn is_case_insensitive(&self, repo_path: &Path) -> bool {
+        static DETECTED: OnceLock<Mutex<HashMap<PathBuf, bool>>> = OnceLock::new();
+        let mut detected = DETECTED.get_or_init(Default::default).lock().unwrap();
+        *detected.entry(repo_path.to_path_buf()).or_insert_with(|| {
+            let case_insensitive = detect_case_insensitive(repo_path);
+            if case_insensitive {
+                log::debug!("Case-insensitive filesystem: {}", repo_path.display());
+            }
+            case_insensitive
+        })
+    }
+}
+
+fn detect_case_insensitive(repo_path: &Path) -> bool {
+    // 优先查找大小写互换后的仓库目录, 无需写入
+    if let Some(name) = repo_path.file_name().and_then(|name| name.to_str()) {
+        let swapped: String = name
+            .chars()
+            .map(|c| match c.is_uppercase() {
+                true => c.to_lowercase().next().unwrap_or(c),
+                false => c.to_uppercase().next().unwrap_or(c),
+            })
+            .collect();
+        if swapped != name {
+            return is_same_file(repo_path, &repo_path.with_file_name(swapped));
+        }
+    }
+    let probe = repo_path.join(".mrc-case-probe");
+    if std::fs::write(&probe, b"").is_err() {
+        return false;
+    }
+    let case_insensitive = repo_path.join(".MRC-CASE-PROBE").exists();
+    let _ = std::fs::remove_file(&probe);
+    case_insensitive
+}
+
+// 只查询能否加写锁, 不实际加锁
+#[cfg(unix)]
+fn is_locked(path: &Path) -> bool {
+    use std::os::fd::AsRawFd;
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(_) => return false,
+    };
+    let mut lock: libc::flock = unsafe { std::mem::zeroed() };
+    lock.l_type = libc::F_WRLCK as libc::c_short;
+    lock.l_whence = libc::SEEK_SET as libc::c_short;
+    if unsafe { libc::fcntl(file.as_raw_fd(), libc::F_GETLK, &mut lock) } != 0 {
+        return false;
+    }
+    lock.l_type != libc::F_UNLCK as libc::c_short
+}
+
+// Windows 上持有锁的 Java 进程打开文件时不允许共享删除, 删除本身会失败
+#[cfg(not(unix))]
+fn is_locked(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (std::fs::metadata(a), std::fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    a.exists() && b.exists()
+}
+
+#[derive(Clone)]
+pub struct FsHandle(Arc<dyn RepoFs>);
+
+impl FsHandle {
+    pub fn new(fs: impl RepoFs + 'static) -> Self {
+        FsHandle(Arc::new(fs))
+    }
+}
+
+impl<T: RepoFs + 'static> From<Arc<T>> for FsHandle {
+    fn from(fs: Arc<T>) -> Self {
+        FsHandle(fs)
+    }
+}
+
+impl Default for FsHandle {
+    fn default() -> Self {
+        FsHandle::new(RealFs)
+    }
+}
+
+impl Deref for FsHandle {
+    type Target = dyn RepoFs;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+pub fn device(path: &Path) -> Option<u64> {
+    std::fs::metadata(path)
+        .ok()
+        .and_then(|metadata| metadata_device(&metadata))
+}
+
+#[cfg(unix)]
+fn metadata_device(metadata: &std::fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.dev())
+}
+
+#[cfg(not(unix))]
+fn metadata_device(_metadata: &std::fs::Metadata) -> Option<u64> {
+    None
+}
+
+// Windows 上转换为 \\?\ 扩展长度路径, 使超过 260 字符的深层 groupId 目录可以访问
+#[cfg(windows)]
+pub fn extended_length(path: &Path) -> PathBuf {
+    if let Ok(canonical) = std::fs::canonicalize(path) {
+        return canonical;
+    }
+    // 尚不存在的目标目录无法 canonicalize, 手动加前缀
+    let absolute = match std::path::absolute(path) {
+        Ok(absolute) => absolute,
+        Err(_) => return path.to_path_buf(),
+    };
+    let text = absolute.to_string_lossy().replace('/', "\\");
+    if text.starts_with(r"\\?\") {
+        return PathBuf::from(text);
+    }
+    match text.strip_prefix(r"\\") {
+        Some(unc) => PathBuf::from(format!(r"\\?\UNC\{}", unc)),
+        None => PathBuf::from(format!(r"\\?\{}", text)),
+    }
+}
+
+#[cfg(not(windows))]
+pub fn extended_length(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
@@ -0,0 +1,91 @@
+use crate::fs::RealFs;
+use crate::{get_file_name, is_artifact, is_walkable_dir, max_depth, read_dir_sorted};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Gav {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: String,
+}
+
+impl fmt::Display for Gav {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.group_id, self.artifact_id, self.version)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VersionDir {
+    pub gav: Gav,
+    pub path: PathBuf,
+    pub files: Vec<String>,
+}
+
+// 版本目录: group/.../artifact/version, 且包含构件文件
+pub fn from_version_dir(repo_path: &Path, path: &Path) -> Option<Gav> {
+    let relative_path = path.strip_prefix(repo_path).ok()?;
+    let mut components: Vec<String> = relative_path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    if components.len() < 3 {
+        return None;
+    }
+    let version = components.pop()?;
+    let artifact_id = components.pop()?;
+    Some(Gav {
+        group_id: components.join("."),
+        artifact_id,
+        version,
+    })
+}
+
+// 版本目录显示为 groupId:artifactId:version, 其他目录 (如构件目录) 显示为相对路径
+pub fn label(repo_path: &Path, path: &Path) -> String {
+    match from_version_dir(repo_path, path) {
+        Some(gav) if gav.version.starts_with(|c: char| c.is_ascii_digit()) => gav.to_string(),
+        _ => path
+            .strip_prefix(repo_path)
+            .unwrap_or(path)
+            .display()
+            .to_string(),
+    }
+}
+
+pub fn scan_versions(repo_path: &Path) -> Vec<VersionDir> {
+    let mut versions = Vec::new();
+    let mut queue = VecDeque::new();
+    queue.push_back((repo_path.to_path_buf(), 0));
+    while let Some((path, depth)) = queue.pop_front() {
+        let folder = match read_dir_sorted(&path) {
+            Ok(folder) => folder,
+            Err(e) => {
+                log::error!("Failed to read directory '{}': {}", path.display(), e);
+                continue;
+            }
+        };
+        log::debug!("Scanning: {}", path.display());
+        let mut files = Vec::new();
+        for entry in folder.flatten() {
+            let entry_path = entry.path();
+            if is_walkable_dir(&RealFs, &entry_path) {
+                if depth + 1 < max_depth() {
+                    queue.push_back((entry_path, depth + 1));
+                }
+            } else if let Some(file_name) = get_file_name(&entry_path) {
+                files.push(file_name);
+            }
+        }
+        if !files.iter().any(|f| is_artifact(f)) {
+            continue;
+        }
+        if let Some(gav) = from_version_dir(repo_path, &path) {
+            versions.push(VersionDir { gav, path, files });
+        }
+    }
+    versions
+}
@@ -0,0 +1,49 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::io::Write;
+
+// GitHub Actions 工作流命令: 错误和警告显示为注解, 汇总行显示为 notice
+pub struct GhaLogger {
+    level: LevelFilter,
+}
+
+impl GhaLogger {
+    pub fn new(level: LevelFilter) -> Self {
+        GhaLogger { level }
+    }
+}
+
+impl Log for GhaLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let message = record.args().to_string();
+        let line = match record.level() {
+            Level::Error => format!("::error::{}", escape(&message)),
+            Level::Warn => format!("::warning::{}", escape(&message)),
+            // 每个步骤的注解数量有限, 明细行不作为注解
+            Level::Info if record.target() == "mrc::summary" && !message.starts_with(' ') => {
+                format!("::notice::{}", escape(&message))
+            }
+            _ => message,
+        };
+        // runner 也解析 stderr 上的命令, stdout 留给 export 等输出
+        let _ = writeln!(std::io::stderr().lock(), "{}", line);
+    }
+
+    fn flush(&self) {
+        let _ = std::io::stderr().flush();
+    }
+}
+
+// 命令消息中的 % 和换行需要转义
+fn escape(message: &str) -> String {
+    message
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
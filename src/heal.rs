@@ -0,0 +1,202 @@
+use crate::doctor::LAST_UPDATED_SUFFIX;
+use crate::fs::{RealFs, RepoFs};
+use crate::policy::LOCAL_METADATA_FILE;
+use crate::remote_repositories::{self, REMOTE_REPOSITORIES_FILE};
+use crate::verified::VerifiedCache;
+use crate::{
+    get_file_name, integrity, is_artifact, is_walkable_dir, layout, purge, read_dir_sorted,
+    CHECKSUM_SUFFIXIES,
+};
+use std::collections::VecDeque;
+use std::path::Path;
+
+static SHA1_SUFFIX: &str = ".sha1";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Healed {
+    pub checksums: usize,
+    pub metadata: usize,
+    pub markers: usize,
+    pub errors: usize,
+}
+
+// doctor 发现的问题中可以安全自动修复的部分, 一次遍历完成.
+// 上次校验后大小和修改时间未变的构件不再计算摘要, rehash 时全部重新计算
+pub fn heal(repo_path: &Path, dry_run: bool, rehash: bool) -> Healed {
+    let mut healed = Healed::default();
+    let mut cache = VerifiedCache::open(repo_path, rehash, dry_run);
+    let mut queue = VecDeque::new();
+    queue.push_back(repo_path.to_path_buf());
+    while let Some(path) = queue.pop_front() {
+        log::debug!("Scanning: {}", path.display());
+        let folder = match read_dir_sorted(&path) {
+            Ok(folder) => folder,
+            Err(e) => {
+                log::error!("Failed to read directory '{}': {}", path.display(), e);
+                healed.errors += 1;
+                continue;
+            }
+        };
+        let mut files = Vec::new();
+        let mut versions = Vec::new();
+        for entry in folder.flatten() {
+            let entry_path = entry.path();
+            let name = match get_file_name(&entry_path) {
+                Some(name) => name,
+                None => continue,
+            };
+            if is_walkable_dir(&RealFs, &entry_path) {
+                versions.push(name);
+                queue.push_back(entry_path);
+            } else {
+                files.push(name);
+            }
+        }
+        heal_folder(
+            repo_path,
+            &path,
+            &files,
+            &versions,
+            dry_run,
+            &mut cache,
+            &mut healed,
+        );
+    }
+    cache.save();
+    healed
+}
+
+fn heal_folder(
+    repo_path: &Path,
+    path: &Path,
+    files: &[String],
+    versions: &[String],
+    dry_run: bool,
+    cache: &mut VerifiedCache,
+    healed: &mut Healed,
+) {
+    let has = |name: &str| files.iter().any(|file| file == name);
+    let sources = match has(REMOTE_REPOSITORIES_FILE) {
+        true => remote_repositories::read(&RealFs, &path.join(REMOTE_REPOSITORIES_FILE)),
+        false => Default::default(),
+    };
+    let gav = layout::gav(repo_path, path);
+    let published = layout::published(&RealFs, path);
+    let ignore_case = RealFs.is_case_insensitive(repo_path);
+    // 过时的快照构建由清理删除, 不为其补写校验文件
+    let stale = |name: &str| {
+        gav.as_ref()
+            .is_some_and(|gav| layout::is_stale_build(gav, name, ignore_case, &published))
+    };
+    for name in files {
+        let file = path.join(name);
+        if name.ends_with(LAST_UPDATED_SUFFIX) {
+            remove_marker(&file, dry_run, healed);
+        } else if name == LOCAL_METADATA_FILE && !versions.is_empty() {
+            rewrite_metadata(&file, versions, dry_run, healed);
+        } else if is_artifact(name) && !layout::is_metadata(name) && !stale(name) {
+            let sha1 = format!("{}{}", name, SHA1_SUFFIX);
+            // 本地安装的构件没有 .sha1 是正常的, 只修复已有的
+            let downloaded = sources.get(name).is_some_and(|source| !source.is_empty());
+            if has(&sha1) || downloaded {
+                write_checksum(&file, &path.join(sha1), dry_run, cache, healed);
+            }
+        }
+    }
+}
+
+fn remove_marker(path: &Path, dry_run: bool, healed: &mut Healed) {
+    if dry_run {
+        log::info!("Would remove marker: {}", path.display());
+        healed.markers += 1;
+        return;
+    }
+    log::info!("Removing marker: {}", path.display());
+    match std::fs::remove_file(path) {
+        Ok(()) => healed.markers += 1,
+        Err(e) => {
+            log::error!("Failed to remove '{}': {}", path.display(), e);
+            healed.errors += 1;
+        }
+    }
+}
+
+// 删除已不存在的版本; 原有校验和随之失效
+fn rewrite_metadata(path: &Path, versions: &[String], dry_run: bool, healed: &mut Healed) {
+    let updated = match std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| purge::prune_versions(&content, versions))
+    {
+        Ok(Some(updated)) => updated,
+        Ok(None) => return,
+        Err(e) => {
+            log::warn!("Not updating invalid metadata '{}': {}", path.display(), e);
+            return;
+        }
+    };
+    if dry_run {
+        log::info!("Would rewrite metadata: {}", path.display());
+        healed.metadata += 1;
+        return;
+    }
+    log::info!("Rewriting metadata: {}", path.display());
+    if let Err(e) = std::fs::write(path, updated) {
+        log::error!("Failed to write '{}': {}", path.display(), e);
+        healed.errors += 1;
+        return;
+    }
+    for suffix in CHECKSUM_SUFFIXIES {
+        let mut checksum = path.as_os_str().to_owned();
+        checksum.push(suffix);
+        let _ = std::fs::remove_file(Path::new(&checksum));
+    }
+    healed.metadata += 1;
+}
+
+// 校验文件可能是 "摘要  文件名" 的格式; 损坏的构件不重新计算, 否则会掩盖损坏
+fn write_checksum(
+    artifact: &Path,
+    checksum: &Path,
+    dry_run: bool,
+    cache: &mut VerifiedCache,
+    healed: &mut Healed,
+) {
+    let recorded = std::fs::read_to_string(checksum).ok();
+    let recorded = recorded
+        .as_deref()
+        .and_then(|content| content.split_whitespace().next())
+        .map(str::to_ascii_lowercase);
+    let actual = match cache.sha1(artifact) {
+        Ok(actual) => actual,
+        Err(e) => {
+            log::error!("Failed to read '{}': {}", artifact.display(), e);
+            healed.errors += 1;
+            return;
+        }
+    };
+    if recorded.as_deref() == Some(actual.as_str()) {
+        return;
+    }
+    let file_name = get_file_name(artifact).unwrap_or_default();
+    if let Some(Err(e)) = integrity::check_file(artifact, &file_name) {
+        log::warn!(
+            "Not rewriting the checksum of corrupt file '{}': {}",
+            artifact.display(),
+            e
+        );
+        return;
+    }
+    if dry_run {
+        log::info!("Would write checksum: {}", checksum.display());
+        healed.checksums += 1;
+        return;
+    }
+    log::info!("Writing checksum: {}", checksum.display());
+    match std::fs::write(checksum, &actual) {
+        Ok(()) => healed.checksums += 1,
+        Err(e) => {
+            log::error!("Failed to write '{}': {}", checksum.display(), e);
+            healed.errors += 1;
+        }
+    }
+}
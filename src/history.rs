@@ -0,0 +1,218 @@
+use crate::snapshot::Timestamp;
+use crate::summary::Summary;
+use crate::{human_size, i18n};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub static HISTORY_FILE: &str = ".mrc-history.jsonl";
+
+const DAY: f64 = 24.0 * 60.0 * 60.0;
+
+// 每次清理追加一行, 文件很小, 可以保留数年的记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Run {
+    pub date: u64,
+    pub policy: String,
+    pub deleted_size: usize,
+    pub files_deleted: usize,
+    pub errors: usize,
+    // 清理后的仓库大小
+    pub repo_size: usize,
+}
+
+impl Run {
+    pub fn new(policy: &str, summary: &Summary, repo_size: usize) -> Self {
+        Run {
+            date: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default(),
+            policy: policy.to_string(),
+            deleted_size: summary.deleted_size,
+            files_deleted: summary.files_deleted,
+            errors: summary.errors,
+            repo_size,
+        }
+    }
+}
+
+pub fn record(repo_path: &Path, run: &Run) {
+    let path = repo_path.join(HISTORY_FILE);
+    let result = serde_json::to_string(run)
+        .map_err(std::io::Error::other)
+        .and_then(|line| {
+            let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+            writeln!(file, "{}", line)
+        });
+    if let Err(e) = result {
+        log::error!("Failed to write history '{}': {}", path.display(), e);
+    }
+}
+
+// 无法解析的行 (如写入一半) 会被跳过
+pub fn load(repo_path: &Path) -> Result<Vec<Run>, String> {
+    let content =
+        std::fs::read_to_string(repo_path.join(HISTORY_FILE)).map_err(|e| e.to_string())?;
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+// 按天计算的变化量; 第一次清理之前的删除量不计入
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trend {
+    pub days: f64,
+    // 清理后仓库大小的变化, 即在当前清理频率下的实际增长
+    pub net: f64,
+    // 两次清理之间新下载的内容
+    pub gross: f64,
+    pub freed: f64,
+}
+
+// 至少需要两次相隔一段时间的清理
+pub fn trend(runs: &[Run]) -> Option<Trend> {
+    let (first, last) = (runs.first()?, runs.last()?);
+    let days = last.date.checked_sub(first.date).filter(|&s| s > 0)? as f64 / DAY;
+    let mut gross = 0.0;
+    let mut freed = 0.0;
+    for pair in runs.windows(2) {
+        let before_cleanup = pair[1].repo_size + pair[1].deleted_size;
+        gross += before_cleanup as f64 - pair[0].repo_size as f64;
+        freed += pair[1].deleted_size as f64;
+    }
+    Some(Trend {
+        days,
+        net: (last.repo_size as f64 - first.repo_size as f64) / days,
+        gross: gross / days,
+        freed: freed / days,
+    })
+}
+
+// 按当前净增长速度, 剩余空间可用的天数
+pub fn days_until_full(trend: &Trend, available: u64) -> Option<f64> {
+    (trend.net > 0.0).then(|| available as f64 / trend.net)
+}
+
+#[cfg(unix)]
+pub fn available_space(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(windows)]
+pub fn available_space(path: &Path) -> Option<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+    let path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut available: u64 = 0;
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(
+            path.as_ptr(),
+            &mut available,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    (ok != 0).then_some(available)
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn available_space(_path: &Path) -> Option<u64> {
+    None
+}
+
+pub fn show_trend(repo_path: &Path) -> bool {
+    let runs = match load(repo_path) {
+        Ok(runs) => runs,
+        Err(e) => {
+            log::error!("No history, run a cleanup first: {}", e);
+            return false;
+        }
+    };
+    let trend = match trend(&runs) {
+        Some(trend) => trend,
+        None => {
+            log::error!("Not enough history for a trend: {} runs", runs.len());
+            return false;
+        }
+    };
+    let per_day = |bytes: f64| {
+        let sign = if bytes < 0.0 { "-" } else { "+" };
+        format!("{}{}/day", sign, human_size(bytes.abs() as usize))
+    };
+    log::info!("Period: {:.1} days, {} runs", trend.days, runs.len());
+    log::info!("Growth rate: {}", per_day(trend.net));
+    log::info!("Downloaded between cleanups: {}", per_day(trend.gross));
+    log::info!("Freed by cleanups: {}", per_day(-trend.freed));
+    let available = match available_space(repo_path) {
+        Some(available) => available,
+        None => {
+            log::warn!("Free disk space is unknown on this platform");
+            return true;
+        }
+    };
+    log::info!("Free disk space: {}", human_size(available as usize));
+    match days_until_full(&trend, available) {
+        Some(days) => {
+            let last = runs.last().map(|run| run.date).unwrap_or_default();
+            let full = Timestamp::from_unix(last + (days * DAY) as u64);
+            log::info!(
+                "Disk full in {:.0} days at the current trend ({:04}-{:02}-{:02})",
+                days,
+                full.year,
+                full.month,
+                full.day
+            );
+        }
+        None => log::info!("Repository is not growing"),
+    }
+    true
+}
+
+pub fn show(repo_path: &Path) -> bool {
+    let runs = match load(repo_path) {
+        Ok(runs) => runs,
+        Err(e) => {
+            log::error!("No history, run a cleanup first: {}", e);
+            return false;
+        }
+    };
+    println!(
+        "{:<17} {:<20} {:>12} {:>10} {:>8} {:>12}",
+        i18n::translate("Date"),
+        i18n::translate("Policy"),
+        i18n::translate("Freed"),
+        i18n::translate("Files"),
+        i18n::translate("Errors"),
+        i18n::translate("Size")
+    );
+    for run in &runs {
+        let date = Timestamp::from_unix(run.date);
+        println!(
+            "{:<17} {:<20} {:>12} {:>10} {:>8} {:>12}",
+            format!(
+                "{:04}-{:02}-{:02} {:02}:{:02}",
+                date.year, date.month, date.day, date.hour, date.minute
+            ),
+            run.policy,
+            human_size(run.deleted_size),
+            run.files_deleted,
+            run.errors,
+            human_size(run.repo_size)
+        );
+    }
+    let freed: usize = runs.iter().map(|run| run.deleted_size).sum();
+    log::info!("Runs: {}", runs.len());
+    log::info!("Total freed: {}", human_size(freed));
+    true
+}
@@ -0,0 +1,82 @@
+use crate::remove::Candidate;
+use crate::summary::Summary;
+use serde::Serialize;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+#[derive(Serialize)]
+struct BeforeDelete<'a> {
+    repository: &'a Path,
+    candidates: &'a [Candidate],
+}
+
+// 删除前执行, 非零退出码时取消本次删除
+pub fn before_delete(command: &str, repo_path: &Path, candidates: &[Candidate]) -> bool {
+    let input = BeforeDelete {
+        repository: repo_path,
+        candidates,
+    };
+    let envs = [
+        ("MRC_REPOSITORY", repo_path.display().to_string()),
+        ("MRC_CANDIDATES", candidates.len().to_string()),
+    ];
+    match run(command, &input, &envs) {
+        Ok(true) => true,
+        Ok(false) => {
+            log::error!("on-before-delete hook failed, skipping deletion");
+            false
+        }
+        Err(e) => {
+            log::error!("Failed to run on-before-delete hook: {}", e);
+            false
+        }
+    }
+}
+
+pub fn after_run(command: &str, summary: &Summary) {
+    let envs = [
+        ("MRC_DELETED_SIZE", summary.deleted_size.to_string()),
+        ("MRC_FILES_DELETED", summary.files_deleted.to_string()),
+        ("MRC_DIRS_REMOVED", summary.dirs_removed.to_string()),
+        ("MRC_FILES_SKIPPED", summary.files_skipped.to_string()),
+        ("MRC_ERRORS", summary.errors.to_string()),
+    ];
+    match run(command, summary, &envs) {
+        Ok(true) => {}
+        Ok(false) => log::warn!("on-after-run hook exited with failure"),
+        Err(e) => log::error!("Failed to run on-after-run hook: {}", e),
+    }
+}
+
+// 数据以 JSON 写入标准输入, 关键数字同时放入环境变量
+fn run(command: &str, input: &impl Serialize, envs: &[(&str, String)]) -> io::Result<bool> {
+    let json = serde_json::to_vec(input).map_err(io::Error::other)?;
+    log::debug!("Running hook: {}", command);
+    let mut child = shell(command)
+        .envs(envs.iter().map(|(key, value)| (*key, value)))
+        .stdin(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // 钩子可能不读取标准输入
+        match stdin.write_all(&json) {
+            Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(child.wait()?.success())
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
@@ -0,0 +1,146 @@
+mod zh;
+
+use log::{Log, Metadata, Record};
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+// 英文即源字符串, 其他语言按消息模板翻译
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    #[value(help = "English")]
+    En,
+    #[value(help = "简体中文")]
+    Zh,
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+pub fn set_lang(lang: Lang) {
+    let _ = LANG.set(lang);
+}
+
+pub fn lang() -> Lang {
+    *LANG.get_or_init(detect)
+}
+
+// 与 gettext 相同的优先级: LC_ALL > LC_MESSAGES > LANG
+pub fn detect() -> Lang {
+    for name in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+        match std::env::var(name) {
+            Ok(value) if !value.is_empty() => {
+                return if value.to_lowercase().starts_with("zh") {
+                    Lang::Zh
+                } else {
+                    Lang::En
+                };
+            }
+            _ => {}
+        }
+    }
+    Lang::En
+}
+
+fn catalog(lang: Lang) -> &'static [(&'static str, &'static str)] {
+    match lang {
+        Lang::En => &[],
+        Lang::Zh => zh::MESSAGES,
+    }
+}
+
+// 按模板匹配已格式化的消息, 未收录的消息原样返回
+pub fn translate(message: &str) -> Cow<'_, str> {
+    for (source, target) in catalog(lang()) {
+        if let Some(args) = match_template(source, message) {
+            return Cow::Owned(fill(target, &args));
+        }
+    }
+    Cow::Borrowed(message)
+}
+
+// 模板中的 {...} 匹配任意文本, 前面的参数取最短匹配
+fn match_template<'a>(template: &str, message: &'a str) -> Option<Vec<&'a str>> {
+    let pieces = literals(template);
+    let (first, rest) = pieces.split_first()?;
+    let mut remaining = message.strip_prefix(first)?;
+    let mut args = Vec::new();
+    for (index, piece) in rest.iter().enumerate() {
+        if index == rest.len() - 1 {
+            args.push(remaining.strip_suffix(piece)?);
+            remaining = "";
+        } else {
+            let end = remaining.find(piece)?;
+            args.push(&remaining[..end]);
+            remaining = &remaining[end + piece.len()..];
+        }
+    }
+    remaining.is_empty().then_some(args)
+}
+
+fn literals(template: &str) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        pieces.push(&rest[..start]);
+        rest = &rest[end + 1..];
+    }
+    pieces.push(rest);
+    pieces
+}
+
+// {} 依次取参数, {0} {1} 用于调整参数顺序
+fn fill(template: &str, args: &[&str]) -> String {
+    let mut text = String::new();
+    let mut next = 0;
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        text.push_str(&rest[..start]);
+        let index = match rest[start + 1..end].parse::<usize>() {
+            Ok(index) => index,
+            Err(_) => {
+                next += 1;
+                next - 1
+            }
+        };
+        text.push_str(args.get(index).copied().unwrap_or_default());
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+    text
+}
+
+// 包装实际输出日志的 logger, 输出前翻译消息
+pub struct Translated(pub Box<dyn Log>);
+
+impl Log for Translated {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.0.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if lang() == Lang::En || !self.0.enabled(record.metadata()) {
+            return self.0.log(record);
+        }
+        let message = record.args().to_string();
+        self.0.log(
+            &Record::builder()
+                .args(format_args!("{}", translate(&message)))
+                .metadata(record.metadata().clone())
+                .module_path(record.module_path())
+                .file(record.file())
+                .line(record.line())
+                .build(),
+        );
+    }
+
+    fn flush(&self) {
+        self.0.flush()
+    }
+}
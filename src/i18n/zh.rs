@@ -0,0 +1,487 @@
+// 以英文源字符串为键, {} 按顺序对应参数
+pub static MESSAGES: &[(&str, &str)] = &[
+    // 汇总
+    ("Deleted size: {}", "已删除大小: {}"),
+    ("Deleted files: {}", "已删除文件: {}"),
+    ("Removed directories: {}", "已删除目录: {}"),
+    ("Skipped files: {}", "跳过文件: {}"),
+    ("Errors: {}", "错误: {}"),
+    ("Unreadable (skipped): {}", "无法读取 (已跳过): {}"),
+    ("Scanned directories: {}", "扫描目录: {}"),
+    ("Examined files: {}", "检查文件: {}"),
+    ("Scan time: {}", "扫描耗时: {}"),
+    ("Delete time: {}", "删除耗时: {}"),
+    ("Peak memory: {}", "峰值内存: {}"),
+    ("  {}: {} across {} files", "  {}: {}, 共 {} 个文件"),
+    ("Failed to write report '{}': {}", "无法写入报告 '{}': {}"),
+    ("Failed to write journal: {}", "无法写入删除日志: {}"),
+    (
+        "Failed to write journal, skipping '{}': {}",
+        "无法写入删除日志, 跳过 '{}': {}",
+    ),
+    (
+        "Failed to open journal '{}': {}",
+        "无法打开删除日志 '{}': {}",
+    ),
+    ("Invalid journal '{}': {}", "无效的删除日志 '{}': {}"),
+    ("Skipping journal line {}: {}", "跳过删除日志第 {} 行: {}"),
+    ("Interrupted batches: {}", "中断的批次: {}"),
+    ("Profile '{}' has no paths", "配置 '{}' 中没有仓库路径"),
+    (
+        "Failed to write history '{}': {}",
+        "无法写入清理历史 '{}': {}",
+    ),
+    (
+        "No history, run a cleanup first: {}",
+        "没有清理历史, 请先执行一次清理: {}",
+    ),
+    ("Runs: {}", "清理次数: {}"),
+    ("Total freed: {}", "共释放: {}"),
+    (
+        "Not enough history for a trend: {} runs",
+        "历史记录不足, 无法计算趋势: {} 次清理",
+    ),
+    (
+        "Period: {:.1} days, {} runs",
+        "时间跨度: {:.1} 天, {} 次清理",
+    ),
+    ("Growth rate: {}", "增长速度: {}"),
+    ("Downloaded between cleanups: {}", "两次清理之间新增: {}"),
+    ("Freed by cleanups: {}", "清理释放: {}"),
+    (
+        "Free disk space is unknown on this platform",
+        "此平台无法获取磁盘剩余空间",
+    ),
+    ("Free disk space: {}", "磁盘剩余空间: {}"),
+    (
+        "Disk full in {:.0} days at the current trend ({:04}-{:02}-{:02})",
+        "按当前趋势 {:.0} 天后磁盘将满 ({:04}-{:02}-{:02})",
+    ),
+    ("Repository is not growing", "仓库没有增长"),
+    ("Explain: {} -> {} ({})", "判定: {} -> {} ({})"),
+    ("Not a file: {}", "不是文件: {}"),
+    (
+        "'{}' is not inside the repository '{}'",
+        "'{}' 不在仓库 '{}' 中",
+    ),
+    (
+        "No repository given and no home directory found",
+        "未指定仓库, 也找不到用户主目录",
+    ),
+    (
+        "Progress: {}/{}, {} freed, {}, ETA {}",
+        "进度: {}/{}, 已释放 {}, {}, 预计剩余 {}",
+    ),
+    (
+        "Scanning: {} directories, {} candidates",
+        "扫描中: {} 个目录, {} 个候选",
+    ),
+    ("Elapsed: {}, {}", "耗时: {}, {}"),
+    // 清理
+    ("Cleaning up: {}", "正在清理: {}"),
+    ("Deleting: {}", "正在删除: {}"),
+    ("Failed to delete file '{}': {}", "无法删除文件 '{}': {}"),
+    (
+        "Failed to delete directory '{}': {}",
+        "无法删除目录 '{}': {}",
+    ),
+    ("Archived: {} -> {}", "已归档: {} -> {}"),
+    ("Failed to archive '{}': {}", "无法归档 '{}': {}"),
+    ("Moved: {} -> {}", "已移动: {} -> {}"),
+    ("Failed to move '{}': {}", "无法移动 '{}': {}"),
+    (
+        "Failed to start async runtime: {}",
+        "无法启动异步运行时: {}",
+    ),
+    ("Deletion task failed: {}", "删除任务失败: {}"),
+    ("Failed to read '{}': {}", "无法读取 '{}': {}"),
+    ("Failed to read directory entry: {}", "无法读取目录项: {}"),
+    ("Failed to read directory '{}': {}", "无法读取目录 '{}': {}"),
+    ("Failed to read directory: {}", "无法读取目录: {}"),
+    (
+        "Failed to read metadata '{}': {}",
+        "无法读取元数据 '{}': {}",
+    ),
+    ("Skipping unreadable: {}", "跳过无法读取的路径: {}"),
+    (
+        "Permission denied in strict mode, aborting: {}",
+        "严格模式下权限不足, 中止: {}",
+    ),
+    (
+        "Transient error on '{}': {}, retrying in {} ({}/{})",
+        "'{}' 出现临时错误: {}, {} 后重试 ({}/{})",
+    ),
+    (
+        "Removing artifact that could not be re-downloaded: {}",
+        "正在删除无法重新下载的构件: {}",
+    ),
+    ("Failed to serialize index: {}", "无法序列化索引: {}"),
+    ("Failed to write index '{}': {}", "无法写入索引 '{}': {}"),
+    ("Failed to parse '{}': {}", "无法解析 '{}': {}"),
+    (
+        "Failed to parse settings '{}': {}",
+        "无法解析 settings '{}': {}",
+    ),
+    (
+        "Failed to read settings '{}': {}",
+        "无法读取 settings '{}': {}",
+    ),
+    // 钩子, 插件和脚本
+    (
+        "on-before-delete hook failed, skipping deletion",
+        "on-before-delete 钩子失败, 跳过删除",
+    ),
+    (
+        "Failed to run on-before-delete hook: {}",
+        "无法运行 on-before-delete 钩子: {}",
+    ),
+    (
+        "on-after-run hook exited with failure",
+        "on-after-run 钩子以失败状态退出",
+    ),
+    (
+        "Failed to run on-after-run hook: {}",
+        "无法运行 on-after-run 钩子: {}",
+    ),
+    (
+        "Plugin '{}' ({}) returned unknown action {} for {}",
+        "插件 '{}' ({}) 对 {} 返回未知动作 {}",
+    ),
+    ("Loaded plugin '{}' from {}", "已加载插件 '{}': {}"),
+    (
+        "Script returned '{}' for {}, keeping",
+        "脚本对 {1} 返回 '{0}', 保留",
+    ),
+    (
+        "Script returned {} for {}, keeping",
+        "脚本对 {1} 返回 {0}, 保留",
+    ),
+    (
+        "Script failed for {}: {}, keeping",
+        "脚本处理 {} 失败: {}, 保留",
+    ),
+    (
+        "Quarantine requested without --quarantine-dir, keeping: {}",
+        "未指定 --quarantine-dir, 无法隔离, 保留: {}",
+    ),
+    // 子命令
+    ("Validating: {}", "正在校验: {}"),
+    ("Diagnosing: {}", "正在诊断: {}"),
+    (
+        "Artifacts without checksums: {}, delete them so Maven downloads and verifies them again",
+        "缺少校验文件的构件: {}, 删除后 Maven 会重新下载并校验",
+    ),
+    (
+        "Corrupt files: {}, run `mrc integrity --delete` so Maven refetches them",
+        "损坏的文件: {}, 运行 `mrc integrity --delete` 让 Maven 重新获取",
+    ),
+    (
+        "Dangling metadata: {}, safe to delete, Maven recreates it when needed",
+        "悬空的元数据: {}, 可以安全删除, Maven 需要时会重新生成",
+    ),
+    (
+        "Files with mixed ownership or permissions: {}, fix them with chown/chmod so builds and cleanups can replace them",
+        "属主或权限不一致的文件: {}, 请用 chown/chmod 修正, 以便构建和清理可以替换它们",
+    ),
+    (
+        "Abandoned locks: {}, delete them when no Maven build is running",
+        "遗留的锁文件: {}, 请在没有 Maven 构建运行时删除",
+    ),
+    (
+        "Snapshot builds no longer listed in their metadata: {}, run `mrc` to delete them",
+        "元数据中已不再列出的快照构建: {}, 运行 `mrc` 删除它们",
+    ),
+    ("Findings: {}", "发现的问题: {}"),
+    ("Looking for identical artifacts: {}", "正在查找相同的构件: {}"),
+    (
+        "Identical {} jars, {} each, {} wasted (sha256 {}):",
+        "{} 个相同的 jar, 每个 {}, 浪费 {} (sha256 {}):",
+    ),
+    ("Identical sets: {}", "相同构件组: {}"),
+    ("Combined waste: {}", "合计浪费: {}"),
+    ("Healing: {}", "正在修复: {}"),
+    ("Checksums written: {}", "写入的校验文件: {}"),
+    ("Metadata rewritten: {}", "重写的元数据: {}"),
+    ("Markers removed: {}", "删除的标记: {}"),
+    ("Would remove marker: {}", "将删除标记: {}"),
+    ("Removing marker: {}", "正在删除标记: {}"),
+    ("Would rewrite metadata: {}", "将重写元数据: {}"),
+    ("Rewriting metadata: {}", "正在重写元数据: {}"),
+    ("Would write checksum: {}", "将写入校验文件: {}"),
+    ("Writing checksum: {}", "正在写入校验文件: {}"),
+    (
+        "Not rewriting the checksum of corrupt file '{}': {}",
+        "不重写损坏文件 '{}' 的校验文件: {}",
+    ),
+    (
+        "Mixed-case duplicates in '{}': {}",
+        "'{}' 中存在仅大小写不同的重复项: {}",
+    ),
+    ("File at wrong depth: {}", "文件层级错误: {}"),
+    (
+        "Version directory has no artifacts: {}",
+        "版本目录中没有构件: {}",
+    ),
+    (
+        "Artifact does not match '{}:{}': {}",
+        "构件与 '{}:{}' 不匹配: {}",
+    ),
+    ("Anomalies found: {}", "发现异常: {}"),
+    ("Checking integrity: {}", "正在检查完整性: {}"),
+    ("Corrupt file '{}': {}", "文件已损坏 '{}': {}"),
+    ("Corrupt files: {}", "损坏文件: {}"),
+    ("Auditing: {}", "正在审计: {}"),
+    ("Querying OSV for {} versions", "正在向 OSV 查询 {} 个版本"),
+    ("Failed to query OSV: {}", "无法查询 OSV: {}"),
+    ("Vulnerable {}: {}", "存在漏洞 {}: {}"),
+    ("Vulnerable versions: {}", "存在漏洞的版本: {}"),
+    ("Failed to check for updates: {}", "无法检查更新: {}"),
+    ("Already up to date: {}", "已是最新版本: {}"),
+    ("New version available: {} -> {}", "有新版本: {} -> {}"),
+    (
+        "Release {} has no binary for this platform: {}",
+        "版本 {} 没有适用于本平台的程序: {}",
+    ),
+    (
+        "Release {} has no checksum for {}",
+        "版本 {} 没有 {} 的校验和",
+    ),
+    ("Failed to download '{}': {}", "无法下载 '{}': {}"),
+    (
+        "Failed to locate the running binary: {}",
+        "无法定位当前程序: {}",
+    ),
+    ("Downloading: {}", "正在下载: {}"),
+    (
+        "Checksum mismatch for {}: expected {}, got {}",
+        "{} 的校验和不匹配: 应为 {}, 实际为 {}",
+    ),
+    ("Failed to replace '{}': {}", "无法替换 '{}': {}"),
+    ("Updated to {}", "已更新到 {}"),
+    ("Checking: {}", "正在检查: {}"),
+    ("Reclaimable files: {}", "可回收文件: {}"),
+    ("Reclaimable size: {}", "可回收大小: {}"),
+    (
+        "Reclaimable size exceeds --max-garbage {}",
+        "可回收大小超过 --max-garbage {}",
+    ),
+    ("Verifying: {}", "正在验证: {}"),
+    ("Not compliant ({}): {}", "不符合策略 ({}): {}"),
+    (
+        "Scan failed, compliance could not be verified",
+        "扫描失败, 无法验证是否符合策略",
+    ),
+    (
+        "Repository is not clean: {} candidates",
+        "仓库未清理干净: {} 个候选",
+    ),
+    ("Repository is clean", "仓库已清理干净"),
+    ("Comparing with baseline: {}", "正在与基线比较: {}"),
+    ("Failed to serialize baseline: {}", "无法序列化基线: {}"),
+    ("Failed to serialize verification: {}", "无法序列化校验记录: {}"),
+    ("Failed to write baseline '{}': {}", "无法写入基线 '{}': {}"),
+    (
+        "No baseline, run a cleanup first: {}",
+        "没有基线, 请先执行一次清理: {}",
+    ),
+    ("Baseline from {} ago", "基线创建于 {} 前"),
+    ("Total growth: {}{}", "总增长: {}{}"),
+    ("New garbage: {}", "新增可清理内容: {}"),
+    ("Invalid manifest '{}': {}", "无效的清单 '{}': {}"),
+    ("Deleted files in manifest: {}", "清单中已删除的文件: {}"),
+    (
+        "Reappeared files: {} ({}) in {} directories",
+        "重新出现的文件: {} ({}), 位于 {} 个目录",
+    ),
+    ("Analyzing: {}", "正在分析: {}"),
+    ("Deduplicating: {}", "正在去重: {}"),
+    ("Compacting: {}", "正在压实: {}"),
+    ("Failed to hash '{}': {}", "无法计算哈希 '{}': {}"),
+    (
+        "Identical across filesystems (report only): {}",
+        "跨文件系统的相同文件 (仅报告): {}",
+    ),
+    ("Linking: {} -> {}", "正在链接: {} -> {}"),
+    ("Failed to link '{}': {}", "无法链接 '{}': {}"),
+    ("Linked files: {}", "已链接文件: {}"),
+    ("Saved size: {}", "节省空间: {}"),
+    ("Duplicated across filesystems: {}", "跨文件系统重复: {}"),
+    ("Failed to store '{}': {}", "无法存储 '{}': {}"),
+    ("Stored files: {}", "已存储文件: {}"),
+    (
+        "Compressing artifacts unused for: {}",
+        "正在压缩超过该时长未使用的构件: {}",
+    ),
+    ("Compressing: {}", "正在压缩: {}"),
+    ("Failed to compress '{}': {}", "无法压缩 '{}': {}"),
+    ("Compressed files: {}", "已压缩文件: {}"),
+    ("Compressed size saved: {}", "压缩节省空间: {}"),
+    ("Restoring: {}", "正在恢复: {}"),
+    ("Failed to restore '{}': {}", "无法恢复 '{}': {}"),
+    ("Restored files: {}", "已恢复文件: {}"),
+    ("Watching: {}", "正在监听: {}"),
+    ("Failed to create watcher: {}", "无法创建监听器: {}"),
+    ("Failed to watch '{}': {}", "无法监听 '{}': {}"),
+    (
+        "Failed to start dashboard on '{}': {}",
+        "无法在 '{}' 上启动面板: {}",
+    ),
+    (
+        "Watcher lost events, next run rescans the repository",
+        "监听器丢失事件, 下次运行将重新扫描仓库",
+    ),
+    ("Watch error: {}", "监听错误: {}"),
+    ("Repository size: {}", "仓库大小: {}"),
+    ("Dashboard: http://{}", "面板: http://{}"),
+    ("Benchmarking", "正在进行基准测试"),
+    ("Generating fixture: {}", "正在生成测试仓库: {}"),
+    (
+        "Failed to generate fixture '{}': {}",
+        "无法生成测试仓库 '{}': {}",
+    ),
+    ("Simulating: {}", "正在模拟: {}"),
+    ("Simulating policy: {}", "正在模拟策略: {}"),
+    ("Exporting SBOM: {}", "正在导出 SBOM: {}"),
+    ("Components: {}", "组件: {}"),
+    ("Written: {}", "已写入: {}"),
+    ("No artifacts match the coordinates", "没有与坐标匹配的构件"),
+    (
+        "Removing artifact without versions: {}",
+        "正在删除没有版本的构件: {}",
+    ),
+    ("Failed to remove '{}': {}", "无法删除 '{}': {}"),
+    (
+        "Not updating invalid metadata '{}': {}",
+        "元数据无效, 不更新 '{}': {}",
+    ),
+    ("Updating metadata: {}", "正在更新元数据: {}"),
+    ("Failed to write '{}': {}", "无法写入 '{}': {}"),
+    (
+        "Skipping version pinned in {}: {}",
+        "跳过在 {} 中固定的版本: {}",
+    ),
+    ("Invalid groupId: {}", "无效的 groupId: {}"),
+    ("Group not found: {}", "未找到 group: {}"),
+    ("Version pinned in {}: {}", "在 {} 中固定的版本: {}"),
+    (
+        "Not purging a group with pinned versions: {}",
+        "group 中有固定的版本, 不清除: {}",
+    ),
+    ("Would purge: {}", "将清除: {}"),
+    ("Purging: {}", "正在清除: {}"),
+    ("Files: {}, size: {}", "文件: {}, 大小: {}"),
+    ("Delete {} files ({})?", "删除 {} 个文件 ({})?"),
+    ("Aborted, nothing deleted", "已取消, 未删除任何文件"),
+    (
+        "Refusing to delete without confirmation, pass --yes",
+        "未经确认不会删除, 请传入 --yes",
+    ),
+    (
+        "Failed to lower CPU priority: {}",
+        "无法降低 CPU 优先级: {}",
+    ),
+    (
+        "Failed to lower I/O priority: {}",
+        "无法降低 I/O 优先级: {}",
+    ),
+    (
+        "Failed to enter background priority: {}",
+        "无法进入后台优先级: {}",
+    ),
+    (
+        "Low priority mode is not supported on this platform",
+        "当前平台不支持低优先级模式",
+    ),
+    // 命令行
+    (
+        "--one-file-system is not supported on this platform",
+        "当前平台不支持 --one-file-system",
+    ),
+    ("Invalid config: {}", "无效的配置: {}"),
+    ("Invalid --workspace: {}", "无效的 --workspace: {}"),
+    ("Invalid pin at {}:{}: {}", "无效的固定项 {}:{}: {}"),
+    ("Invalid --for: {}", "无效的 --for: {}"),
+    ("Invalid --stale-locks: {}", "无效的 --stale-locks: {}"),
+    (
+        "Download in progress, skipping: {}",
+        "正在下载, 跳过: {}",
+    ),
+    ("Invalid coordinate: {}", "无效的坐标: {}"),
+    ("No versions match {}", "没有匹配 {} 的版本"),
+    ("Pinned: {} ({})", "已固定: {} ({})"),
+    ("Unpinned: {}", "已取消固定: {}"),
+    ("Pinned versions: {}", "固定的版本: {}"),
+    ("Failed to update '{}': {}", "更新 '{}' 失败: {}"),
+    (
+        "Ignoring invalid {} on '{}': {}",
+        "忽略 '{1}' 上无效的 {0}: {2}",
+    ),
+    ("Workspace dependencies kept: {}", "保留的工作区依赖: {}"),
+    ("Skipping build file '{}': {}", "跳过构建文件 '{}': {}"),
+    (
+        "Config '{}' already exists, use --force to overwrite",
+        "配置 '{}' 已存在, 使用 --force 覆盖",
+    ),
+    ("Wrote config: {}", "已写入配置: {}"),
+    ("Failed to write config '{}': {}", "写入配置 '{}' 失败: {}"),
+    (
+        "No --config given and no home directory found",
+        "未指定 --config, 也找不到用户主目录",
+    ),
+    ("Invalid policy '{}': {}", "无效的策略 '{}': {}"),
+    (
+        "Policy '{}' defines {} policies, use simulate to compare them",
+        "策略文件 '{}' 定义了 {} 个策略, 请使用 simulate 比较",
+    ),
+    (
+        "Policy archive rules require --archive-dir",
+        "策略中的归档规则需要 --archive-dir",
+    ),
+    ("Failed to load plugins: {}", "无法加载插件: {}"),
+    ("Invalid script '{}': {}", "无效的脚本 '{}': {}"),
+    ("Invalid --throttle: {}", "无效的 --throttle: {}"),
+    ("Invalid --retry-backoff: {}", "无效的 --retry-backoff: {}"),
+    (
+        "Invalid --compress-older-than: {}",
+        "无效的 --compress-older-than: {}",
+    ),
+    ("Invalid --max-garbage: {}", "无效的 --max-garbage: {}"),
+    ("Invalid --interval: {}", "无效的 --interval: {}"),
+    (
+        "Invalid --progress-interval: {}",
+        "无效的 --progress-interval: {}",
+    ),
+    ("Invalid --versions: {}", "无效的 --versions: {}"),
+    ("Invalid --keep-matching: {}", "无效的 --keep-matching: {}"),
+    ("Invalid --proxy: {}", "无效的 --proxy: {}"),
+    (
+        "Invalid --timestamp-format: {}",
+        "无效的 --timestamp-format: {}",
+    ),
+    ("Cannot verify remotes: {}", "无法校验远程仓库: {}"),
+    ("Invalid proxy '{}': {}", "无效的代理 '{}': {}"),
+    ("Invalid coordinate: {}", "无效的坐标: {}"),
+    (
+        "Invalid coordinate on stdin: {}",
+        "标准输入中的坐标无效: {}",
+    ),
+    (
+        "file or directory does not exist: {}",
+        "文件或目录不存在: {}",
+    ),
+    ("Maven Repo is not a file: {}", "Maven 仓库不能是文件: {}"),
+    // 表头
+    ("License", "许可证"),
+    ("Policy", "策略"),
+    ("Version", "版本"),
+    ("Versions", "版本数"),
+    ("Candidates", "候选"),
+    ("Files", "文件"),
+    ("Size", "大小"),
+    ("Bytes", "字节"),
+    ("Growth", "增长"),
+    ("Date", "日期"),
+    ("Freed", "释放"),
+    ("Errors", "错误"),
+    ("New garbage", "新增可清理"),
+];
@@ -0,0 +1,170 @@
+use crate::baseline::BASELINE_FILE;
+use crate::history::HISTORY_FILE;
+use crate::pins::PINS_FILE;
+use crate::read_dir_sorted;
+use crate::verified::VERIFIED_FILE;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+pub static INDEX_FILE: &str = ".mrc-index.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirEntry {
+    pub mtime: u128,
+    pub size: u64,
+    pub children: Vec<String>,
+    // 上次的判定随时间或其他版本变化, 下次即使未修改也要重新读取
+    #[serde(default)]
+    pub revisit: bool,
+}
+
+// policy 记录影响删除决策的选项, 选项变化时索引失效
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Index {
+    pub policy: String,
+    pub dirs: HashMap<String, DirEntry>,
+}
+
+pub struct IndexWriter {
+    path: PathBuf,
+    previous: Index,
+    current: Index,
+    changed: Vec<PathBuf>,
+    watched: Option<HashSet<PathBuf>>,
+    revisit: HashSet<PathBuf>,
+}
+
+impl IndexWriter {
+    pub fn open(path: PathBuf, policy: String) -> Self {
+        let previous = load(&path);
+        let previous = if previous.policy == policy {
+            previous
+        } else {
+            log::debug!("Policy changed, rescanning: {}", path.display());
+            Index::default()
+        };
+        IndexWriter {
+            path,
+            previous,
+            current: Index {
+                policy,
+                dirs: HashMap::new(),
+            },
+            changed: Vec::new(),
+            watched: None,
+            revisit: HashSet::new(),
+        }
+    }
+
+    // 由文件监听提供变化的目录, 其余目录直接信任索引而无需 stat
+    pub fn with_watched(mut self, changes: HashSet<PathBuf>) -> Self {
+        self.watched = Some(changes);
+        self
+    }
+
+    // 目录未变化时返回其子目录, 调用方不必再读取目录
+    pub fn unchanged(&mut self, repo_path: &Path, path: &Path) -> Option<Vec<String>> {
+        let key = key(repo_path, path);
+        let entry = self.previous.dirs.remove(&key)?;
+        if entry.revisit {
+            return None;
+        }
+        match &self.watched {
+            Some(watched) => {
+                if watched.contains(path) {
+                    return None;
+                }
+            }
+            None => {
+                if mtime(path)? != entry.mtime {
+                    return None;
+                }
+            }
+        }
+        let children = entry.children.clone();
+        self.current.dirs.insert(key, entry);
+        Some(children)
+    }
+
+    pub fn mark_changed(&mut self, path: &Path) {
+        self.changed.push(path.to_path_buf());
+    }
+
+    // policy 阶段发现的需要下次重新判定的目录
+    pub fn revisit(&mut self, dirs: HashSet<PathBuf>) {
+        self.revisit.extend(dirs);
+    }
+
+    pub fn save(mut self, repo_path: &Path) {
+        for path in std::mem::take(&mut self.changed) {
+            if let Some(mut entry) = read_entry(&path) {
+                entry.revisit = self.revisit.contains(&path);
+                self.current.dirs.insert(key(repo_path, &path), entry);
+            }
+        }
+        let content = match serde_json::to_string(&self.current) {
+            Ok(content) => content,
+            Err(e) => {
+                log::error!("Failed to serialize index: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = std::fs::write(&self.path, content) {
+            log::error!("Failed to write index '{}': {}", self.path.display(), e);
+        }
+    }
+}
+
+pub fn load(path: &Path) -> Index {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn key(repo_path: &Path, path: &Path) -> String {
+    path.strip_prefix(repo_path)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+fn mtime(path: &Path) -> Option<u128> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos())
+}
+
+pub fn read_entry(path: &Path) -> Option<DirEntry> {
+    let mut size: u64 = 0;
+    let mut children = Vec::new();
+    for entry in read_dir_sorted(path).ok()?.flatten() {
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+        if metadata.is_dir() {
+            children.push(entry.file_name().to_string_lossy().to_string());
+        } else if ![
+            INDEX_FILE,
+            BASELINE_FILE,
+            HISTORY_FILE,
+            PINS_FILE,
+            VERIFIED_FILE,
+        ]
+        .contains(&&*entry.file_name().to_string_lossy())
+        {
+            size += metadata.len();
+        }
+    }
+    Some(DirEntry {
+        mtime: mtime(path)?,
+        size,
+        children,
+        revisit: false,
+    })
+}
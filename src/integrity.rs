@@ -0,0 +1,227 @@
+use crate::remove::{self, Action, Candidate};
+use crate::summary::{Reason, Summary};
+use crate::{get_file_name, is_walkable_dir, CleanupOptions};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+static ARCHIVE_SUFFIXIES: [&str; 2] = [".jar", ".war"];
+
+static POM_SUFFIX: &str = ".pom";
+
+const EOCD_SIGNATURE: u32 = 0x06054b50;
+const EOCD_SIZE: u64 = 22;
+const ZIP64_LOCATOR_SIGNATURE: u32 = 0x07064b50;
+const ZIP64_EOCD_SIGNATURE: u32 = 0x06064b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x02014b50;
+const CENTRAL_HEADER_SIZE: usize = 46;
+const LOCAL_HEADER_SIGNATURE: u32 = 0x04034b50;
+
+pub fn check(repo_path: PathBuf, delete: bool, options: &CleanupOptions) -> bool {
+    let mut corrupted: usize = 0;
+    let mut candidates = Vec::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(repo_path.clone());
+    while let Some(path) = queue.pop_front() {
+        if path == repo_path || is_walkable_dir(&*options.fs, &path) {
+            match options.fs.read_dir(&path) {
+                Ok(folder) => {
+                    for entry in folder {
+                        match entry {
+                            Ok(entry) => queue.push_back(entry),
+                            Err(e) => log::error!("Failed to read directory entry: {:?}", e),
+                        }
+                    }
+                    log::debug!("Scanning: {}", path.display());
+                }
+                Err(e) => {
+                    log::error!("Failed to read directory: {}", e);
+                }
+            }
+            continue;
+        }
+
+        let file_name = match get_file_name(&path) {
+            Some(file_name) => file_name,
+            None => continue,
+        };
+        let result = match check_file(&path, &file_name) {
+            Some(result) => result,
+            None => continue,
+        };
+
+        if let Err(e) = result {
+            corrupted += 1;
+            log::warn!("Corrupt file '{}': {}", path.display(), e);
+            if delete {
+                candidates.push(Candidate::new(path, Action::WithChecksums, Reason::Corrupt));
+            }
+        }
+    }
+
+    log::info!("Corrupt files: {}", corrupted);
+    if delete {
+        let mut summary = Summary::default();
+        remove::execute(&repo_path, candidates, options, &mut summary);
+        summary.finish(&repo_path, options);
+    }
+    corrupted == 0
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// 只检查 POM 和 jar/war, 其他文件返回 None
+pub fn check_file(path: &Path, file_name: &str) -> Option<io::Result<()>> {
+    if file_name.ends_with(POM_SUFFIX) {
+        Some(check_pom(path))
+    } else if ARCHIVE_SUFFIXIES
+        .iter()
+        .any(|suffix| file_name.ends_with(suffix))
+    {
+        Some(check_archive(path))
+    } else {
+        None
+    }
+}
+
+// 只报告格式错误; 编码和 DOCTYPE 都是合法的 POM 写法
+pub fn check_pom(path: &Path) -> io::Result<()> {
+    let content = decode(&std::fs::read(path)?)?;
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    roxmltree::Document::parse_with_options(&content, options)
+        .map_err(|e| invalid(&e.to_string()))?;
+    Ok(())
+}
+
+// 按字节序标记或 XML 声明中的编码解码; 声明了其他编码或不是有效的 UTF-8 时按 Latin-1 解码,
+// 单字节编码的每个字节都对应一个字符, 足以检查格式
+fn decode(bytes: &[u8]) -> io::Result<String> {
+    let utf16 = |bytes: &[u8], unit: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| unit([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16(&units).map_err(|e| invalid(&e.to_string()))
+    };
+    if let Some(bytes) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return utf16(bytes, u16::from_le_bytes);
+    }
+    if let Some(bytes) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        return utf16(bytes, u16::from_be_bytes);
+    }
+    let bytes = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+    let utf8 =
+        declared_encoding(bytes).is_none_or(|encoding| encoding.eq_ignore_ascii_case("UTF-8"));
+    match std::str::from_utf8(bytes) {
+        Ok(text) if utf8 => Ok(text.to_string()),
+        _ => Ok(bytes.iter().map(|&byte| byte as char).collect()),
+    }
+}
+
+// <?xml version="1.0" encoding="..."?> 中的编码名
+fn declared_encoding(bytes: &[u8]) -> Option<&str> {
+    let declaration = bytes.strip_prefix(b"<?xml")?;
+    let end = declaration.windows(2).position(|pair| pair == b"?>")?;
+    let declaration = std::str::from_utf8(&declaration[..end]).ok()?;
+    let (_, rest) = declaration.split_once("encoding")?;
+    let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    rest[1..].split(quote).next()
+}
+
+pub fn check_archive(path: &Path) -> io::Result<()> {
+    let mut file = File::open(path)?;
+    let file_size = file.metadata()?.len();
+    if file_size < EOCD_SIZE {
+        return Err(invalid("file too small"));
+    }
+
+    // 中央目录结束记录在文件末尾, 其后最多跟随 65535 字节的注释
+    let tail_size = file_size.min(EOCD_SIZE + u16::MAX as u64);
+    let mut tail = vec![0u8; tail_size as usize];
+    file.seek(SeekFrom::Start(file_size - tail_size))?;
+    file.read_exact(&mut tail)?;
+    let eocd = (0..=tail.len() - EOCD_SIZE as usize)
+        .rev()
+        .find(|&i| read_u32(&tail, i) == EOCD_SIGNATURE)
+        .ok_or_else(|| invalid("end of central directory not found"))?;
+    let eocd_offset = file_size - tail_size + eocd as u64;
+
+    let mut entries = read_u16(&tail, eocd + 10) as u64;
+    let mut cd_size = read_u32(&tail, eocd + 12) as u64;
+    let mut cd_offset = read_u32(&tail, eocd + 16) as u64;
+    let mut cd_end = eocd_offset;
+    if entries == u16::MAX as u64 || cd_size == u32::MAX as u64 || cd_offset == u32::MAX as u64 {
+        if eocd < 20 || read_u32(&tail, eocd - 20) != ZIP64_LOCATOR_SIGNATURE {
+            return Err(invalid("zip64 locator not found"));
+        }
+        let zip64_offset = read_u64(&tail, eocd - 12);
+        let mut zip64_eocd = [0u8; 56];
+        file.seek(SeekFrom::Start(zip64_offset))?;
+        file.read_exact(&mut zip64_eocd)?;
+        if read_u32(&zip64_eocd, 0) != ZIP64_EOCD_SIGNATURE {
+            return Err(invalid("zip64 end of central directory not found"));
+        }
+        entries = read_u64(&zip64_eocd, 32);
+        cd_size = read_u64(&zip64_eocd, 40);
+        cd_offset = read_u64(&zip64_eocd, 48);
+        cd_end = zip64_offset;
+    }
+    if cd_offset
+        .checked_add(cd_size)
+        .is_none_or(|end| end > cd_end)
+    {
+        return Err(invalid("central directory out of bounds"));
+    }
+
+    let mut cd = vec![0u8; cd_size as usize];
+    file.seek(SeekFrom::Start(cd_offset))?;
+    file.read_exact(&mut cd)?;
+    let mut position = 0;
+    let mut local_header = [0u8; 4];
+    for _ in 0..entries {
+        if position + CENTRAL_HEADER_SIZE > cd.len()
+            || read_u32(&cd, position) != CENTRAL_HEADER_SIGNATURE
+        {
+            return Err(invalid("truncated central directory"));
+        }
+        let name_size = read_u16(&cd, position + 28) as usize;
+        let extra_size = read_u16(&cd, position + 30) as usize;
+        let comment_size = read_u16(&cd, position + 32) as usize;
+        let local_offset = read_u32(&cd, position + 42) as u64;
+        // zip64 的实际偏移在扩展字段中, 跳过本地文件头校验
+        if local_offset != u32::MAX as u64 {
+            if local_offset >= cd_offset {
+                return Err(invalid("local header out of bounds"));
+            }
+            file.seek(SeekFrom::Start(local_offset))?;
+            file.read_exact(&mut local_header)?;
+            if read_u32(&local_header, 0) != LOCAL_HEADER_SIGNATURE {
+                return Err(invalid("local header signature mismatch"));
+            }
+        }
+        position += CENTRAL_HEADER_SIZE + name_size + extra_size + comment_size;
+    }
+    if position > cd.len() {
+        return Err(invalid("truncated central directory"));
+    }
+    Ok(())
+}
+
+fn read_u16(buf: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([buf[offset], buf[offset + 1]])
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(buf[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(buf: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(buf[offset..offset + 8].try_into().unwrap())
+}
@@ -0,0 +1,157 @@
+use crate::fs::RepoFs;
+use crate::remove::Candidate;
+use crate::summary::{Reason, Summary};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// 每行一条记录; 删除一批文件前写入 intent, 完成后写入 done, 每条记录都先落盘再继续
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "kebab-case")]
+enum Record {
+    Run {
+        repo: PathBuf,
+        started: u64,
+    },
+    Intent {
+        batch: usize,
+        reason: String,
+        files: Vec<(PathBuf, usize)>,
+    },
+    Done {
+        batch: usize,
+        deleted: Vec<PathBuf>,
+    },
+}
+
+// 同一个日志文件可以记录多次运行, 批次编号在每次运行内递增
+pub struct Journal {
+    file: Mutex<File>,
+    next: AtomicUsize,
+}
+
+impl Journal {
+    pub fn open(path: &Path, repo_path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let journal = Journal {
+            file: Mutex::new(file),
+            next: AtomicUsize::new(0),
+        };
+        journal.append(&Record::Run {
+            repo: repo_path.to_path_buf(),
+            started: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default(),
+        })?;
+        Ok(journal)
+    }
+
+    // 返回批次编号, 写入失败时不应继续删除
+    pub fn begin(&self, candidate: &Candidate, files: Vec<(PathBuf, usize)>) -> io::Result<usize> {
+        let batch = self.next.fetch_add(1, Ordering::Relaxed);
+        self.append(&Record::Intent {
+            batch,
+            reason: candidate.reason.to_string(),
+            files,
+        })?;
+        Ok(batch)
+    }
+
+    pub fn commit(&self, batch: usize, summary: &Summary) {
+        let record = Record::Done {
+            batch,
+            deleted: summary
+                .deleted
+                .iter()
+                .map(|deletion| deletion.path.clone())
+                .collect(),
+        };
+        if let Err(e) = self.append(&record) {
+            log::error!("Failed to write journal: {}", e);
+        }
+    }
+
+    fn append(&self, record: &Record) -> io::Result<()> {
+        let mut line = serde_json::to_string(record).map_err(io::Error::other)?;
+        line.push('\n');
+        let mut file = self.file.lock().unwrap();
+        file.write_all(line.as_bytes())?;
+        file.sync_data()
+    }
+}
+
+// 批次的删除原因和将删除的文件
+type Batch = (Reason, Vec<(PathBuf, usize)>);
+
+pub struct Replay {
+    // 最后一次运行清理的仓库
+    pub repo_path: PathBuf,
+    pub summary: Summary,
+    // 写入 intent 后未完成的批次, 通常是进程崩溃或被终止
+    pub interrupted: usize,
+}
+
+// 完成的批次按 done 中记录的文件统计; 中断的批次以文件是否还存在为准
+pub fn replay(path: &Path, fs: &dyn RepoFs) -> Result<Replay, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut replay = Replay {
+        repo_path: PathBuf::new(),
+        summary: Summary::default(),
+        interrupted: 0,
+    };
+    let mut pending: BTreeMap<usize, Batch> = BTreeMap::new();
+    for (number, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: Record = match serde_json::from_str(line) {
+            Ok(record) => record,
+            // 崩溃时最后一行可能只写了一半
+            Err(e) => {
+                log::warn!("Skipping journal line {}: {}", number + 1, e);
+                continue;
+            }
+        };
+        match record {
+            Record::Run { repo, .. } => {
+                finish_interrupted(&mut replay, std::mem::take(&mut pending), fs);
+                replay.repo_path = repo;
+            }
+            Record::Intent {
+                batch,
+                reason,
+                files,
+            } => {
+                pending.insert(batch, (Reason::from_code(&reason), files));
+            }
+            Record::Done { batch, deleted } => {
+                if let Some((reason, files)) = pending.remove(&batch) {
+                    for (file, size) in files {
+                        if deleted.contains(&file) {
+                            replay.summary.record(reason, &file, size);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    finish_interrupted(&mut replay, pending, fs);
+    Ok(replay)
+}
+
+fn finish_interrupted(replay: &mut Replay, pending: BTreeMap<usize, Batch>, fs: &dyn RepoFs) {
+    for (_, (reason, files)) in pending {
+        replay.interrupted += 1;
+        for (file, size) in files {
+            if !fs.exists(&file) {
+                replay.summary.record(reason, &file, size);
+            }
+        }
+    }
+}
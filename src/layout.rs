@@ -0,0 +1,268 @@
+use crate::fs::RepoFs;
+use crate::gav::{self, Gav};
+use crate::remote_repositories::REMOTE_REPOSITORIES_FILE;
+use crate::snapshot::Build;
+use crate::{get_file_name, unicode, xml, SNAPSHOT_SUFFIX};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+// Maven 和 Gradle 发布时附带的校验文件
+pub static CHECKSUM_SUFFIXES: [&str; 4] = [".sha1", ".md5", ".sha256", ".sha512"];
+
+pub static SIGNATURE_SUFFIX: &str = ".asc";
+
+// 下载失败标记、未完成的下载和锁文件, 不是构件的一部分
+pub static TRANSIENT_SUFFIXES: [&str; 3] = [".lastUpdated", ".part", ".lock"];
+
+pub static LOCK_SUFFIX: &str = ".lock";
+
+// Maven Resolver 的命名锁目录, 其中的文件名可能是没有后缀的摘要
+pub static LOCKS_DIR: &str = ".locks";
+
+static METADATA_PREFIX: &str = "maven-metadata";
+
+static RESOLVER_STATUS_FILE: &str = "resolver-status.properties";
+
+// 仓库中的文件: group/.../artifact/version/artifact-version[-classifier].extension
+// 名称统一为 NFC, 不区分大小写的文件系统上忽略大小写
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub gav: Option<Gav>,
+    pub file_name: String,
+    // 文件名不符合 artifactId-version 命名时为 None, 如 _remote.repositories
+    pub artifact: Option<ArtifactFile>,
+    // 属于所在版本目录的合法文件名, 见 is_expected
+    pub expected: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactFile {
+    pub classifier: Option<String>,
+    // 不含校验和签名后缀, 如 jar, tar.gz
+    pub extension: String,
+    // 时间戳快照的构建信息
+    pub build: Option<Build>,
+    // .sha1, .md5, .sha256 或 .sha512
+    pub checksum: Option<&'static str>,
+    pub signature: bool,
+}
+
+// 版本目录中发布的一个构件, 快照构建的版本带时间戳 (如 1.0-20240101.120000-3)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Published {
+    pub classifier: Option<String>,
+    pub extension: String,
+    pub version: String,
+}
+
+// 所在目录的坐标, 深度不足三层时为 None
+pub fn gav(repo_path: &Path, version_dir: &Path) -> Option<Gav> {
+    let nfc = |text: &str| unicode::nfc(text).into_owned();
+    gav::from_version_dir(repo_path, version_dir).map(|gav| Gav {
+        group_id: nfc(&gav.group_id),
+        artifact_id: nfc(&gav.artifact_id),
+        version: nfc(&gav.version),
+    })
+}
+
+// 仓库根目录下 .locks 中的文件, 以及其他位置的 *.lock
+pub fn is_lock(repo_path: &Path, path: &Path) -> bool {
+    let in_locks = path.strip_prefix(repo_path).is_ok_and(|relative| {
+        relative
+            .components()
+            .next()
+            .is_some_and(|first| first.as_os_str() == LOCKS_DIR)
+    });
+    in_locks || get_file_name(path).is_some_and(|name| name.ends_with(LOCK_SUFFIX))
+}
+
+// published 为所在版本目录元数据中列出的构件, 见 published
+pub fn locate(
+    repo_path: &Path,
+    path: &Path,
+    ignore_case: bool,
+    published: &[Published],
+) -> Option<Entry> {
+    let file_name = unicode::nfc(&get_file_name(path)?).into_owned();
+    let gav = gav(repo_path, path.parent()?);
+    let artifact = gav
+        .as_ref()
+        .and_then(|gav| parse_file_name(gav, &file_name, ignore_case));
+    let expected = gav
+        .as_ref()
+        .is_some_and(|gav| is_expected(gav, &file_name, ignore_case, published));
+    Some(Entry {
+        gav,
+        file_name,
+        artifact,
+        expected,
+    })
+}
+
+// 版本目录中的合法文件: 该版本的 artifactId-version[-classifier].ext 及其校验和签名文件,
+// 以及仓库元数据; 时间戳快照构建只有列在元数据中的才合法
+pub fn is_expected(gav: &Gav, file_name: &str, ignore_case: bool, published: &[Published]) -> bool {
+    if is_metadata(file_name) {
+        return true;
+    }
+    if TRANSIENT_SUFFIXES
+        .iter()
+        .any(|suffix| file_name.ends_with(suffix))
+    {
+        return false;
+    }
+    match parse_file_name(gav, file_name, ignore_case) {
+        Some(artifact) if artifact.build.is_some() => expected_file_names(gav, published)
+            .iter()
+            .any(|name| unicode::strip_prefix(file_name, name, ignore_case) == Some("")),
+        Some(_) => true,
+        None => false,
+    }
+}
+
+// 快照目录中没有列在元数据里的时间戳构建, 清理时作为过时快照删除
+pub fn is_stale_build(
+    gav: &Gav,
+    file_name: &str,
+    ignore_case: bool,
+    published: &[Published],
+) -> bool {
+    parse_file_name(gav, file_name, ignore_case).is_some_and(|artifact| artifact.build.is_some())
+        && !is_expected(gav, file_name, ignore_case, published)
+}
+
+// maven-metadata*.xml (含校验文件)、_remote.repositories 和 resolver-status.properties
+pub fn is_metadata(file_name: &str) -> bool {
+    file_name == REMOTE_REPOSITORIES_FILE
+        || file_name == RESOLVER_STATUS_FILE
+        || (file_name.starts_with(METADATA_PREFIX) && strip_checksum(file_name).0.ends_with(".xml"))
+}
+
+fn strip_checksum(file_name: &str) -> (&str, Option<&'static str>) {
+    for suffix in CHECKSUM_SUFFIXES {
+        if let Some(name) = file_name.strip_suffix(suffix) {
+            return (name, Some(suffix));
+        }
+    }
+    (file_name, None)
+}
+
+// artifact-version[-classifier].extension[.asc][.sha1], 快照版本可能带时间戳
+pub fn parse_file_name(gav: &Gav, file_name: &str, ignore_case: bool) -> Option<ArtifactFile> {
+    let strip_prefix = |text, prefix: &str| unicode::strip_prefix(text, prefix, ignore_case);
+    let (name, checksum) = strip_checksum(file_name);
+    let (name, signature) = match name.strip_suffix(SIGNATURE_SUFFIX) {
+        Some(name) => (name, true),
+        None => (name, false),
+    };
+    let rest = strip_prefix(name, &format!("{}-", gav.artifact_id))?;
+    let (rest, build) = match strip_prefix(rest, &gav.version) {
+        Some(rest) => (rest, None),
+        None => {
+            let base = gav.version.strip_suffix(SNAPSHOT_SUFFIX)?;
+            let rest = strip_prefix(rest, &format!("{}-", base))?;
+            let (build, rest) = Build::split(rest)?;
+            (rest, Some(build))
+        }
+    };
+    let (classifier, extension) = match rest.strip_prefix('-') {
+        Some(rest) => {
+            let (classifier, extension) = rest.split_once('.')?;
+            (Some(classifier.to_string()), extension)
+        }
+        None => (None, rest.strip_prefix('.')?),
+    };
+    if extension.is_empty() {
+        return None;
+    }
+    Some(ArtifactFile {
+        classifier,
+        extension: extension.to_string(),
+        build,
+        checksum,
+        signature,
+    })
+}
+
+// 快照版本目录中 maven-metadata-*.xml 的 <snapshotVersions>
+pub fn read_snapshot_versions(content: &str) -> Result<Vec<Published>, String> {
+    let document = roxmltree::Document::parse(content).map_err(|e| e.to_string())?;
+    let versions = xml::child(document.root_element(), "versioning")
+        .and_then(|versioning| xml::child(versioning, "snapshotVersions"));
+    let versions = match versions {
+        Some(versions) => versions,
+        None => return Ok(Vec::new()),
+    };
+    Ok(xml::children(versions, "snapshotVersion")
+        .filter_map(|version| {
+            let field = |name| xml::child(version, name).map(xml::text);
+            Some(Published {
+                classifier: field("classifier").filter(|classifier| !classifier.is_empty()),
+                extension: field("extension")?,
+                version: field("value")?,
+            })
+        })
+        .collect())
+}
+
+// 快照版本目录中各个 maven-metadata*.xml 列出的构件; 正式版目录没有, 返回空
+pub fn published(fs: &dyn RepoFs, version_dir: &Path) -> Vec<Published> {
+    let is_snapshot = get_file_name(version_dir)
+        .is_some_and(|version| unicode::nfc(&version).ends_with(SNAPSHOT_SUFFIX));
+    if !is_snapshot {
+        return Vec::new();
+    }
+    let mut published = Vec::new();
+    for path in fs
+        .read_dir(version_dir)
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+    {
+        let is_metadata_xml = get_file_name(&path)
+            .is_some_and(|name| name.starts_with(METADATA_PREFIX) && name.ends_with(".xml"));
+        if !is_metadata_xml {
+            continue;
+        }
+        match fs
+            .read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| read_snapshot_versions(&content))
+        {
+            Ok(versions) => published.extend(versions),
+            Err(e) => log::debug!("Ignoring metadata '{}': {}", path.display(), e),
+        }
+    }
+    published
+}
+
+// 版本目录中全部合法的构件文件名: 每个构件及其签名, 以及它们的校验文件;
+// 快照构建另有本地安装的 -SNAPSHOT 副本
+pub fn expected_file_names(gav: &Gav, published: &[Published]) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    for artifact in published {
+        let mut versions = vec![artifact.version.as_str()];
+        if gav.version.ends_with(SNAPSHOT_SUFFIX) && artifact.version != gav.version {
+            versions.push(&gav.version);
+        }
+        for version in versions {
+            let classifier = artifact
+                .classifier
+                .as_ref()
+                .map(|classifier| format!("-{}", classifier))
+                .unwrap_or_default();
+            let name = format!(
+                "{}-{}{}.{}",
+                gav.artifact_id, version, classifier, artifact.extension
+            );
+            let signature = format!("{}{}", name, SIGNATURE_SUFFIX);
+            for name in [name, signature] {
+                for suffix in CHECKSUM_SUFFIXES {
+                    names.insert(format!("{}{}", name, suffix));
+                }
+                names.insert(name);
+            }
+        }
+    }
+    names
+}
@@ -0,0 +1,302 @@
+pub mod analyze;
+pub mod archive;
+#[cfg(feature = "async")]
+pub mod async_io;
+#[cfg(feature = "remote")]
+pub mod audit;
+pub mod baseline;
+pub mod bench;
+pub mod check;
+mod cleaner;
+pub mod compress;
+pub mod config;
+#[cfg(feature = "daemon")]
+pub mod daemon;
+#[cfg(feature = "daemon")]
+pub mod dashboard;
+pub mod dedup;
+pub mod diff;
+pub mod doctor;
+pub mod downloads;
+pub mod duration;
+pub mod explain;
+pub mod fixture;
+pub mod fs;
+pub mod gav;
+pub mod gha;
+pub mod hash;
+pub mod heal;
+pub mod history;
+pub mod hooks;
+pub mod i18n;
+pub mod index;
+pub mod integrity;
+pub mod journal;
+pub mod layout;
+pub mod memory_fs;
+pub mod pins;
+pub mod pipeline;
+#[cfg(feature = "plugins")]
+pub mod plugins;
+pub mod policy;
+pub mod pom;
+pub mod priority;
+pub mod progress;
+pub mod purge;
+pub mod relocate;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod remote_repositories;
+pub mod remove;
+pub mod retry;
+pub mod sbom;
+#[cfg(feature = "scripting")]
+pub mod script;
+pub mod settings;
+pub mod simulate;
+pub mod snapshot;
+pub mod summary;
+pub mod throttle;
+pub mod ttl;
+pub mod unicode;
+#[cfg(feature = "remote")]
+pub mod update;
+pub mod validate;
+pub mod verified;
+pub mod version;
+pub mod workspace;
+pub mod xattr;
+pub mod xml;
+
+pub use cleaner::{Cleaner, Removal};
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fs::DirEntry;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::mpsc;
+use std::sync::OnceLock;
+use std::time::Instant;
+use summary::Summary;
+
+pub static SNAPSHOT_SUFFIX: &str = "-SNAPSHOT";
+
+static ARTIFACT_SUFFIXIES: [&str; 8] = [
+    ".jar", ".war", ".ear", ".aar", ".pom", ".zip", ".module", ".tar.gz",
+];
+
+pub static CHECKSUM_SUFFIXIES: [&str; 2] = [".sha1", ".md5"];
+
+static UNITS: OnceLock<Units> = OnceLock::new();
+
+static FOLLOW_SYMLINKS: AtomicBool = AtomicBool::new(false);
+
+static ONE_FILE_SYSTEM: AtomicBool = AtomicBool::new(false);
+
+static MAX_DEPTH: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum Units {
+    #[value(help = "Powers of 1024 (KiB, MiB, GiB)")]
+    Binary,
+    #[value(help = "Powers of 1000 (kB, MB, GB)")]
+    Si,
+}
+
+// 遇到无权限读取的目录或文件时的处理方式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Unreadable {
+    #[default]
+    Error,
+    Skip,
+    Fatal,
+}
+
+#[derive(Default, Clone)]
+pub struct CleanupOptions {
+    #[cfg(feature = "remote")]
+    pub verifier: Option<remote::RemoteVerifier>,
+    pub known_repositories: Option<Vec<String>>,
+    pub from_repo: Option<String>,
+    pub archive_dir: Option<PathBuf>,
+    pub move_to: Option<PathBuf>,
+    pub quarantine_dir: Option<PathBuf>,
+    pub on_before_delete: Option<String>,
+    pub on_after_run: Option<String>,
+    pub incremental: bool,
+    pub explain: bool,
+    pub unreadable: Unreadable,
+    pub report: Option<PathBuf>,
+    pub report_format: summary::ReportFormat,
+    pub journal: Option<std::sync::Arc<journal::Journal>>,
+    pub throttle: Option<throttle::Limit>,
+    pub threads: usize,
+    pub fs: fs::FsHandle,
+    pub rules: policy::Rules,
+    pub protected_groups: policy::ProtectedGroups,
+    pub workspace: workspace::KeepSet,
+    pub pins: pins::Pins,
+    pub reasons: policy::ReasonFilter,
+    #[cfg(feature = "plugins")]
+    pub plugins: plugins::Plugins,
+    #[cfg(feature = "scripting")]
+    pub script: Option<std::sync::Arc<script::Script>>,
+    #[cfg(feature = "async")]
+    pub io_depth: usize,
+}
+
+pub fn cleanup(
+    repo_path: PathBuf,
+    options: &CleanupOptions,
+    watched: Option<HashSet<PathBuf>>,
+) -> Summary {
+    let (candidates, received) = mpsc::channel();
+    let started = Instant::now();
+    let (mut summary, index) = pipeline::run(&repo_path, options, watched, &mut |candidate| {
+        let _ = candidates.send(candidate);
+    });
+    drop(candidates);
+    summary.stats.scan_time = started.elapsed();
+    if summary.aborted {
+        return summary;
+    }
+    let started = Instant::now();
+    pipeline::executor(&repo_path, received, options, &mut summary);
+    summary.stats.delete_time = started.elapsed();
+    summary.stats.peak_memory = summary::peak_memory();
+    if let Some(index) = index {
+        index.save(&repo_path);
+    }
+    summary.finish(&repo_path, options);
+    summary
+}
+
+pub fn get_file_name(path: &Path) -> Option<String> {
+    match path.file_name() {
+        None => None,
+        Some(folder_name) => folder_name
+            .to_str()
+            .map(|folder_name| folder_name.to_string()),
+    }
+}
+
+pub fn read_dir_sorted(
+    path: &Path,
+) -> std::io::Result<std::vec::IntoIter<std::io::Result<DirEntry>>> {
+    let mut entries: Vec<_> = std::fs::read_dir(path)?.collect();
+    entries.sort_by(|a, b| match (a, b) {
+        (Ok(a), Ok(b)) => a.file_name().cmp(&b.file_name()),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => Ordering::Equal,
+    });
+    Ok(entries.into_iter())
+}
+
+pub fn is_artifact(file_name: &str) -> bool {
+    let file_name = file_name
+        .strip_suffix(compress::COMPRESSED_SUFFIX)
+        .unwrap_or(file_name);
+    ARTIFACT_SUFFIXIES
+        .iter()
+        .any(|suffix| file_name.ends_with(suffix))
+}
+
+pub fn set_follow_symlinks(follow: bool) {
+    FOLLOW_SYMLINKS.store(follow, AtomicOrdering::Relaxed);
+}
+
+pub fn follows_symlinks() -> bool {
+    FOLLOW_SYMLINKS.load(AtomicOrdering::Relaxed)
+}
+
+pub fn set_one_file_system(enabled: bool) {
+    ONE_FILE_SYSTEM.store(enabled, AtomicOrdering::Relaxed);
+}
+
+pub fn one_file_system() -> bool {
+    ONE_FILE_SYSTEM.load(AtomicOrdering::Relaxed)
+}
+
+// 仓库根目录下的层数, 根目录的直接子项为 1, 更深的内容不遍历
+pub fn set_max_depth(depth: usize) {
+    MAX_DEPTH.store(depth, AtomicOrdering::Relaxed);
+}
+
+pub fn max_depth() -> usize {
+    MAX_DEPTH.load(AtomicOrdering::Relaxed)
+}
+
+// 目录与父目录设备号不同即为挂载点
+pub fn is_mount_point(fs: &dyn fs::RepoFs, path: &Path) -> bool {
+    let device = |path: &Path| fs.metadata(path).ok().and_then(|m| m.device);
+    match (device(path), path.parent().and_then(device)) {
+        (Some(device), Some(parent)) => device != parent,
+        _ => false,
+    }
+}
+
+// 默认不进入符号链接目录, 避免遍历到仓库之外
+pub fn is_walkable_dir(fs: &dyn fs::RepoFs, path: &Path) -> bool {
+    fs.metadata(path)
+        .is_ok_and(|metadata| metadata.is_dir && (follows_symlinks() || !metadata.is_symlink))
+        && !(one_file_system() && is_mount_point(fs, path))
+}
+
+pub fn set_units(units: Units) {
+    let _ = UNITS.set(units);
+}
+
+pub fn human_size(size: usize) -> String {
+    let (base, units) = match UNITS.get() {
+        Some(Units::Si) => (1000.0, ["kB", "MB", "GB"]),
+        _ => (1024.0, ["KiB", "MiB", "GiB"]),
+    };
+    let s = size as f64;
+    match s {
+        s if s >= base * base * base => format!("{:.2} {}", s / (base * base * base), units[2]),
+        s if s >= base * base => format!("{:.2} {}", s / (base * base), units[1]),
+        s if s >= base => format!("{:.2} {}", s / base, units[0]),
+        _ => format!("{} B", size),
+    }
+}
+
+pub fn size_unit(unit: &str) -> Option<f64> {
+    match unit {
+        "" | "B" => Some(1.0),
+        "KiB" => Some(1024.0),
+        "MiB" => Some(1024.0 * 1024.0),
+        "GiB" => Some(1024.0 * 1024.0 * 1024.0),
+        "TiB" => Some(1024.0 * 1024.0 * 1024.0 * 1024.0),
+        "kB" => Some(1000.0),
+        "MB" => Some(1000.0 * 1000.0),
+        "GB" => Some(1000.0 * 1000.0 * 1000.0),
+        "TB" => Some(1000.0 * 1000.0 * 1000.0 * 1000.0),
+        _ => None,
+    }
+}
+
+// 支持: 5GiB, 500MB, 1.5TB, 4096
+pub fn parse_size(text: &str) -> Result<usize, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid number: {}", text))?;
+    let multiplier = size_unit(unit.trim()).ok_or_else(|| format!("invalid unit: {}", unit))?;
+    Ok((number * multiplier) as usize)
+}
+
+// 附带精确字节数, 方便下游工具解析
+pub fn format_size(size: usize) -> String {
+    let text = human_size(size);
+    if text.ends_with(" B") {
+        text
+    } else {
+        format!("{} ({} bytes)", text, size)
+    }
+}
@@ -0,0 +1,182 @@
+use crate::fs::{Metadata, RepoFs};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+enum Node {
+    Dir,
+    File(Vec<u8>),
+}
+
+// 内存中的文件系统, 用于测试清理策略
+#[derive(Default)]
+pub struct MemoryFs {
+    nodes: Mutex<BTreeMap<PathBuf, Node>>,
+    modified: Mutex<BTreeMap<PathBuf, SystemTime>>,
+    case_insensitive: bool,
+}
+
+impl MemoryFs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 自动创建所有上级目录
+    pub fn file(self, path: impl AsRef<Path>, content: impl Into<Vec<u8>>) -> Self {
+        let path = path.as_ref();
+        {
+            let mut nodes = self.nodes.lock().unwrap();
+            for ancestor in path.ancestors().skip(1) {
+                if ancestor.as_os_str().is_empty() {
+                    break;
+                }
+                nodes.insert(ancestor.to_path_buf(), Node::Dir);
+            }
+            nodes.insert(path.to_path_buf(), Node::File(content.into()));
+        }
+        self
+    }
+
+    pub fn dir(self, path: impl AsRef<Path>) -> Self {
+        self.nodes
+            .lock()
+            .unwrap()
+            .insert(path.as_ref().to_path_buf(), Node::Dir);
+        self
+    }
+
+    // 设置文件或目录的修改时间, 未设置时为 None
+    pub fn modified(self, path: impl AsRef<Path>, time: SystemTime) -> Self {
+        self.modified
+            .lock()
+            .unwrap()
+            .insert(path.as_ref().to_path_buf(), time);
+        self
+    }
+
+    // 只影响名称比较, 查找路径仍区分大小写
+    pub fn case_insensitive(mut self) -> Self {
+        self.case_insensitive = true;
+        self
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.nodes
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, node)| matches!(node, Node::File(_)))
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("no such file or directory: {}", path.display()),
+    )
+}
+
+impl RepoFs for MemoryFs {
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<io::Result<PathBuf>>> {
+        let nodes = self.nodes.lock().unwrap();
+        match nodes.get(path) {
+            Some(Node::Dir) => {}
+            Some(Node::File(_)) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotADirectory,
+                    format!("not a directory: {}", path.display()),
+                ))
+            }
+            None => return Err(not_found(path)),
+        }
+        let mut children: Vec<PathBuf> = nodes
+            .keys()
+            .filter(|child| child.parent() == Some(path))
+            .cloned()
+            .collect();
+        children.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+        Ok(children.into_iter().map(Ok).collect())
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        let modified = self.modified.lock().unwrap().get(path).copied();
+        match self.nodes.lock().unwrap().get(path) {
+            Some(Node::Dir) => Ok(Metadata {
+                is_dir: true,
+                is_symlink: false,
+                len: 0,
+                device: None,
+                modified,
+            }),
+            Some(Node::File(content)) => Ok(Metadata {
+                is_dir: false,
+                is_symlink: false,
+                len: content.len() as u64,
+                device: None,
+                modified,
+            }),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        match self.nodes.lock().unwrap().get(path) {
+            Some(Node::File(content)) => String::from_utf8(content.clone())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Some(Node::Dir) => Err(io::Error::new(
+                io::ErrorKind::IsADirectory,
+                format!("is a directory: {}", path.display()),
+            )),
+            None => Err(not_found(path)),
+        }
+    }
+
+    // 与真实文件系统一样, 上级目录必须已存在
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        match path.parent().map(|parent| nodes.get(parent)) {
+            Some(Some(Node::Dir)) => {}
+            _ => return Err(not_found(path)),
+        }
+        if matches!(nodes.get(path), Some(Node::Dir)) {
+            return Err(io::Error::new(
+                io::ErrorKind::IsADirectory,
+                format!("is a directory: {}", path.display()),
+            ));
+        }
+        nodes.insert(path.to_path_buf(), Node::File(contents.to_vec()));
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        match nodes.get(path) {
+            Some(Node::File(_)) => {
+                nodes.remove(path);
+                Ok(())
+            }
+            Some(Node::Dir) => Err(io::Error::new(
+                io::ErrorKind::IsADirectory,
+                format!("is a directory: {}", path.display()),
+            )),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        if !matches!(nodes.get(path), Some(Node::Dir)) {
+            return Err(not_found(path));
+        }
+        nodes.retain(|node, _| !node.starts_with(path));
+        Ok(())
+    }
+
+    fn is_case_insensitive(&self, _repo_path: &Path) -> bool {
+        self.case_insensitive
+    }
+}
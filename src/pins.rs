@@ -0,0 +1,82 @@
+use crate::fs::RepoFs;
+use crate::gav::VersionDir;
+use crate::policy::{Decision, LatestVersions, Rule, Rules};
+use crate::CleanupOptions;
+use std::borrow::Cow;
+use std::io;
+use std::path::Path;
+
+// 仓库根目录下的固定文件, 共享仓库的每个用户都可以编辑
+pub static PINS_FILE: &str = ".mvnclean-pins";
+
+// 每行一个 groupId:artifactId[:version], 可使用 * 和 ? 通配符, 忽略空行和 # 注释;
+// 无效的行跳过并报错, 不影响其他固定项
+pub fn load(fs: &dyn RepoFs, repo_path: &Path) -> Vec<Rule> {
+    let path = repo_path.join(PINS_FILE);
+    let content = match fs.read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Vec::new(),
+        Err(e) => {
+            log::error!("Failed to read '{}': {}", path.display(), e);
+            return Vec::new();
+        }
+    };
+    let mut pins = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        match Rule::pinned(line) {
+            Ok(rule) => pins.push(rule),
+            Err(e) => log::error!("Invalid pin at {}:{}: {}", path.display(), number + 1, e),
+        }
+    }
+    log::debug!("Pinned coordinates: {}", pins.len());
+    pins
+}
+
+// 固定的坐标, 在保护组和工作区之后、已下线仓库和插件之前检查
+#[derive(Debug, Default, Clone)]
+pub struct Pins(Option<Rules>);
+
+impl Pins {
+    pub fn is_pinned(&self, fs: &dyn RepoFs, repo_path: &Path, path: &Path) -> bool {
+        self.0.as_ref().is_some_and(|rules| {
+            let mut latest = LatestVersions::default();
+            matches!(
+                rules.decide(fs, repo_path, path, &mut latest),
+                (Decision::Keep, Some(_))
+            )
+        })
+    }
+
+    // 固定项只按坐标匹配, 版本目录中任一文件被固定即整个目录被固定
+    pub fn is_version_pinned(
+        &self,
+        fs: &dyn RepoFs,
+        repo_path: &Path,
+        version: &VersionDir,
+    ) -> bool {
+        self.0.is_some()
+            && version
+                .files
+                .iter()
+                .any(|file| self.is_pinned(fs, repo_path, &version.path.join(file)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+pub fn apply<'a>(repo_path: &Path, options: &'a CleanupOptions) -> Cow<'a, CleanupOptions> {
+    let pins = load(&*options.fs, repo_path);
+    if pins.is_empty() {
+        return Cow::Borrowed(options);
+    }
+    Cow::Owned(CleanupOptions {
+        pins: Pins(Some(Rules::new(pins))),
+        ..options.clone()
+    })
+}
@@ -0,0 +1,401 @@
+use crate::downloads::ActiveDownloads;
+use crate::index::{IndexWriter, INDEX_FILE};
+use crate::layout;
+use crate::pins;
+use crate::policy::{Decision, LatestVersions};
+use crate::progress::ScanProgress;
+use crate::remote_repositories::{self, SourceCache, REMOTE_REPOSITORIES_FILE};
+use crate::remove::{self, Action, Candidate};
+use crate::summary::Summary;
+use crate::ttl::ExpiryCache;
+use crate::{
+    follows_symlinks, get_file_name, is_mount_point, max_depth, one_file_system, CleanupOptions,
+    Unreadable,
+};
+use std::collections::HashSet;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+
+// 清理分为三个阶段, 由通道连接: scanner 遍历仓库产生文件条目, policy 把条目判定为删除候选,
+// executor 执行删除. 预览 (check/verify)、嵌入调用和其他删除后端只需替换最后一个阶段
+const CHANNEL_CAPACITY: usize = 1024;
+
+// scanner 产生的文件条目
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub path: PathBuf,
+    pub size: usize,
+}
+
+// scanner 在后台线程遍历, policy 在当前线程判定, 每个候选交给 emit
+pub fn run(
+    repo_path: &Path,
+    options: &CleanupOptions,
+    watched: Option<HashSet<PathBuf>>,
+    emit: &mut dyn FnMut(Candidate),
+) -> (Summary, Option<IndexWriter>) {
+    let options = &*pins::apply(repo_path, options);
+    let index = open_index(repo_path, options, watched);
+    let found = AtomicUsize::new(0);
+    let (entries, received) = mpsc::sync_channel(CHANNEL_CAPACITY);
+    std::thread::scope(|scope| {
+        let scanner = scope.spawn(|| scanner(repo_path, options, index, entries, &found));
+        let mut revisit = HashSet::new();
+        let mut summary = policy(
+            repo_path,
+            options,
+            received,
+            &mut revisit,
+            &mut |candidate| {
+                found.fetch_add(1, Ordering::Relaxed);
+                emit(candidate)
+            },
+        );
+        let (scanned, mut index) = scanner.join().unwrap();
+        summary.merge(scanned);
+        if let Some(index) = &mut index {
+            index.revisit(revisit);
+        }
+        (summary, index)
+    })
+}
+
+fn open_index(
+    repo_path: &Path,
+    options: &CleanupOptions,
+    watched: Option<HashSet<PathBuf>>,
+) -> Option<IndexWriter> {
+    options.incremental.then(|| {
+        #[allow(unused_mut)]
+        let mut policy = format!(
+            "{:?}/{:?}/{:?}/{:?}/{:?}/{:?}/{:?}",
+            options.known_repositories,
+            options.from_repo,
+            options.rules,
+            options.protected_groups,
+            options.workspace,
+            options.pins,
+            options.reasons
+        );
+        // 插件变化后需要重新扫描
+        #[cfg(feature = "plugins")]
+        policy.push_str(&format!("/{:?}", options.plugins));
+        let index = IndexWriter::open(repo_path.join(INDEX_FILE), policy);
+        match watched {
+            Some(watched) => index.with_watched(watched),
+            None => index,
+        }
+    })
+}
+
+// 深度优先, 栈中只保存待读取的目录; 文件在读取所在目录时就地发出,
+// 内存占用与目录深度和单个目录的大小相关, 与仓库中的文件总数无关.
+// 仓库根目录本身可以是链接. 中止时不返回索引
+pub fn scanner(
+    repo_path: &Path,
+    options: &CleanupOptions,
+    mut index: Option<IndexWriter>,
+    entries: SyncSender<Entry>,
+    found: &AtomicUsize,
+) -> (Summary, Option<IndexWriter>) {
+    let mut summary = Summary::default();
+    let fs = &*options.fs;
+    let mut progress = ScanProgress::default();
+    let mut stack = vec![(repo_path.to_path_buf(), 0)];
+    while let Some((path, depth)) = stack.pop() {
+        progress.update(summary.stats.dirs_visited, found.load(Ordering::Relaxed));
+        if get_file_name(&path).is_none() && path != repo_path {
+            continue;
+        }
+        let children = match index
+            .as_mut()
+            .and_then(|index| index.unchanged(repo_path, &path))
+        {
+            // 未变化的目录只需继续检查其子目录
+            Some(children) => {
+                log::trace!("Unchanged: {}", path.display());
+                children.iter().map(|child| Ok(path.join(child))).collect()
+            }
+            None => {
+                if let Some(index) = &mut index {
+                    index.mark_changed(&path);
+                }
+                match fs.read_dir(&path) {
+                    Ok(children) => {
+                        summary.stats.dirs_visited += 1;
+                        log::debug!("Scanning: {}", path.display());
+                        children
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                    Err(e) => {
+                        if !unreadable(&mut summary, options, &path, &e) {
+                            return (summary, None);
+                        }
+                        continue;
+                    }
+                }
+            }
+        };
+        let mut subdirs = Vec::new();
+        for child in children {
+            let child = match child {
+                Ok(child) => child,
+                Err(e) => {
+                    log::error!("Failed to read directory entry: {:?}", e);
+                    summary.error();
+                    continue;
+                }
+            };
+            let metadata = match fs.metadata(&child) {
+                Ok(metadata) => metadata,
+                // 已被其他规则删除
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => {
+                    if !unreadable(&mut summary, options, &child, &e) {
+                        return (summary, None);
+                    }
+                    continue;
+                }
+            };
+            if !follows_symlinks() && metadata.is_symlink {
+                log::debug!("Skipping symlink: {}", child.display());
+                summary.skip();
+                continue;
+            }
+            if !metadata.is_dir {
+                let entry = Entry {
+                    path: child,
+                    size: metadata.len as usize,
+                };
+                // policy 阶段已结束, 不必再遍历
+                if entries.send(entry).is_err() {
+                    return (summary, index);
+                }
+            } else if one_file_system() && is_mount_point(fs, &child) {
+                log::debug!("Skipping mount point: {}", child.display());
+            } else if depth + 1 >= max_depth() {
+                log::debug!("Skipping beyond --max-depth: {}", child.display());
+            } else {
+                subdirs.push((child, depth + 1));
+            }
+        }
+        // 逆序入栈, 保持按名称排序的遍历顺序
+        stack.extend(subdirs.into_iter().rev());
+    }
+    (summary, index)
+}
+
+// 按保护组、来源仓库和规则判定每个条目; 缓存只在本次扫描内有效.
+// 判定可能随时间或其他版本变化的目录记入 revisit, 增量扫描下次不跳过
+pub fn policy(
+    repo_path: &Path,
+    options: &CleanupOptions,
+    entries: Receiver<Entry>,
+    revisit: &mut HashSet<PathBuf>,
+    emit: &mut dyn FnMut(Candidate),
+) -> Summary {
+    let mut summary = Summary::default();
+    let fs = &*options.fs;
+    let mut sources = SourceCache::default();
+    let mut expiries = ExpiryCache::default();
+    let mut downloads = ActiveDownloads::default();
+    let mut latest = LatestVersions::default();
+    let mut archived = HashSet::new();
+    let explain = |path: &Path, verdict: &dyn fmt::Display, by: &dyn fmt::Display| {
+        if options.explain {
+            log::info!("Explain: {} -> {} ({})", path.display(), verdict, by);
+        }
+    };
+    let mut emit = |candidate: Candidate, size: usize, summary: &mut Summary| {
+        if !options.reasons.allows(candidate.reason) {
+            explain(
+                &candidate.path,
+                &"keep",
+                &"filtered by --only-reason/--skip-reason",
+            );
+            log::debug!(
+                "Filtered out ({}): {}",
+                candidate.reason,
+                candidate.path.display()
+            );
+            summary.skip();
+            return;
+        }
+        let version_dir = match candidate.action {
+            Action::VersionDir => Some(candidate.path.as_path()),
+            _ => candidate.path.parent(),
+        };
+        let gav = version_dir.and_then(|dir| layout::gav(repo_path, dir));
+        emit(Candidate {
+            gav,
+            size,
+            ..candidate
+        })
+    };
+    for Entry { path, size } in entries {
+        summary.stats.files_examined += 1;
+        let file_name = match get_file_name(&path) {
+            Some(file_name) => file_name,
+            None => continue,
+        };
+        if options.protected_groups.is_protected(repo_path, &path) {
+            log::trace!("Protected: {}", path.display());
+            explain(&path, &"keep", &"protected group");
+            summary.skip();
+            continue;
+        }
+        if options.workspace.is_kept(repo_path, &path) {
+            log::trace!("Used by workspace: {}", path.display());
+            explain(&path, &"keep", &"declared by a --workspace project");
+            summary.skip();
+            continue;
+        }
+        if options.pins.is_pinned(fs, repo_path, &path) {
+            log::trace!("Pinned: {}", path.display());
+            explain(&path, &"keep", &"pinned by .mvnclean-pins");
+            summary.skip();
+            continue;
+        }
+        let mut volatile = |path: &Path| {
+            if let Some(folder) = path.parent() {
+                revisit.insert(folder.to_path_buf());
+            }
+        };
+        if let Some(expiry) = expiries.active(fs, &path) {
+            volatile(&path);
+            log::trace!("Pinned: {}", path.display());
+            explain(&path, &"keep", &expiry);
+            summary.skip();
+            continue;
+        }
+        if downloads.contains(fs, &path) {
+            volatile(&path);
+            explain(&path, &"keep", &"download in progress");
+            summary.skip();
+            continue;
+        }
+        if REMOTE_REPOSITORIES_FILE.eq(&file_name) {
+            if let Some(known_repositories) = &options.known_repositories {
+                for candidate in
+                    remote_repositories::plan_decommissioned(&path, known_repositories, options)
+                {
+                    let size = planned_size(options, &candidate);
+                    explain(&candidate.path, &"delete", &candidate.reason);
+                    emit(candidate, size, &mut summary);
+                }
+                continue;
+            }
+        }
+        if let Some(from_repo) = &options.from_repo {
+            if sources.source(fs, &path) != Some(from_repo) {
+                explain(
+                    &path,
+                    &"keep",
+                    &format!("not from --from-repo {}", from_repo),
+                );
+                summary.skip();
+                continue;
+            }
+        }
+
+        #[cfg(feature = "plugins")]
+        let plugin = options.plugins.evaluate(repo_path, &path);
+        #[cfg(not(feature = "plugins"))]
+        let plugin: Option<Decision> = None;
+        let (decision, by) = match plugin {
+            Some(decision) => (decision, "plugin".to_string()),
+            None => {
+                let (decision, rule, varies) =
+                    options
+                        .rules
+                        .decide_volatile(fs, repo_path, &path, &mut latest);
+                if varies {
+                    volatile(&path);
+                }
+                match rule {
+                    Some(rule) => (decision, format!("rule {}", rule)),
+                    None => (decision, "no rule matched".to_string()),
+                }
+            }
+        };
+        explain(&path, &decision, &by);
+        match decision {
+            Decision::Delete(reason) => emit(
+                Candidate::new(path, Action::File, reason),
+                size,
+                &mut summary,
+            ),
+            Decision::Archive(reason) | Decision::DeleteDir(reason) => {
+                // 归档以版本目录为单位, 每个目录只产生一个候选
+                if let Some(folder) = path.parent() {
+                    if archived.insert(folder.to_path_buf()) {
+                        let candidate =
+                            Candidate::new(folder.to_path_buf(), Action::VersionDir, reason);
+                        let size = planned_size(options, &candidate);
+                        emit(candidate, size, &mut summary);
+                    }
+                }
+            }
+            Decision::Keep => summary.skip(),
+        }
+    }
+    summary
+}
+
+// 先收齐全部候选再删除, 以便显示进度和预计剩余时间, 并交给钩子和脚本过滤
+pub fn executor(
+    repo_path: &Path,
+    candidates: Receiver<Candidate>,
+    options: &CleanupOptions,
+    summary: &mut Summary,
+) {
+    remove::execute(
+        repo_path,
+        candidates.into_iter().collect(),
+        options,
+        summary,
+    );
+}
+
+fn planned_size(options: &CleanupOptions, candidate: &Candidate) -> usize {
+    remove::planned_files(&*options.fs, std::slice::from_ref(candidate))
+        .iter()
+        .map(|(_, size)| size)
+        .sum()
+}
+
+// 权限不足时按 options.unreadable 处理, 返回 false 表示中止扫描
+fn unreadable(
+    summary: &mut Summary,
+    options: &CleanupOptions,
+    path: &Path,
+    error: &io::Error,
+) -> bool {
+    if error.kind() != io::ErrorKind::PermissionDenied {
+        log::error!("Failed to read '{}': {}", path.display(), error);
+        summary.error();
+        return true;
+    }
+    match options.unreadable {
+        Unreadable::Error => {
+            log::error!("Failed to read '{}': {}", path.display(), error);
+            summary.error();
+        }
+        Unreadable::Skip => {
+            log::warn!("Skipping unreadable: {}", path.display());
+            summary.unreadable.push(path.to_path_buf());
+        }
+        Unreadable::Fatal => {
+            log::error!(
+                "Permission denied in strict mode, aborting: {}",
+                path.display()
+            );
+            summary.aborted = true;
+            return false;
+        }
+    }
+    true
+}
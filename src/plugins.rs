@@ -0,0 +1,234 @@
+// 规则插件: 以 C ABI 导出 mrc_plugin_v1 的动态库 (cdylib)
+use crate::gav;
+use crate::get_file_name;
+use crate::policy::Decision;
+use crate::summary::Reason;
+use std::ffi::{c_char, CStr, CString};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub const ABI_VERSION: u32 = 1;
+pub static ENTRY_SYMBOL: &str = "mrc_plugin_v1";
+
+pub const VERDICT_PASS: u32 = 0;
+pub const VERDICT_KEEP: u32 = 1;
+pub const VERDICT_DELETE: u32 = 2;
+pub const VERDICT_ARCHIVE: u32 = 3;
+
+// 不属于版本目录的字段为空字符串
+#[repr(C)]
+pub struct MrcFile {
+    pub path: *const c_char,
+    pub group: *const c_char,
+    pub artifact: *const c_char,
+    pub version: *const c_char,
+    pub file_name: *const c_char,
+}
+
+// reason 由插件持有, 至少在返回后保持有效; 为空时使用插件名
+#[repr(C)]
+pub struct MrcVerdict {
+    pub action: u32,
+    pub reason: *const c_char,
+}
+
+#[repr(C)]
+pub struct MrcPlugin {
+    pub abi_version: u32,
+    pub name: *const c_char,
+    pub evaluate: unsafe extern "C" fn(file: *const MrcFile) -> MrcVerdict,
+}
+
+type EntryFn = unsafe extern "C" fn() -> *const MrcPlugin;
+
+struct Plugin {
+    name: String,
+    path: PathBuf,
+    descriptor: *const MrcPlugin,
+}
+
+// 插件必须可在多个线程中同时调用; 动态库加载后不再卸载
+unsafe impl Send for Plugin {}
+unsafe impl Sync for Plugin {}
+
+#[derive(Clone, Default)]
+pub struct Plugins(Arc<Vec<Plugin>>);
+
+impl fmt::Debug for Plugins {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|plugin| &plugin.name))
+            .finish()
+    }
+}
+
+impl Plugins {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // 按文件名顺序询问插件, 第一个给出结论的插件生效
+    pub fn evaluate(&self, repo_path: &Path, path: &Path) -> Option<Decision> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let gav = path
+            .parent()
+            .and_then(|folder| gav::from_version_dir(repo_path, folder));
+        let text = |value: &str| CString::new(value).unwrap_or_default();
+        let path_text = text(&path.to_string_lossy());
+        let (group, artifact, version) = match &gav {
+            Some(gav) => (
+                text(&gav.group_id),
+                text(&gav.artifact_id),
+                text(&gav.version),
+            ),
+            None => (text(""), text(""), text("")),
+        };
+        let file_name = text(&get_file_name(path).unwrap_or_default());
+        let file = MrcFile {
+            path: path_text.as_ptr(),
+            group: group.as_ptr(),
+            artifact: artifact.as_ptr(),
+            version: version.as_ptr(),
+            file_name: file_name.as_ptr(),
+        };
+        for plugin in self.0.iter() {
+            let verdict = unsafe { ((*plugin.descriptor).evaluate)(&file) };
+            let reason = || {
+                let reason = if verdict.reason.is_null() {
+                    plugin.name.clone()
+                } else {
+                    unsafe { CStr::from_ptr(verdict.reason) }
+                        .to_string_lossy()
+                        .to_string()
+                };
+                Reason::from_code(&reason)
+            };
+            match verdict.action {
+                VERDICT_PASS => continue,
+                VERDICT_KEEP => return Some(Decision::Keep),
+                VERDICT_DELETE => return Some(Decision::Delete(reason())),
+                VERDICT_ARCHIVE => return Some(Decision::Archive(reason())),
+                action => log::warn!(
+                    "Plugin '{}' ({}) returned unknown action {} for {}",
+                    plugin.name,
+                    plugin.path.display(),
+                    action,
+                    path.display()
+                ),
+            }
+        }
+        None
+    }
+}
+
+pub fn load_dir(dir: &Path) -> Result<Plugins, String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| e.to_string())?;
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == std::env::consts::DLL_EXTENSION)
+        })
+        .collect();
+    paths.sort();
+    let mut plugins = Vec::new();
+    for path in paths {
+        let plugin = load(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        log::info!("Loaded plugin '{}' from {}", plugin.name, path.display());
+        plugins.push(plugin);
+    }
+    Ok(Plugins(Arc::new(plugins)))
+}
+
+fn load(path: &Path) -> Result<Plugin, String> {
+    let entry = unsafe { library::symbol(path, ENTRY_SYMBOL)? };
+    let entry: EntryFn = unsafe { std::mem::transmute(entry) };
+    let descriptor = unsafe { entry() };
+    if descriptor.is_null() {
+        return Err(format!("{} returned null", ENTRY_SYMBOL));
+    }
+    let abi_version = unsafe { (*descriptor).abi_version };
+    if abi_version != ABI_VERSION {
+        return Err(format!(
+            "unsupported ABI version {} (expected {})",
+            abi_version, ABI_VERSION
+        ));
+    }
+    let name = unsafe { (*descriptor).name };
+    let name = if name.is_null() {
+        get_file_name(path).unwrap_or_default()
+    } else {
+        unsafe { CStr::from_ptr(name) }
+            .to_string_lossy()
+            .to_string()
+    };
+    Ok(Plugin {
+        name,
+        path: path.to_path_buf(),
+        descriptor,
+    })
+}
+
+#[cfg(unix)]
+mod library {
+    use std::ffi::{c_void, CStr, CString};
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    pub unsafe fn symbol(path: &Path, name: &str) -> Result<*mut c_void, String> {
+        let file = CString::new(path.as_os_str().as_bytes()).map_err(|e| e.to_string())?;
+        let handle = libc::dlopen(file.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL);
+        if handle.is_null() {
+            return Err(last_error());
+        }
+        let name = CString::new(name).map_err(|e| e.to_string())?;
+        let symbol = libc::dlsym(handle, name.as_ptr());
+        if symbol.is_null() {
+            return Err(last_error());
+        }
+        Ok(symbol)
+    }
+
+    unsafe fn last_error() -> String {
+        let error = libc::dlerror();
+        if error.is_null() {
+            return "unknown error".to_string();
+        }
+        CStr::from_ptr(error).to_string_lossy().to_string()
+    }
+}
+
+#[cfg(windows)]
+mod library {
+    use std::ffi::{c_void, CString};
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::Path;
+    use windows_sys::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryW};
+
+    pub unsafe fn symbol(path: &Path, name: &str) -> Result<*mut c_void, String> {
+        let file: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+        let handle = LoadLibraryW(file.as_ptr());
+        if handle.is_null() {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+        let name = CString::new(name).map_err(|e| e.to_string())?;
+        match GetProcAddress(handle, name.as_ptr() as *const u8) {
+            Some(symbol) => Ok(symbol as *mut c_void),
+            None => Err(std::io::Error::last_os_error().to_string()),
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod library {
+    use std::ffi::c_void;
+    use std::path::Path;
+
+    pub unsafe fn symbol(_path: &Path, _name: &str) -> Result<*mut c_void, String> {
+        Err("plugins are not supported on this platform".to_string())
+    }
+}
@@ -0,0 +1,877 @@
+use crate::fs::RepoFs;
+use crate::gav::Gav;
+use crate::layout::Published;
+use crate::snapshot;
+use crate::summary::Reason;
+use crate::ttl;
+use crate::version::{self, VersionRange};
+use crate::{
+    duration, get_file_name, layout, unicode, CleanupOptions, CHECKSUM_SUFFIXIES, SNAPSHOT_SUFFIX,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+pub static LOCAL_METADATA_FILE: &str = "maven-metadata-local.xml";
+
+// 超过这个时间仍未释放的锁视为进程异常退出后遗留
+pub const LOCK_AGE: Duration = Duration::from_secs(60 * 60);
+
+static KEEP_MATCHING: &str = "keep-matching";
+
+static PINNED: &str = "pinned";
+
+// 只含这些文件和仓库元数据的目录没有可用的构件
+static METADATA_SUFFIXES: [&str; 3] = [".sha1", ".md5", ".lastUpdated"];
+
+static STALE_SNAPSHOT_FILES: [&str; 6] = [
+    "*.jar",
+    "*.jar.sha1",
+    "*.pom",
+    "*.pom.sha1",
+    "*.war",
+    "*.war.sha1",
+];
+
+// 删除当前使用的插件会破坏之后的每次构建, 默认不清理这些 group 及其子 group
+pub static DEFAULT_PROTECTED_GROUPS: [&str; 10] = [
+    "org.apache.maven.plugins",
+    "org.apache.maven.plugin-tools",
+    "org.apache.maven.extensions",
+    "org.apache.maven.surefire",
+    "org.apache.maven.shared",
+    "org.apache.maven.wagon",
+    "org.apache.maven.resolver",
+    "org.codehaus.plexus",
+    "org.codehaus.mojo",
+    "org.sonatype.plexus",
+];
+
+// 策略文件中可包含多个 [[policy]]
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PolicyFile {
+    #[serde(default)]
+    policy: Vec<Policy>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    pub name: String,
+    #[serde(default)]
+    pub purge_decommissioned: bool,
+    pub from_repo: Option<String>,
+    #[serde(default)]
+    pub rule: Vec<Rule>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RuleAction {
+    Keep,
+    Delete,
+    Archive,
+    KeepLatest,
+    DeleteDir,
+}
+
+impl fmt::Display for RuleAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RuleAction::Keep => "keep",
+            RuleAction::Delete => "delete",
+            RuleAction::Archive => "archive",
+            RuleAction::KeepLatest => "keep-latest",
+            RuleAction::DeleteDir => "delete-dir",
+        })
+    }
+}
+
+// 单个字符串或字符串列表, 支持 * 和 ? 通配符
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Patterns {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Patterns {
+    fn matches(&self, text: &str) -> bool {
+        match self {
+            Patterns::One(pattern) => glob_match(pattern, text),
+            Patterns::Many(patterns) => patterns.iter().any(|p| glob_match(p, text)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    pub name: String,
+    #[serde(default)]
+    pub priority: i64,
+    pub group: Option<Patterns>,
+    pub artifact: Option<Patterns>,
+    pub version: Option<Patterns>,
+    pub version_range: Option<String>,
+    pub file: Option<Patterns>,
+    #[serde(rename = "type")]
+    pub types: Option<Patterns>,
+    pub classifier: Option<Patterns>,
+    pub snapshot: Option<bool>,
+    pub timestamped: Option<bool>,
+    pub expected: Option<bool>,
+    pub superseded: Option<bool>,
+    pub metadata_only: Option<bool>,
+    pub lock: Option<bool>,
+    pub older_than: Option<String>,
+    pub action: RuleAction,
+    pub count: Option<usize>,
+    #[serde(skip)]
+    age: Option<Duration>,
+    #[serde(skip)]
+    range: Option<VersionRange>,
+    #[serde(skip, default = "default_reason")]
+    reason: Reason,
+}
+
+// 加载后由规则名确定
+fn default_reason() -> Reason {
+    Reason::Custom("")
+}
+
+pub fn load(path: &Path) -> Result<Vec<Policy>, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    parse(&content)
+}
+
+pub fn parse(content: &str) -> Result<Vec<Policy>, String> {
+    let mut file: PolicyFile = toml::from_str(content).map_err(|e| e.to_string())?;
+    if file.policy.is_empty() {
+        return Err("no [[policy]] defined".to_string());
+    }
+    for policy in &mut file.policy {
+        prepare(&mut policy.rule)?;
+    }
+    Ok(file.policy)
+}
+
+// 解析规则中的时间和版本范围; 策略文件和配置文件中的规则都需要
+pub fn prepare(rules: &mut [Rule]) -> Result<(), String> {
+    for rule in rules {
+        rule.age = match &rule.older_than {
+            Some(age) => Some(duration::parse(age).map_err(|e| format!("{}: {}", rule.name, e))?),
+            None => None,
+        };
+        rule.range = match &rule.version_range {
+            Some(range) => {
+                Some(VersionRange::parse(range).map_err(|e| format!("{}: {}", rule.name, e))?)
+            }
+            None => None,
+        };
+        if rule.action == RuleAction::KeepLatest && rule.count.is_none() {
+            return Err(format!("{}: keep-latest requires count", rule.name));
+        }
+        // 规则名作为删除原因的代码
+        rule.reason = Reason::from_code(&rule.name);
+    }
+    Ok(())
+}
+
+impl Policy {
+    pub fn apply(&self, base: &CleanupOptions, known_repositories: &[String]) -> CleanupOptions {
+        CleanupOptions {
+            known_repositories: self
+                .purge_decommissioned
+                .then(|| known_repositories.to_vec()),
+            from_repo: self.from_repo.clone(),
+            // 模拟需要完整扫描
+            incremental: false,
+            rules: self.rules(),
+            ..base.clone()
+        }
+    }
+
+    // 未定义规则的策略沿用内置规则
+    pub fn rules(&self) -> Rules {
+        if self.rule.is_empty() {
+            Rules::default()
+        } else {
+            Rules::new(self.rule.clone())
+        }
+    }
+
+    pub fn archives(&self) -> bool {
+        self.rule
+            .iter()
+            .any(|rule| rule.action == RuleAction::Archive)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Keep,
+    Delete(Reason),
+    Archive(Reason),
+    DeleteDir(Reason),
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Decision::Keep => write!(f, "keep"),
+            Decision::Delete(reason) => write!(f, "delete as {}", reason),
+            Decision::Archive(reason) => write!(f, "archive as {}", reason),
+            Decision::DeleteDir(reason) => write!(f, "delete directory as {}", reason),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rules(Arc<Vec<Rule>>);
+
+impl Default for Rules {
+    fn default() -> Self {
+        let rule = |reason| Rule::builtin(reason, RuleAction::Delete);
+        Rules::new(vec![
+            Rule {
+                metadata_only: Some(true),
+                ..Rule::builtin(Reason::MetadataOnly, RuleAction::DeleteDir)
+            },
+            Rule {
+                file: Some(Patterns::One(LOCAL_METADATA_FILE.to_string())),
+                ..rule(Reason::LocalMetadata)
+            },
+            Rule {
+                snapshot: Some(true),
+                expected: Some(false),
+                file: Some(Patterns::Many(
+                    STALE_SNAPSHOT_FILES.iter().map(|p| p.to_string()).collect(),
+                )),
+                ..rule(Reason::StaleSnapshot)
+            },
+            Rule::stale_locks(LOCK_AGE),
+        ])
+    }
+}
+
+// 仓库中一个文件的可匹配属性
+struct FileInfo {
+    gav: Option<Gav>,
+    file_name: String,
+    classifier: Option<String>,
+    file_type: String,
+    snapshot: bool,
+    timestamped: bool,
+    expected: bool,
+    lock: bool,
+    modified: Option<SystemTime>,
+}
+
+impl Rules {
+    // 优先级高的规则先匹配, 同优先级按定义顺序
+    pub fn new(mut rules: Vec<Rule>) -> Self {
+        rules.sort_by_key(|rule| std::cmp::Reverse(rule.priority));
+        Rules(Arc::new(rules))
+    }
+
+    // 命令行追加的规则排在同优先级的策略规则之前
+    pub fn with(&self, mut rules: Vec<Rule>) -> Self {
+        rules.extend(self.0.iter().cloned());
+        Rules::new(rules)
+    }
+
+    pub fn evaluate(
+        &self,
+        fs: &dyn RepoFs,
+        repo_path: &Path,
+        path: &Path,
+        latest: &mut LatestVersions,
+    ) -> Decision {
+        self.decide(fs, repo_path, path, latest).0
+    }
+
+    // 同 evaluate, 并返回作出决定的规则名; 没有规则匹配时为 None
+    pub fn decide(
+        &self,
+        fs: &dyn RepoFs,
+        repo_path: &Path,
+        path: &Path,
+        latest: &mut LatestVersions,
+    ) -> (Decision, Option<&str>) {
+        let (decision, rule, _) = self.first_match(fs, repo_path, path, latest, None);
+        (decision, rule)
+    }
+
+    // 同 decide, 并指出结果是否可能随时间或同一构件的其他版本而变化,
+    // 增量扫描时这类文件所在的目录即使未修改也要重新判定
+    pub fn decide_volatile(
+        &self,
+        fs: &dyn RepoFs,
+        repo_path: &Path,
+        path: &Path,
+        latest: &mut LatestVersions,
+    ) -> (Decision, Option<&str>, bool) {
+        self.first_match(fs, repo_path, path, latest, None)
+    }
+
+    // explain-path: 依次记录每条规则是否匹配, 直到作出决定的规则
+    pub fn trace(
+        &self,
+        fs: &dyn RepoFs,
+        repo_path: &Path,
+        path: &Path,
+        latest: &mut LatestVersions,
+    ) -> (Decision, Vec<Step>) {
+        let mut steps = Vec::new();
+        let (decision, _, _) = self.first_match(fs, repo_path, path, latest, Some(&mut steps));
+        (decision, steps)
+    }
+
+    fn first_match(
+        &self,
+        fs: &dyn RepoFs,
+        repo_path: &Path,
+        path: &Path,
+        latest: &mut LatestVersions,
+        mut steps: Option<&mut Vec<Step>>,
+    ) -> (Decision, Option<&str>, bool) {
+        let info = match file_info(fs, repo_path, path, latest) {
+            Some(info) => info,
+            None => return (Decision::Keep, None, false),
+        };
+        let mut volatile = false;
+        for rule in self.0.iter() {
+            // 其余条件都满足时, 这条规则是否匹配取决于当前时间或其他版本
+            volatile |= rule.varies() && rule.fixed_mismatch(&info).is_none();
+            let mismatch = rule.mismatch(&info).or_else(|| {
+                let version_dir = path.parent()?;
+                if rule.metadata_only.is_some_and(|metadata_only| {
+                    metadata_only != (info.gav.is_some() && latest.metadata_only(fs, version_dir))
+                }) {
+                    return Some("metadata_only");
+                }
+                if rule
+                    .superseded
+                    .is_some_and(|superseded| superseded != latest.superseded(fs, version_dir))
+                {
+                    return Some("superseded");
+                }
+                // 仍被构建持有的锁不算遗留
+                if rule.lock == Some(true) && fs.is_locked(path) {
+                    return Some("lock");
+                }
+                None
+            });
+            if let Some(steps) = steps.as_deref_mut() {
+                steps.push(Step {
+                    rule: rule.name.clone(),
+                    action: rule.action,
+                    mismatch,
+                });
+            }
+            if mismatch.is_some() {
+                continue;
+            }
+            let decision = match rule.action {
+                RuleAction::Keep => Decision::Keep,
+                RuleAction::Delete => Decision::Delete(rule.reason),
+                RuleAction::Archive => Decision::Archive(rule.reason),
+                RuleAction::DeleteDir => Decision::DeleteDir(rule.reason),
+                RuleAction::KeepLatest => {
+                    let rank = path
+                        .parent()
+                        .and_then(|version_dir| latest.rank(fs, version_dir));
+                    match rank {
+                        Some(rank) if rank >= rule.count.unwrap_or(0) => {
+                            Decision::Delete(rule.reason)
+                        }
+                        _ => Decision::Keep,
+                    }
+                }
+            };
+            return (decision, Some(&rule.name), volatile);
+        }
+        (Decision::Keep, None, volatile)
+    }
+}
+
+// 规则名、动作和第一个未满足的条件 (匹配时为 None)
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub rule: String,
+    pub action: RuleAction,
+    pub mismatch: Option<&'static str>,
+}
+
+impl Rule {
+    fn builtin(reason: Reason, action: RuleAction) -> Self {
+        Rule {
+            name: reason.code().to_string(),
+            priority: 0,
+            group: None,
+            artifact: None,
+            version: None,
+            version_range: None,
+            file: None,
+            types: None,
+            classifier: None,
+            snapshot: None,
+            timestamped: None,
+            expected: None,
+            superseded: None,
+            metadata_only: None,
+            lock: None,
+            older_than: None,
+            action,
+            count: None,
+            age: None,
+            range: None,
+            reason,
+        }
+    }
+
+    // --versions groupId:artifactId:RANGE, 删除范围内的版本; groupId 和 artifactId 支持通配符
+    pub fn delete_versions(spec: &str) -> Result<Self, String> {
+        let mut parts = spec.splitn(3, ':');
+        let (group, artifact, range) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(group), Some(artifact), Some(range))
+                if !group.is_empty() && !artifact.is_empty() =>
+            {
+                (group, artifact, range)
+            }
+            _ => return Err(format!("expected groupId:artifactId:RANGE, got '{}'", spec)),
+        };
+        let range = VersionRange::parse(range)?;
+        Ok(Rule {
+            group: Some(Patterns::One(group.to_string())),
+            artifact: Some(Patterns::One(artifact.to_string())),
+            version_range: Some(range.to_string()),
+            range: Some(range),
+            ..Rule::builtin(Reason::VersionRange, RuleAction::Delete)
+        })
+    }
+
+    // --drop-superseded-snapshots, 删除早于最新正式版安装时间的快照
+    pub fn superseded_snapshots() -> Self {
+        Rule {
+            snapshot: Some(true),
+            superseded: Some(true),
+            ..Rule::builtin(Reason::SupersededSnapshot, RuleAction::Delete)
+        }
+    }
+
+    // 内置规则和 --stale-locks AGE, 删除没有进程持有且超过 age 的锁文件
+    pub fn stale_locks(age: Duration) -> Self {
+        Rule {
+            lock: Some(true),
+            older_than: Some(duration::format(age)),
+            age: Some(age),
+            ..Rule::builtin(Reason::StaleLock, RuleAction::Delete)
+        }
+    }
+
+    // --keep-matching [groupId:artifactId:]PATTERN, 优先于其他所有规则
+    pub fn keep_versions(spec: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        let (group, artifact, version) = match parts[..] {
+            [version] => (None, None, version),
+            [group, artifact, version] if !group.is_empty() && !artifact.is_empty() => {
+                (Some(group), Some(artifact), version)
+            }
+            _ => {
+                return Err(format!(
+                    "expected [groupId:artifactId:]PATTERN, got '{}'",
+                    spec
+                ))
+            }
+        };
+        if version.is_empty() {
+            return Err(format!("missing version pattern: '{}'", spec));
+        }
+        let patterns = |text: Option<&str>| text.map(|text| Patterns::One(text.to_string()));
+        Ok(Rule {
+            priority: i64::MAX,
+            group: patterns(group),
+            artifact: patterns(artifact),
+            version: patterns(Some(version)),
+            ..Rule::builtin(Reason::Custom(KEEP_MATCHING), RuleAction::Keep)
+        })
+    }
+
+    // 固定文件中的 groupId:artifactId[:version], 省略版本时固定全部版本
+    pub fn pinned(coordinate: &str) -> Result<Self, String> {
+        let rule = match coordinate.split(':').count() {
+            2 => Rule::keep_versions(&format!("{}:*", coordinate)),
+            3 => Rule::keep_versions(coordinate),
+            _ => Err(format!(
+                "expected groupId:artifactId[:version], got '{}'",
+                coordinate
+            )),
+        }?;
+        Ok(Rule {
+            name: PINNED.to_string(),
+            reason: Reason::Custom(PINNED),
+            ..rule
+        })
+    }
+
+    // 第一个未满足的条件, 名称与策略文件中的键相同
+    fn mismatch(&self, info: &FileInfo) -> Option<&'static str> {
+        self.conditions(info)
+            .into_iter()
+            .find(|(_, matches)| !matches)
+            .map(|(name, _)| name)
+    }
+
+    // 同 mismatch, 但不检查文件的修改时间
+    fn fixed_mismatch(&self, info: &FileInfo) -> Option<&'static str> {
+        self.conditions(info)
+            .into_iter()
+            .find(|(name, matches)| !matches && *name != "older_than")
+            .map(|(name, _)| name)
+    }
+
+    // 依赖文件年龄、锁是否被持有或同一构件的其他版本, 文件本身不变时结果也可能变化
+    fn varies(&self) -> bool {
+        self.age.is_some()
+            || self.superseded.is_some()
+            || self.lock == Some(true)
+            || self.action == RuleAction::KeepLatest
+    }
+
+    fn conditions(&self, info: &FileInfo) -> [(&'static str, bool); 12] {
+        let gav_matches = |patterns: &Option<Patterns>, field: fn(&Gav) -> &str| {
+            patterns.as_ref().is_none_or(|patterns| {
+                info.gav
+                    .as_ref()
+                    .is_some_and(|gav| patterns.matches(field(gav)))
+            })
+        };
+        let range_matches = self.range.as_ref().is_none_or(|range| {
+            info.gav
+                .as_ref()
+                .is_some_and(|gav| range.contains(&gav.version))
+        });
+        [
+            ("version_range", range_matches),
+            ("group", gav_matches(&self.group, |gav| &gav.group_id)),
+            (
+                "artifact",
+                gav_matches(&self.artifact, |gav| &gav.artifact_id),
+            ),
+            ("version", gav_matches(&self.version, |gav| &gav.version)),
+            (
+                "file",
+                self.file
+                    .as_ref()
+                    .is_none_or(|patterns| patterns.matches(&info.file_name)),
+            ),
+            (
+                "type",
+                self.types
+                    .as_ref()
+                    .is_none_or(|patterns| patterns.matches(&info.file_type)),
+            ),
+            (
+                "classifier",
+                self.classifier.as_ref().is_none_or(|patterns| {
+                    patterns.matches(info.classifier.as_deref().unwrap_or_default())
+                }),
+            ),
+            (
+                "snapshot",
+                self.snapshot
+                    .is_none_or(|snapshot| snapshot == info.snapshot),
+            ),
+            (
+                "timestamped",
+                self.timestamped
+                    .is_none_or(|timestamped| timestamped == info.timestamped),
+            ),
+            (
+                "expected",
+                self.expected
+                    .is_none_or(|expected| expected == info.expected),
+            ),
+            ("lock", self.lock.is_none_or(|lock| lock == info.lock)),
+            (
+                "older_than",
+                self.age.is_none_or(|age| {
+                    info.modified
+                        .and_then(|modified| modified.elapsed().ok())
+                        .is_some_and(|elapsed| elapsed >= age)
+                }),
+            ),
+        ]
+    }
+}
+
+fn file_info(
+    fs: &dyn RepoFs,
+    repo_path: &Path,
+    path: &Path,
+    latest: &mut LatestVersions,
+) -> Option<FileInfo> {
+    // 文件名与目录名统一为 NFC 后再比较, 不区分大小写的文件系统上忽略大小写
+    let ignore_case = fs.is_case_insensitive(repo_path);
+    let published = latest.published(fs, path.parent()?);
+    let entry = layout::locate(repo_path, path, ignore_case, published)?;
+    let folder_name = unicode::nfc(&get_file_name(path.parent()?)?).into_owned();
+    let snapshot = folder_name.ends_with(SNAPSHOT_SUFFIX);
+    let timestamped = entry
+        .artifact
+        .as_ref()
+        .is_some_and(|artifact| artifact.build.is_some());
+    let (classifier, file_type) = match entry.artifact {
+        Some(artifact) => (artifact.classifier, artifact.extension),
+        None => {
+            let mut name = entry.file_name.as_str();
+            for suffix in CHECKSUM_SUFFIXIES {
+                name = name.strip_suffix(suffix).unwrap_or(name);
+            }
+            let file_type = name
+                .split_once('.')
+                .map(|(_, file_type)| file_type.to_string())
+                .unwrap_or_default();
+            (None, file_type)
+        }
+    };
+    Some(FileInfo {
+        gav: entry.gav,
+        snapshot,
+        timestamped,
+        expected: entry.expected,
+        lock: layout::is_lock(repo_path, path),
+        file_name: entry.file_name,
+        classifier,
+        file_type,
+        modified: fs
+            .metadata(path)
+            .ok()
+            .and_then(|metadata| metadata.modified),
+    })
+}
+
+// 按版本从高到低排序, 缓存每个构件的版本列表和最新正式版
+#[derive(Default)]
+pub struct LatestVersions {
+    artifacts: HashMap<PathBuf, Vec<PathBuf>>,
+    releases: HashMap<PathBuf, Option<(String, SystemTime)>>,
+    superseded: HashMap<PathBuf, bool>,
+    metadata_only: HashMap<PathBuf, bool>,
+    published: HashMap<PathBuf, Vec<Published>>,
+}
+
+impl LatestVersions {
+    // 快照目录元数据中列出的构件, 判断时间戳构建是否过时
+    fn published(&mut self, fs: &dyn RepoFs, version_dir: &Path) -> &[Published] {
+        self.published
+            .entry(version_dir.to_path_buf())
+            .or_insert_with(|| layout::published(fs, version_dir))
+    }
+
+    // 同一构件中同类 (正式版或快照) 版本目录的排名, 按 Maven 版本顺序从高到低,
+    // 顺序相同时 (如 1.0 与 1.0.0) 较新修改的在前; 快照不会挤掉正式版
+    fn rank(&mut self, fs: &dyn RepoFs, version_dir: &Path) -> Option<usize> {
+        let artifact_dir = version_dir.parent()?;
+        let versions = self
+            .artifacts
+            .entry(artifact_dir.to_path_buf())
+            .or_insert_with(|| {
+                let entries = fs.read_dir(artifact_dir).unwrap_or_default();
+                let mut versions: Vec<(String, Option<SystemTime>, PathBuf)> = entries
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|path| {
+                        let metadata = fs.metadata(&path).ok()?;
+                        let name = get_file_name(&path)?;
+                        metadata.is_dir.then_some((name, metadata.modified, path))
+                    })
+                    .collect();
+                versions.sort_by(|a, b| version::compare(&b.0, &a.0).then(b.1.cmp(&a.1)));
+                versions.into_iter().map(|(_, _, path)| path).collect()
+            });
+        let is_snapshot =
+            |path: &Path| get_file_name(path).is_some_and(|name| name.ends_with(SNAPSHOT_SUFFIX));
+        let snapshot = is_snapshot(version_dir);
+        versions
+            .iter()
+            .filter(|path| is_snapshot(path) == snapshot)
+            .position(|path| path == version_dir)
+    }
+
+    // 快照目录的修改时间及其中最新的构建时间都早于最新正式版的安装时间,
+    // 且快照版本不高于该正式版, 解析依赖时不会再选中它
+    fn superseded(&mut self, fs: &dyn RepoFs, version_dir: &Path) -> bool {
+        if let Some(&superseded) = self.superseded.get(version_dir) {
+            return superseded;
+        }
+        let superseded = self.check_superseded(fs, version_dir).unwrap_or(false);
+        self.superseded
+            .insert(version_dir.to_path_buf(), superseded);
+        superseded
+    }
+
+    // 没有子目录, 且只有校验文件、.lastUpdated、_remote.repositories、元数据文件和保留期限旁路文件
+    fn metadata_only(&mut self, fs: &dyn RepoFs, version_dir: &Path) -> bool {
+        *self
+            .metadata_only
+            .entry(version_dir.to_path_buf())
+            .or_insert_with(|| {
+                let entries = match fs.read_dir(version_dir) {
+                    Ok(entries) => entries,
+                    Err(_) => return false,
+                };
+                entries.into_iter().all(|entry| {
+                    let Ok(path) = entry else { return false };
+                    let Some(file_name) = get_file_name(&path) else {
+                        return false;
+                    };
+                    fs.is_file(&path)
+                        && (layout::is_metadata(&file_name)
+                            || file_name == ttl::SIDECAR_FILE
+                            || METADATA_SUFFIXES
+                                .iter()
+                                .any(|suffix| file_name.ends_with(suffix)))
+                })
+            })
+    }
+
+    fn check_superseded(&mut self, fs: &dyn RepoFs, version_dir: &Path) -> Option<bool> {
+        let version = get_file_name(version_dir)?;
+        let base = version.strip_suffix(SNAPSHOT_SUFFIX)?;
+        let artifact_dir = version_dir.parent()?;
+        let artifact_id = get_file_name(artifact_dir)?;
+        let (release, installed) = self
+            .releases
+            .entry(artifact_dir.to_path_buf())
+            .or_insert_with(|| newest_release(fs, artifact_dir))
+            .as_ref()?;
+        if version::compare(base, release).is_gt() {
+            return Some(false);
+        }
+        let builds = fs
+            .read_dir(version_dir)
+            .ok()?
+            .into_iter()
+            .flatten()
+            .filter_map(|path| {
+                let file_name = get_file_name(&path)?;
+                snapshot::parse_file_name(&artifact_id, &version, &file_name)
+            })
+            .map(|build| build.timestamp.to_system_time());
+        let built = fs
+            .metadata(version_dir)
+            .ok()
+            .and_then(|metadata| metadata.modified)
+            .into_iter()
+            .chain(builds)
+            .max()?;
+        Some(built < *installed)
+    }
+}
+
+// 版本号最高的正式版及其目录的修改时间 (即安装时间)
+fn newest_release(fs: &dyn RepoFs, artifact_dir: &Path) -> Option<(String, SystemTime)> {
+    fs.read_dir(artifact_dir)
+        .ok()?
+        .into_iter()
+        .flatten()
+        .filter_map(|path| {
+            let version = get_file_name(&path)?;
+            if snapshot::parse(&version).is_some() {
+                return None;
+            }
+            let metadata = fs.metadata(&path).ok()?;
+            metadata.is_dir.then_some((version, metadata.modified))
+        })
+        .max_by(|a, b| version::compare(&a.0, &b.0))
+        .and_then(|(version, modified)| Some((version, modified?)))
+}
+
+// 受保护的 groupId 前缀, 支持通配符
+#[derive(Debug, Clone)]
+pub struct ProtectedGroups(Arc<Vec<String>>);
+
+impl Default for ProtectedGroups {
+    fn default() -> Self {
+        ProtectedGroups::new(
+            DEFAULT_PROTECTED_GROUPS
+                .iter()
+                .map(|group| group.to_string())
+                .collect(),
+        )
+    }
+}
+
+impl ProtectedGroups {
+    pub fn new(groups: Vec<String>) -> Self {
+        ProtectedGroups(Arc::new(groups))
+    }
+
+    pub fn is_protected(&self, repo_path: &Path, path: &Path) -> bool {
+        if self.0.is_empty() {
+            return false;
+        }
+        let folder = match path
+            .parent()
+            .and_then(|folder| folder.strip_prefix(repo_path).ok())
+        {
+            Some(folder) => folder,
+            None => return false,
+        };
+        // 依次比较 a, a.b, a.b.c ... 前缀
+        let mut prefix = String::new();
+        for component in folder.components() {
+            if !prefix.is_empty() {
+                prefix.push('.');
+            }
+            prefix.push_str(&unicode::nfc(&component.as_os_str().to_string_lossy()));
+            if self.0.iter().any(|group| glob_match(group, &prefix)) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+// --only-reason / --skip-reason, 同一次扫描只执行部分原因的删除
+#[derive(Debug, Default, Clone)]
+pub struct ReasonFilter {
+    pub only: Vec<Reason>,
+    pub skip: Vec<Reason>,
+}
+
+impl ReasonFilter {
+    pub fn allows(&self, reason: Reason) -> bool {
+        (self.only.is_empty() || self.only.contains(&reason)) && !self.skip.contains(&reason)
+    }
+}
+
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,55 @@
+use crate::gav::Gav;
+use crate::xml::{child, children, text};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default)]
+pub struct Pom {
+    pub parent: Option<Gav>,
+    pub licenses: Vec<String>,
+}
+
+pub fn read(path: &Path) -> Option<Pom> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            log::error!("Failed to read '{}': {}", path.display(), e);
+            return None;
+        }
+    };
+    let document = match roxmltree::Document::parse(&content) {
+        Ok(document) => document,
+        Err(e) => {
+            log::error!("Failed to parse '{}': {}", path.display(), e);
+            return None;
+        }
+    };
+    let project = document.root_element();
+
+    let parent = child(project, "parent").and_then(|parent| {
+        Some(Gav {
+            group_id: child(parent, "groupId").map(text)?,
+            artifact_id: child(parent, "artifactId").map(text)?,
+            version: child(parent, "version").map(text)?,
+        })
+    });
+    let licenses = child(project, "licenses")
+        .map(|licenses| {
+            children(licenses, "license")
+                .filter_map(|license| child(license, "name").map(text))
+                .filter(|name| !name.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(Pom { parent, licenses })
+}
+
+pub fn path_in_repo(repo_path: &Path, gav: &Gav) -> PathBuf {
+    let mut path = repo_path.to_path_buf();
+    for group in gav.group_id.split('.') {
+        path.push(group);
+    }
+    path.push(&gav.artifact_id);
+    path.push(&gav.version);
+    path.push(format!("{}-{}.pom", gav.artifact_id, gav.version));
+    path
+}
@@ -0,0 +1,50 @@
+// 降低进程的 CPU 和 I/O 优先级, 避免与 CI 任务争抢资源
+#[cfg(unix)]
+pub fn lower() {
+    const NICE: libc::c_int = 19;
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, NICE) } != 0 {
+        log::warn!(
+            "Failed to lower CPU priority: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+    #[cfg(target_os = "linux")]
+    {
+        const IOPRIO_WHO_PROCESS: libc::c_long = 1;
+        const IOPRIO_CLASS_IDLE: libc::c_long = 3;
+        const IOPRIO_CLASS_SHIFT: libc::c_long = 13;
+        let result = unsafe {
+            libc::syscall(
+                libc::SYS_ioprio_set,
+                IOPRIO_WHO_PROCESS,
+                0,
+                IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT,
+            )
+        };
+        if result != 0 {
+            log::warn!(
+                "Failed to lower I/O priority: {}",
+                std::io::Error::last_os_error()
+            );
+        }
+    }
+}
+
+// 后台模式同时降低 CPU, I/O 和内存优先级
+#[cfg(windows)]
+pub fn lower() {
+    use windows_sys::Win32::System::Threading::{
+        GetCurrentProcess, SetPriorityClass, PROCESS_MODE_BACKGROUND_BEGIN,
+    };
+    if unsafe { SetPriorityClass(GetCurrentProcess(), PROCESS_MODE_BACKGROUND_BEGIN) } == 0 {
+        log::warn!(
+            "Failed to enter background priority: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn lower() {
+    log::warn!("Low priority mode is not supported on this platform");
+}
@@ -0,0 +1,100 @@
+use crate::summary::Summary;
+use crate::{duration, human_size};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+const REPORT_INTERVAL: Duration = Duration::from_secs(5);
+
+static INTERVAL: OnceLock<Duration> = OnceLock::new();
+
+// --progress-interval: 扫描和删除时按固定间隔输出一行汇总, 逐文件的删除日志降为 debug
+pub fn set_interval(interval: Duration) {
+    let _ = INTERVAL.set(interval);
+}
+
+pub fn periodic() -> bool {
+    INTERVAL.get().is_some()
+}
+
+fn interval() -> Duration {
+    INTERVAL.get().copied().unwrap_or(REPORT_INTERVAL)
+}
+
+// 扫描阶段的进度, 只在 --progress-interval 下输出
+pub struct ScanProgress {
+    last_report: Instant,
+}
+
+impl Default for ScanProgress {
+    fn default() -> Self {
+        ScanProgress {
+            last_report: Instant::now(),
+        }
+    }
+}
+
+impl ScanProgress {
+    pub fn update(&mut self, dirs: usize, candidates: usize) {
+        if !periodic() || self.last_report.elapsed() < interval() {
+            return;
+        }
+        self.last_report = Instant::now();
+        log::info!("Scanning: {} directories, {} candidates", dirs, candidates);
+    }
+}
+
+pub struct Progress {
+    total: usize,
+    start: Instant,
+    last_report: Instant,
+}
+
+impl Progress {
+    pub fn new(total: usize) -> Self {
+        let now = Instant::now();
+        Progress {
+            total,
+            start: now,
+            last_report: now,
+        }
+    }
+
+    pub fn update(&mut self, done: usize, summary: &Summary) {
+        if self.last_report.elapsed() < interval() || done >= self.total {
+            return;
+        }
+        self.last_report = Instant::now();
+        let elapsed = self.start.elapsed();
+        // 按已处理候选数的速度估算剩余时间
+        let eta = elapsed.mul_f64((self.total - done) as f64 / done as f64);
+        log::info!(
+            "Progress: {}/{}, {} freed, {}, ETA {}",
+            done,
+            self.total,
+            human_size(summary.deleted_size),
+            rate(summary, elapsed),
+            duration::format(eta)
+        );
+    }
+
+    pub fn finish(&self, summary: &Summary) {
+        if self.total == 0 {
+            return;
+        }
+        let elapsed = self.start.elapsed();
+        log::info!(
+            "Elapsed: {}, {}",
+            duration::format(elapsed),
+            rate(summary, elapsed)
+        );
+    }
+}
+
+fn rate(summary: &Summary, elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs_f64().max(0.001);
+    format!(
+        "{:.1} files/s, {}/s",
+        summary.files_deleted as f64 / seconds,
+        human_size((summary.deleted_size as f64 / seconds) as usize)
+    )
+}
@@ -0,0 +1,540 @@
+use crate::fs::RepoFs;
+use crate::gav::{self, VersionDir};
+use crate::pins::{self, PINS_FILE};
+use crate::policy::glob_match;
+use crate::remove::{self, Action, Candidate};
+use crate::summary::{Reason, Summary};
+use crate::{format_size, get_file_name, i18n, xml, CleanupOptions, CHECKSUM_SUFFIXIES};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+// groupId:artifactId[:version], 各部分可使用 * 和 ? 通配符
+pub struct Coordinate {
+    group: String,
+    artifact: String,
+    version: Option<String>,
+}
+
+impl Coordinate {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = text.split(':').collect();
+        if parts.iter().any(|part| part.is_empty()) {
+            return Err(format!("empty part in '{}'", text));
+        }
+        match parts.as_slice() {
+            [group, artifact] => Ok(Coordinate {
+                group: group.to_string(),
+                artifact: artifact.to_string(),
+                version: None,
+            }),
+            [group, artifact, version] => Ok(Coordinate {
+                group: group.to_string(),
+                artifact: artifact.to_string(),
+                version: Some(version.to_string()),
+            }),
+            _ => Err(format!(
+                "expected groupId:artifactId[:version], got '{}'",
+                text
+            )),
+        }
+    }
+
+    pub fn matches(&self, version: &VersionDir) -> bool {
+        glob_match(&self.group, &version.gav.group_id)
+            && glob_match(&self.artifact, &version.gav.artifact_id)
+            && self
+                .version
+                .as_ref()
+                .is_none_or(|pattern| glob_match(pattern, &version.gav.version))
+    }
+}
+
+// 每行一个坐标, 忽略空行和 # 注释
+pub fn read_coordinates(reader: impl BufRead) -> Result<Vec<Coordinate>, String> {
+    let mut coordinates = Vec::new();
+    for (number, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| e.to_string())?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        coordinates
+            .push(Coordinate::parse(line).map_err(|e| format!("line {}: {}", number + 1, e))?);
+    }
+    Ok(coordinates)
+}
+
+// 类似 dependency:purge-local-repository: 删除匹配的版本目录并更新构件的元数据
+pub fn purge(
+    repo_path: &Path,
+    coordinates: &[Coordinate],
+    options: &CleanupOptions,
+    dry_run: bool,
+    assume_yes: bool,
+) -> bool {
+    let options = &*pins::apply(repo_path, options);
+    let fs = &*options.fs;
+    // 未指定版本的坐标删除整个构件目录, 但构件有固定的版本时只删除其余版本
+    let mut whole_artifacts: BTreeSet<PathBuf> = BTreeSet::new();
+    let mut pinned: BTreeSet<PathBuf> = BTreeSet::new();
+    let mut versions: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+    for version in gav::scan_versions(repo_path) {
+        let matching: Vec<&Coordinate> = coordinates
+            .iter()
+            .filter(|coordinate| coordinate.matches(&version))
+            .collect();
+        let artifact_dir = match version.path.parent() {
+            Some(artifact_dir) if !matching.is_empty() => artifact_dir.to_path_buf(),
+            _ => continue,
+        };
+        if options.pins.is_version_pinned(fs, repo_path, &version) {
+            log::warn!("Skipping version pinned in {}: {}", PINS_FILE, version.gav);
+            pinned.insert(artifact_dir);
+            continue;
+        }
+        if matching
+            .iter()
+            .any(|coordinate| coordinate.version.is_none())
+        {
+            whole_artifacts.insert(artifact_dir.clone());
+        }
+        versions.entry(artifact_dir).or_default().push(version.path);
+    }
+    whole_artifacts.retain(|artifact_dir| !pinned.contains(artifact_dir));
+    versions.retain(|artifact_dir, _| !whole_artifacts.contains(artifact_dir));
+    if whole_artifacts.is_empty() && versions.is_empty() {
+        log::error!("No artifacts match the coordinates");
+        return false;
+    }
+    // 不再有任何版本的构件目录连同元数据一起删除, 其余的改写元数据
+    let mut rewrites = Vec::new();
+    let mut stale_checksums = Vec::new();
+    for (artifact_dir, purged) in &versions {
+        // 无法读取时保留构件目录, 也不改写元数据
+        let Some(remaining) = remaining_versions(fs, artifact_dir, purged) else {
+            continue;
+        };
+        if remaining.is_empty() {
+            log::info!(
+                "Removing artifact without versions: {}",
+                artifact_dir.display()
+            );
+            whole_artifacts.insert(artifact_dir.clone());
+            continue;
+        }
+        for (path, updated) in plan_metadata(fs, artifact_dir, &remaining) {
+            // 原有校验和已失效
+            stale_checksums.extend(CHECKSUM_SUFFIXIES.iter().filter_map(|suffix| {
+                let mut checksum = path.as_os_str().to_owned();
+                checksum.push(suffix);
+                let checksum = PathBuf::from(checksum);
+                fs.is_file(&checksum).then_some(checksum)
+            }));
+            rewrites.push((path, updated));
+        }
+    }
+    versions.retain(|artifact_dir, _| !whole_artifacts.contains(artifact_dir));
+    let candidates = whole_artifacts
+        .iter()
+        .chain(versions.values().flatten())
+        .map(|path| Candidate::new(path.clone(), Action::VersionDir, Reason::Purged))
+        .chain(
+            stale_checksums
+                .into_iter()
+                .map(|path| Candidate::new(path, Action::File, Reason::Purged)),
+        )
+        .collect();
+    confirm_and_execute(
+        repo_path, candidates, &rewrites, options, dry_run, assume_yes,
+    )
+}
+
+// 构件目录中除本次清除的版本以外的版本目录名
+fn remaining_versions(
+    fs: &dyn RepoFs,
+    artifact_dir: &Path,
+    purged: &[PathBuf],
+) -> Option<Vec<String>> {
+    match fs.read_dir(artifact_dir) {
+        Ok(entries) => Some(
+            entries
+                .into_iter()
+                .flatten()
+                .filter(|path| !purged.contains(path) && fs.is_dir(path))
+                .filter_map(|path| get_file_name(&path))
+                .collect(),
+        ),
+        Err(e) => {
+            log::error!(
+                "Failed to read directory '{}': {}",
+                artifact_dir.display(),
+                e
+            );
+            None
+        }
+    }
+}
+
+// 需要改写的元数据文件及其新内容
+fn plan_metadata(
+    fs: &dyn RepoFs,
+    artifact_dir: &Path,
+    remaining: &[String],
+) -> Vec<(PathBuf, String)> {
+    let entries = fs.read_dir(artifact_dir).unwrap_or_default();
+    let mut rewrites = Vec::new();
+    for path in entries
+        .into_iter()
+        .flatten()
+        .filter(|path| is_metadata(path))
+    {
+        let content = match fs.read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                log::error!("Failed to read '{}': {}", path.display(), e);
+                continue;
+            }
+        };
+        match prune_versions(&content, remaining) {
+            Ok(Some(updated)) => rewrites.push((path, updated)),
+            Ok(None) => {}
+            Err(e) => log::warn!("Not updating invalid metadata '{}': {}", path.display(), e),
+        }
+    }
+    rewrites
+}
+
+fn is_metadata(path: &Path) -> bool {
+    get_file_name(path)
+        .is_some_and(|name| name.starts_with("maven-metadata") && name.ends_with(".xml"))
+}
+
+// 删除已不存在的 <version>, 指向它们的 <latest>/<release> 改为剩余的最后一个版本
+pub(crate) fn prune_versions(
+    content: &str,
+    remaining: &[String],
+) -> Result<Option<String>, String> {
+    let document = roxmltree::Document::parse(content).map_err(|e| e.to_string())?;
+    let versioning = match xml::child(document.root_element(), "versioning") {
+        Some(versioning) => versioning,
+        None => return Ok(None),
+    };
+    let versions = match xml::child(versioning, "versions") {
+        Some(versions) => versions,
+        None => return Ok(None),
+    };
+    // 连同前面的缩进一起删除
+    let removal = |range: std::ops::Range<usize>| {
+        let start = content[..range.start]
+            .rfind(|c: char| !c.is_whitespace())
+            .map_or(range.start, |index| index + 1);
+        (start..range.end, String::new())
+    };
+    let mut edits: Vec<(std::ops::Range<usize>, String)> = Vec::new();
+    let mut kept = Vec::new();
+    for version in xml::children(versions, "version") {
+        let text = xml::text(version);
+        if remaining.contains(&text) {
+            kept.push(text);
+        } else {
+            edits.push(removal(version.range()));
+        }
+    }
+    if edits.is_empty() {
+        return Ok(None);
+    }
+    // 没有剩余版本时删除 <latest>/<release>, 空元素会被 Maven 视为损坏
+    for name in ["latest", "release"] {
+        if let Some(node) = xml::child(versioning, name) {
+            if !kept.contains(&xml::text(node)) {
+                edits.push(match kept.last() {
+                    Some(fallback) => (node.range(), format!("<{0}>{1}</{0}>", name, fallback)),
+                    None => removal(node.range()),
+                });
+            }
+        }
+    }
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    let mut updated = content.to_string();
+    for (range, replacement) in edits {
+        updated.replace_range(range, &replacement);
+    }
+    Ok(Some(updated))
+}
+
+// 删除 groupId 前缀下的全部内容, 包括子 group
+pub fn purge_group(
+    repo_path: &Path,
+    group: &str,
+    options: &CleanupOptions,
+    dry_run: bool,
+    assume_yes: bool,
+) -> bool {
+    let dir = match group_dir(repo_path, group) {
+        Some(dir) => dir,
+        None => {
+            log::error!("Invalid groupId: {}", group);
+            return false;
+        }
+    };
+    if !options.fs.is_dir(&dir) {
+        log::error!("Group not found: {}", dir.display());
+        return false;
+    }
+    // 删除整个目录无法绕开固定的版本, 整个 group 都不删除
+    let options = &*pins::apply(repo_path, options);
+    if !options.pins.is_empty() {
+        let pinned: Vec<VersionDir> = gav::scan_versions(repo_path)
+            .into_iter()
+            .filter(|version| version.path.starts_with(&dir))
+            .filter(|version| {
+                options
+                    .pins
+                    .is_version_pinned(&*options.fs, repo_path, version)
+            })
+            .collect();
+        for version in &pinned {
+            log::error!("Version pinned in {}: {}", PINS_FILE, version.gav);
+        }
+        if !pinned.is_empty() {
+            log::error!("Not purging a group with pinned versions: {}", group);
+            return false;
+        }
+    }
+    let candidates = vec![Candidate::new(dir, Action::VersionDir, Reason::Purged)];
+    confirm_and_execute(repo_path, candidates, &[], options, dry_run, assume_yes)
+}
+
+fn group_dir(repo_path: &Path, group: &str) -> Option<PathBuf> {
+    let mut dir = repo_path.to_path_buf();
+    for part in group.split('.') {
+        if part.is_empty() || part == ".." || part.contains(['/', '\\']) {
+            return None;
+        }
+        dir.push(part);
+    }
+    Some(dir)
+}
+
+// 确认后先改写元数据, 再删除
+fn confirm_and_execute(
+    repo_path: &Path,
+    candidates: Vec<Candidate>,
+    rewrites: &[(PathBuf, String)],
+    options: &CleanupOptions,
+    dry_run: bool,
+    assume_yes: bool,
+) -> bool {
+    let files = remove::planned_files(&*options.fs, &candidates);
+    let size: usize = files.iter().map(|(_, size)| size).sum();
+    for candidate in &candidates {
+        match dry_run {
+            true => log::info!("Would purge: {}", candidate.path.display()),
+            false => log::info!("Purging: {}", candidate.path.display()),
+        }
+    }
+    for (dir, stat) in remove::planned_versions(&files) {
+        log::info!(
+            "  {}: {} across {} files",
+            gav::label(repo_path, &dir),
+            format_size(stat.size),
+            stat.files
+        );
+    }
+    if dry_run {
+        for (path, _) in rewrites {
+            log::info!("Would rewrite metadata: {}", path.display());
+        }
+    }
+    log::info!("Files: {}, size: {}", files.len(), format_size(size));
+    if dry_run {
+        return true;
+    }
+    if !assume_yes
+        && !confirm(&format!(
+            "Delete {} files ({})?",
+            files.len(),
+            format_size(size)
+        ))
+    {
+        log::warn!("Aborted, nothing deleted");
+        return false;
+    }
+    let mut summary = Summary::default();
+    for (path, updated) in rewrites {
+        log::info!("Updating metadata: {}", path.display());
+        if let Err(e) = options.fs.write(path, updated.as_bytes()) {
+            log::error!("Failed to write '{}': {}", path.display(), e);
+            summary.error();
+        }
+    }
+    remove::execute(repo_path, candidates, options, &mut summary);
+    summary.finish(repo_path, options);
+    summary.errors == 0
+}
+
+// 非交互环境下需要 --yes
+fn confirm(question: &str) -> bool {
+    if !io::stdin().is_terminal() {
+        log::error!("Refusing to delete without confirmation, pass --yes");
+        return false;
+    }
+    eprint!("{} [y/N] ", i18n::translate(question));
+    let _ = io::stderr().flush();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_coordinates_with_and_without_version() {
+        let coordinate = Coordinate::parse("com.foo:bar").unwrap();
+        assert_eq!(
+            (coordinate.group.as_str(), coordinate.artifact.as_str()),
+            ("com.foo", "bar")
+        );
+        assert_eq!(coordinate.version, None);
+
+        let coordinate = Coordinate::parse("com.*:b?r:1.*").unwrap();
+        assert_eq!(coordinate.group, "com.*");
+        assert_eq!(coordinate.artifact, "b?r");
+        assert_eq!(coordinate.version.as_deref(), Some("1.*"));
+
+        for text in ["com.foo", "com.foo:", ":bar", "com.foo:bar:", "a:b:c:d", ""] {
+            assert!(Coordinate::parse(text).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn wildcards_match_version_directories() {
+        let version = |group: &str, artifact: &str, version: &str| VersionDir {
+            gav: crate::gav::Gav {
+                group_id: group.to_string(),
+                artifact_id: artifact.to_string(),
+                version: version.to_string(),
+            },
+            path: PathBuf::new(),
+            files: Vec::new(),
+        };
+        let coordinate = Coordinate::parse("com.*:b?r:1.*").unwrap();
+        assert!(coordinate.matches(&version("com.foo", "bar", "1.2")));
+        assert!(!coordinate.matches(&version("com.foo", "bar", "2.0")));
+        assert!(!coordinate.matches(&version("org.foo", "bar", "1.2")));
+        assert!(!coordinate.matches(&version("com.foo", "baar", "1.2")));
+        // 省略版本时匹配全部版本
+        assert!(Coordinate::parse("com.foo:bar").unwrap().matches(&version(
+            "com.foo",
+            "bar",
+            "9-SNAPSHOT"
+        )));
+    }
+
+    static METADATA: &str = "<metadata>
+  <versioning>
+    <latest>2.0</latest>
+    <release>2.0</release>
+    <versions>
+      <version>1.0</version>
+      <version>1.1</version>
+      <version>2.0</version>
+    </versions>
+  </versioning>
+</metadata>";
+
+    #[test]
+    fn pruned_latest_and_release_fall_back_to_the_last_remaining_version() {
+        let remaining = ["1.0".to_string(), "1.1".to_string()];
+        let updated = prune_versions(METADATA, &remaining).unwrap().unwrap();
+        assert_eq!(
+            updated,
+            "<metadata>
+  <versioning>
+    <latest>1.1</latest>
+    <release>1.1</release>
+    <versions>
+      <version>1.0</version>
+      <version>1.1</version>
+    </versions>
+  </versioning>
+</metadata>"
+        );
+        // 没有变化时不改写
+        let all = ["1.0", "1.1", "2.0"].map(String::from);
+        assert_eq!(prune_versions(METADATA, &all).unwrap(), None);
+        assert_eq!(prune_versions("<metadata/>", &remaining).unwrap(), None);
+        assert!(prune_versions("<metadata>", &remaining).is_err());
+    }
+
+    #[test]
+    fn latest_and_release_are_removed_when_no_listed_version_remains() {
+        let updated = prune_versions(METADATA, &["3.0".to_string()])
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            updated,
+            "<metadata>
+  <versioning>
+    <versions>
+    </versions>
+  </versioning>
+</metadata>"
+        );
+        roxmltree::Document::parse(&updated).unwrap();
+    }
+
+    #[test]
+    fn group_directories_stay_inside_the_repository() {
+        let repo_path = Path::new("/repo");
+        assert_eq!(
+            group_dir(repo_path, "com.foo.bar"),
+            Some(PathBuf::from("/repo/com/foo/bar"))
+        );
+        for group in [
+            "",
+            "com..foo",
+            ".com",
+            "com.",
+            "..",
+            "com.....",
+            "com/../../etc",
+            "com\\foo",
+            "../etc",
+        ] {
+            assert_eq!(group_dir(repo_path, group), None, "{}", group);
+        }
+    }
+
+    #[test]
+    fn reads_coordinates_one_per_line() {
+        let coordinates = read_coordinates("com.foo:bar\ncom.foo:baz:1.*\n".as_bytes()).unwrap();
+        let versions: Vec<Option<&str>> = coordinates
+            .iter()
+            .map(|coordinate| coordinate.version.as_deref())
+            .collect();
+        assert_eq!(versions, [None, Some("1.*")]);
+        assert!(read_coordinates("".as_bytes()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn coordinate_input_skips_blank_and_comment_lines_and_reports_line_numbers() {
+        let input = "# purge list\n\n  com.foo:bar  \n   \n  # indented comment\ncom.foo:baz:2.0\n";
+        let coordinates = read_coordinates(input.as_bytes()).unwrap();
+        let artifacts: Vec<&str> = coordinates
+            .iter()
+            .map(|coordinate| coordinate.artifact.as_str())
+            .collect();
+        assert_eq!(artifacts, ["bar", "baz"]);
+
+        let input = "# purge list\ncom.foo:bar\n\ncom.foo\ncom.foo:baz\n";
+        let error = read_coordinates(input.as_bytes()).err().unwrap();
+        assert!(error.starts_with("line 4: "), "{}", error);
+        assert!(error.contains("'com.foo'"), "{}", error);
+    }
+}
@@ -0,0 +1,116 @@
+use std::fs::{FileTimes, Metadata};
+use std::io;
+use std::path::{Path, PathBuf};
+
+// 保持仓库目录结构, 目标可能位于其他文件系统.
+// 跨文件系统时逐个复制, 并保留属主、权限和时间, 以便多用户共享的仓库原样恢复
+pub fn move_path(repo_path: &Path, path: &Path, target_root: &Path) -> io::Result<PathBuf> {
+    let relative_path = path
+        .strip_prefix(repo_path)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let target = target_root.join(relative_path);
+    if let Some(parent) = relative_path.parent() {
+        create_parents(repo_path, parent, target_root)?;
+    }
+    if std::fs::rename(path, &target).is_ok() {
+        return Ok(target);
+    }
+    copy_all(path, &target)?;
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)?;
+    } else {
+        std::fs::remove_file(path)?;
+    }
+    Ok(target)
+}
+
+// 新建的上级目录沿用仓库中对应目录的属主和权限; 其修改时间随后续写入变化, 不保留
+fn create_parents(repo_path: &Path, relative_parent: &Path, target_root: &Path) -> io::Result<()> {
+    let mut source = repo_path.to_path_buf();
+    let mut target = target_root.to_path_buf();
+    std::fs::create_dir_all(&target)?;
+    for component in relative_parent.components() {
+        source.push(component);
+        target.push(component);
+        if target.is_dir() {
+            continue;
+        }
+        std::fs::create_dir(&target)?;
+        if let Ok(metadata) = std::fs::metadata(&source) {
+            keep_owner(&metadata, &target);
+            std::fs::set_permissions(&target, metadata.permissions())?;
+        }
+    }
+    Ok(())
+}
+
+fn copy_all(from: &Path, to: &Path) -> io::Result<()> {
+    let metadata = std::fs::metadata(from)?;
+    if !metadata.is_dir() {
+        std::fs::copy(from, to)?;
+        return copy_attributes(&metadata, to);
+    }
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        copy_all(&entry.path(), &to.join(entry.file_name()))?;
+    }
+    // 目录的修改时间在写完其内容后才能确定
+    copy_attributes(&metadata, to)
+}
+
+// 把源文件的修改和访问时间、属主和权限应用到副本. 权限最后设置, 因为更改属主会清除 setuid 位
+pub fn copy_attributes(from: &Metadata, to: &Path) -> io::Result<()> {
+    let mut times = FileTimes::new().set_modified(from.modified()?);
+    if let Ok(accessed) = from.accessed() {
+        times = times.set_accessed(accessed);
+    }
+    // 只读的副本在 Windows 上无法打开写入, 先取消只读
+    #[cfg(not(unix))]
+    if from.permissions().readonly() {
+        let mut writable = from.permissions();
+        writable.set_readonly(false);
+        std::fs::set_permissions(to, writable)?;
+    }
+    open_for_times(to, from.is_dir())?.set_times(times)?;
+    keep_owner(from, to);
+    std::fs::set_permissions(to, from.permissions())
+}
+
+// Unix 上属主可以用只读方式打开的文件描述符修改时间, 目录也是如此
+#[cfg(unix)]
+fn open_for_times(path: &Path, _is_dir: bool) -> io::Result<std::fs::File> {
+    std::fs::File::open(path)
+}
+
+#[cfg(windows)]
+fn open_for_times(path: &Path, is_dir: bool) -> io::Result<std::fs::File> {
+    use std::os::windows::fs::OpenOptionsExt;
+    use windows_sys::Win32::Storage::FileSystem::FILE_FLAG_BACKUP_SEMANTICS;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true);
+    if is_dir {
+        options.custom_flags(FILE_FLAG_BACKUP_SEMANTICS);
+    }
+    options.open(path)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn open_for_times(path: &Path, _is_dir: bool) -> io::Result<std::fs::File> {
+    std::fs::OpenOptions::new().write(true).open(path)
+}
+
+// 只有 root 能把文件交给其他用户; 普通用户至少保留属组 (需是该组成员)
+#[cfg(unix)]
+fn keep_owner(from: &Metadata, to: &Path) {
+    use std::os::unix::fs::{chown, MetadataExt};
+    if chown(to, Some(from.uid()), Some(from.gid())).is_ok() {
+        return;
+    }
+    if let Err(e) = chown(to, None, Some(from.gid())) {
+        log::debug!("Failed to keep the owner of '{}': {}", to.display(), e);
+    }
+}
+
+#[cfg(not(unix))]
+fn keep_owner(_from: &Metadata, _to: &Path) {}
@@ -0,0 +1,60 @@
+use crate::settings::{Repository, Settings};
+use crate::{get_file_name, is_artifact, SNAPSHOT_SUFFIX};
+use std::path::Path;
+use std::time::Duration;
+
+#[derive(Clone)]
+pub struct RemoteVerifier {
+    repositories: Vec<Repository>,
+    agent: ureq::Agent,
+}
+
+impl RemoteVerifier {
+    pub fn new(settings: &Settings) -> Result<Self, String> {
+        Ok(RemoteVerifier {
+            repositories: settings.resolved_repositories(),
+            agent: settings.agent(Duration::from_secs(10))?,
+        })
+    }
+
+    pub fn is_resolvable(&self, relative_path: &str) -> bool {
+        self.repositories.iter().any(|repository| {
+            let url = format!("{}/{}", repository.url.trim_end_matches('/'), relative_path);
+            match self.agent.head(&url).call() {
+                Ok(_) => {
+                    log::debug!("Found in '{}': {}", &repository.id, &url);
+                    true
+                }
+                Err(e) => {
+                    log::debug!("Not found in '{}': {} ({})", &repository.id, &url, e);
+                    false
+                }
+            }
+        })
+    }
+
+    pub fn warn_if_unresolvable(&self, repo_path: &Path, path: &Path) {
+        let file_name = match get_file_name(path) {
+            Some(file_name) => file_name,
+            None => return,
+        };
+        let version = path.parent().and_then(get_file_name).unwrap_or_default();
+        if version.ends_with(SNAPSHOT_SUFFIX) || !is_artifact(&file_name) {
+            return;
+        }
+        let relative_path = match path.strip_prefix(repo_path) {
+            Ok(relative_path) => relative_path
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            Err(_) => return,
+        };
+        if !self.is_resolvable(&relative_path) {
+            log::warn!(
+                "Removing artifact that could not be re-downloaded: {}",
+                path.display()
+            );
+        }
+    }
+}
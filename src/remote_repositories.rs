@@ -0,0 +1,107 @@
+use crate::fs::RepoFs;
+use crate::remove::{Action, Candidate};
+use crate::summary::Reason;
+use crate::{CleanupOptions, CHECKSUM_SUFFIXIES};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub static REMOTE_REPOSITORIES_FILE: &str = "_remote.repositories";
+
+// 每行格式: artifact-1.0.jar>central=
+pub fn read(fs: &dyn RepoFs, path: &Path) -> HashMap<String, String> {
+    let mut sources = HashMap::new();
+    let content = match fs.read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            log::error!("Failed to read '{}': {}", path.display(), e);
+            return sources;
+        }
+    };
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let entry = line.split('=').next().unwrap_or_default();
+        if let Some((file_name, repository_id)) = entry.split_once('>') {
+            sources.insert(file_name.to_string(), repository_id.to_string());
+        }
+    }
+    sources
+}
+
+#[derive(Default)]
+pub struct SourceCache {
+    folder: Option<PathBuf>,
+    sources: HashMap<String, String>,
+}
+
+impl SourceCache {
+    pub fn source(&mut self, fs: &dyn RepoFs, path: &Path) -> Option<&String> {
+        let folder = path.parent()?;
+        if self.folder.as_deref() != Some(folder) {
+            let file = folder.join(REMOTE_REPOSITORIES_FILE);
+            self.sources = if fs.is_file(&file) {
+                read(fs, &file)
+            } else {
+                HashMap::new()
+            };
+            self.folder = Some(folder.to_path_buf());
+        }
+        // 校验文件跟随其对应的构件
+        let mut file_name = path.file_name()?.to_str()?;
+        for suffix in CHECKSUM_SUFFIXIES {
+            file_name = file_name.strip_suffix(suffix).unwrap_or(file_name);
+        }
+        self.sources.get(file_name)
+    }
+}
+
+pub fn plan_decommissioned(
+    path: &Path,
+    known_repositories: &[String],
+    options: &CleanupOptions,
+) -> Vec<Candidate> {
+    let folder = match path.parent() {
+        Some(folder) => folder,
+        None => return Vec::new(),
+    };
+    let fs = &*options.fs;
+    let sources = read(fs, path);
+    let mut decommissioned: Vec<(&String, &String)> = sources
+        .iter()
+        .filter(|(_, id)| !id.is_empty() && !known_repositories.contains(id))
+        .filter(|(_, id)| {
+            options
+                .from_repo
+                .as_ref()
+                .is_none_or(|from_repo| from_repo == *id)
+        })
+        .collect();
+    decommissioned.sort();
+    if decommissioned.is_empty() {
+        return Vec::new();
+    }
+
+    for (file_name, repository_id) in &decommissioned {
+        log::debug!(
+            "Downloaded from decommissioned '{}': {}",
+            repository_id,
+            file_name
+        );
+    }
+    // 全部来自已下线仓库时删除整个版本目录
+    if decommissioned.len() == sources.len() {
+        return vec![Candidate::new(
+            folder.to_path_buf(),
+            Action::VersionDir,
+            Reason::Decommissioned,
+        )];
+    }
+    decommissioned
+        .into_iter()
+        .map(|(file_name, _)| folder.join(file_name))
+        .filter(|artifact| fs.is_file(artifact))
+        .map(|artifact| Candidate::new(artifact, Action::WithChecksums, Reason::Decommissioned))
+        .collect()
+}
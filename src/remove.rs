@@ -0,0 +1,382 @@
+use crate::fs::{RealFs, RepoFs};
+use crate::gav::Gav;
+use crate::hooks;
+use crate::progress::{self, Progress};
+use crate::summary::{Reason, Stat, Summary};
+use crate::throttle::Throttle;
+use crate::{archive, relocate, CleanupOptions, CHECKSUM_SUFFIXIES};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    File,
+    WithChecksums,
+    VersionDir,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Candidate {
+    pub path: PathBuf,
+    pub action: Action,
+    pub reason: Reason,
+    pub quarantine: bool,
+    // 由 policy 阶段填写: 所在版本目录的坐标和将释放的大小 (含校验文件)
+    pub gav: Option<Gav>,
+    pub size: usize,
+}
+
+impl Candidate {
+    pub fn new(path: PathBuf, action: Action, reason: Reason) -> Self {
+        Candidate {
+            path,
+            action,
+            reason,
+            quarantine: false,
+            gav: None,
+            size: 0,
+        }
+    }
+}
+
+// 先收集候选再统一删除, 以便显示进度和预计剩余时间
+pub fn execute(
+    repo_path: &Path,
+    candidates: Vec<Candidate>,
+    options: &CleanupOptions,
+    summary: &mut Summary,
+) {
+    #[cfg(feature = "scripting")]
+    let candidates = match &options.script {
+        Some(script) => {
+            let planned = candidates.len();
+            let candidates = script.apply(repo_path, candidates, options);
+            summary.files_skipped += planned - candidates.len();
+            candidates
+        }
+        None => candidates,
+    };
+    if let Some(command) = &options.on_before_delete {
+        if !candidates.is_empty() && !hooks::before_delete(command, repo_path, &candidates) {
+            summary.files_skipped += candidates.len();
+            return;
+        }
+    }
+    #[cfg(feature = "async")]
+    if options.io_depth > 1 {
+        crate::async_io::execute(repo_path, candidates, options, summary);
+        return;
+    }
+    #[cfg(feature = "remote")]
+    if let Some(verifier) = &options.verifier {
+        for path in verification_targets(&*options.fs, &candidates) {
+            verifier.warn_if_unresolvable(repo_path, &path);
+        }
+    }
+    let next = AtomicUsize::new(0);
+    let state = Mutex::new((std::mem::take(summary), Progress::new(candidates.len()), 0));
+    let throttle = options.throttle.map(Throttle::new);
+    std::thread::scope(|scope| {
+        for _ in 0..options.threads.max(1) {
+            scope.spawn(|| {
+                while let Some(candidate) =
+                    candidates.get(next.fetch_add(1, AtomicOrdering::Relaxed))
+                {
+                    let mut local = Summary::default();
+                    remove_candidate(repo_path, candidate, options, &mut local);
+                    let (ops, bytes) = {
+                        let mut state = state.lock().unwrap();
+                        let (summary, progress, done) = &mut *state;
+                        summary.merge(local);
+                        *done += 1;
+                        progress.update(*done, summary);
+                        (*done, summary.deleted_size)
+                    };
+                    if let Some(throttle) = &throttle {
+                        throttle.pace(ops, bytes);
+                    }
+                }
+            });
+        }
+    });
+    let (result, progress, _) = state.into_inner().unwrap();
+    progress.finish(&result);
+    *summary = result;
+}
+
+// 删除前检查能否重新下载的文件, 版本目录展开为其中的文件
+pub fn verification_targets(fs: &dyn RepoFs, candidates: &[Candidate]) -> Vec<PathBuf> {
+    let mut targets = Vec::new();
+    for candidate in candidates {
+        match candidate.action {
+            Action::VersionDir => targets.extend(
+                dir_files(fs, &candidate.path)
+                    .into_iter()
+                    .map(|(path, _)| path),
+            ),
+            _ => targets.push(candidate.path.clone()),
+        }
+    }
+    targets
+}
+
+// 候选将删除的全部文件及其大小, 包括校验文件
+pub fn planned_files(fs: &dyn RepoFs, candidates: &[Candidate]) -> Vec<(PathBuf, usize)> {
+    let mut files = Vec::new();
+    for candidate in candidates {
+        let mut paths = vec![candidate.path.clone()];
+        match candidate.action {
+            Action::VersionDir => {
+                files.extend(dir_files(fs, &candidate.path));
+                continue;
+            }
+            Action::WithChecksums => paths.extend(CHECKSUM_SUFFIXIES.iter().map(|suffix| {
+                let mut checksum = candidate.path.as_os_str().to_owned();
+                checksum.push(suffix);
+                PathBuf::from(checksum)
+            })),
+            Action::File => {}
+        }
+        for path in paths {
+            if let Ok(metadata) = fs.metadata(&path) {
+                if !metadata.is_dir && !files.iter().any(|(file, _)| *file == path) {
+                    files.push((path, metadata.len as usize));
+                }
+            }
+        }
+    }
+    files
+}
+
+// 以版本目录为单位汇总待删除的文件, 按大小从大到小
+pub fn planned_versions(files: &[(PathBuf, usize)]) -> Vec<(PathBuf, Stat)> {
+    let mut versions: HashMap<&Path, Stat> = HashMap::new();
+    for (path, size) in files {
+        let stat = versions.entry(path.parent().unwrap_or(path)).or_default();
+        stat.files += 1;
+        stat.size += size;
+    }
+    let mut versions: Vec<(PathBuf, Stat)> = versions
+        .into_iter()
+        .map(|(dir, stat)| (dir.to_path_buf(), stat))
+        .collect();
+    versions.sort_by(|a, b| b.1.size.cmp(&a.1.size).then_with(|| a.0.cmp(&b.0)));
+    versions
+}
+
+pub fn remove_candidate(
+    repo_path: &Path,
+    candidate: &Candidate,
+    options: &CleanupOptions,
+    summary: &mut Summary,
+) {
+    let path = candidate.path.as_path();
+    // 同一文件可能被多条规则选中
+    if !options.fs.exists(path) {
+        return;
+    }
+    // 隔离的候选移入隔离目录, 不归档
+    let quarantine;
+    let options = if candidate.quarantine {
+        quarantine = CleanupOptions {
+            move_to: options.quarantine_dir.clone(),
+            archive_dir: None,
+            ..options.clone()
+        };
+        &quarantine
+    } else {
+        options
+    };
+    let batch = match begin_batch(candidate, options) {
+        Ok(batch) => batch,
+        Err(_) => {
+            summary.error();
+            return;
+        }
+    };
+    let mut local = Summary::default();
+    match candidate.action {
+        Action::File => remove_file(repo_path, path, options, &mut local, candidate.reason),
+        Action::WithChecksums => {
+            remove_with_checksums(repo_path, path, options, &mut local, candidate.reason)
+        }
+        Action::VersionDir => {
+            prune_version_dir(repo_path, path, options, &mut local, candidate.reason)
+        }
+    }
+    commit_batch(batch, options, &local);
+    summary.merge(local);
+}
+
+// 删除前先在日志中记录将删除的文件, 崩溃后据此判断实际删除了哪些;
+// 日志写入失败时不删除
+pub fn begin_batch(candidate: &Candidate, options: &CleanupOptions) -> io::Result<Option<usize>> {
+    let journal = match &options.journal {
+        Some(journal) => journal,
+        None => return Ok(None),
+    };
+    let files = planned_files(&*options.fs, std::slice::from_ref(candidate));
+    journal.begin(candidate, files).map(Some).inspect_err(|e| {
+        log::error!(
+            "Failed to write journal, skipping '{}': {}",
+            candidate.path.display(),
+            e
+        );
+    })
+}
+
+pub fn commit_batch(batch: Option<usize>, options: &CleanupOptions, summary: &Summary) {
+    if let (Some(journal), Some(batch)) = (&options.journal, batch) {
+        journal.commit(batch, summary);
+    }
+}
+
+pub fn remove_file(
+    repo_path: &Path,
+    path: &Path,
+    options: &CleanupOptions,
+    summary: &mut Summary,
+    reason: Reason,
+) {
+    let size = options
+        .fs
+        .metadata(path)
+        .map(|metadata| metadata.len as usize)
+        .unwrap_or(0);
+    if let Some(move_to) = &options.move_to {
+        if move_path(repo_path, path, move_to) {
+            summary.record(reason, path, size);
+        } else {
+            summary.error();
+        }
+        return;
+    }
+    log_deleting(path);
+    match options.fs.remove_file(path) {
+        Ok(_) => summary.record(reason, path, size),
+        Err(e) => {
+            log::error!("Failed to delete file '{}': {}", path.display(), e);
+            summary.error();
+        }
+    }
+}
+
+pub fn remove_with_checksums(
+    repo_path: &Path,
+    path: &Path,
+    options: &CleanupOptions,
+    summary: &mut Summary,
+    reason: Reason,
+) {
+    remove_file(repo_path, path, options, summary, reason);
+    for suffix in CHECKSUM_SUFFIXIES {
+        let mut checksum = path.as_os_str().to_owned();
+        checksum.push(suffix);
+        let checksum = PathBuf::from(checksum);
+        if options.fs.is_file(&checksum) {
+            remove_file(repo_path, &checksum, options, summary, reason);
+        }
+    }
+}
+
+pub fn remove_dir(
+    repo_path: &Path,
+    path: &Path,
+    options: &CleanupOptions,
+    summary: &mut Summary,
+    reason: Reason,
+) {
+    let files = dir_files(&*options.fs, path);
+    let removed = match &options.move_to {
+        Some(move_to) => move_path(repo_path, path, move_to),
+        None => {
+            log_deleting(path);
+            match options.fs.remove_dir(path) {
+                Ok(_) => true,
+                Err(e) => {
+                    log::error!("Failed to delete directory '{}': {}", path.display(), e);
+                    false
+                }
+            }
+        }
+    };
+    if !removed {
+        summary.error();
+        return;
+    }
+    summary.dirs_removed += 1;
+    for (file, size) in files {
+        summary.record(reason, &file, size);
+    }
+}
+
+pub fn prune_version_dir(
+    repo_path: &Path,
+    path: &Path,
+    options: &CleanupOptions,
+    summary: &mut Summary,
+    reason: Reason,
+) {
+    if let Some(archive_dir) = &options.archive_dir {
+        match archive::archive_version(repo_path, path, archive_dir) {
+            Ok(archive) => log::info!("Archived: {} -> {}", path.display(), archive.display()),
+            Err(e) => {
+                log::error!("Failed to archive '{}': {}", path.display(), e);
+                summary.error();
+                return;
+            }
+        }
+    }
+    remove_dir(repo_path, path, options, summary, reason);
+}
+
+fn move_path(repo_path: &Path, path: &Path, move_to: &Path) -> bool {
+    match relocate::move_path(repo_path, path, move_to) {
+        Ok(target) => {
+            log::info!("Moved: {} -> {}", path.display(), target.display());
+            true
+        }
+        Err(e) => {
+            log::error!("Failed to move '{}': {}", path.display(), e);
+            false
+        }
+    }
+}
+
+// 定期输出进度时不再逐个记录删除的文件
+pub fn log_deleting(path: &Path) {
+    match progress::periodic() {
+        true => log::debug!("Deleting: {}", path.display()),
+        false => log::info!("Deleting: {}", path.display()),
+    }
+}
+
+pub fn dir_files(fs: &dyn RepoFs, path: &Path) -> Vec<(PathBuf, usize)> {
+    let mut files = Vec::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(path.to_path_buf());
+    while let Some(path) = queue.pop_front() {
+        if let Ok(folder) = fs.read_dir(&path) {
+            for entry_path in folder.into_iter().flatten() {
+                match fs.metadata(&entry_path) {
+                    // remove_dir_all 只删除链接本身, 不进入链接目录
+                    Ok(metadata) if metadata.is_dir && !metadata.is_symlink => {
+                        queue.push_back(entry_path)
+                    }
+                    Ok(metadata) => files.push((entry_path, metadata.len as usize)),
+                    Err(_) => files.push((entry_path, 0)),
+                }
+            }
+        }
+    }
+    files
+}
+
+pub fn dir_size(path: &Path) -> usize {
+    dir_files(&RealFs, path).iter().map(|(_, size)| size).sum()
+}
@@ -0,0 +1,253 @@
+// 网络文件系统上的瞬时错误 (ESTALE, EIO 等) 按指数退避重试
+use crate::fs::{FsHandle, Metadata, RepoFs};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+const MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Retry {
+    pub attempts: usize,
+    pub backoff: Duration,
+}
+
+pub struct RetryFs {
+    inner: FsHandle,
+    retry: Retry,
+}
+
+impl RetryFs {
+    pub fn new(inner: FsHandle, retry: Retry) -> Self {
+        RetryFs { inner, retry }
+    }
+
+    fn run<T>(&self, path: &Path, mut op: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+        let mut backoff = self.retry.backoff;
+        let mut attempt = 0;
+        loop {
+            match op() {
+                Err(e) if attempt < self.retry.attempts && is_transient(&e) => {
+                    attempt += 1;
+                    log::warn!(
+                        "Transient error on '{}': {}, retrying in {:?} ({}/{})",
+                        path.display(),
+                        e,
+                        backoff,
+                        attempt,
+                        self.retry.attempts
+                    );
+                    thread::sleep(backoff);
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    // 删除可能已在失败的那次调用中生效, 重试时不存在视为成功
+    fn remove(&self, path: &Path, op: impl Fn() -> io::Result<()>) -> io::Result<()> {
+        let mut retried = false;
+        self.run(path, || match op() {
+            Err(e) if retried && e.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => {
+                retried = true;
+                result
+            }
+        })
+    }
+}
+
+#[cfg(unix)]
+pub fn is_transient(error: &io::Error) -> bool {
+    matches!(
+        error.raw_os_error(),
+        Some(libc::ESTALE | libc::EIO | libc::EAGAIN | libc::EINTR | libc::ETIMEDOUT | libc::EBUSY)
+    ) || matches!(
+        error.kind(),
+        io::ErrorKind::Interrupted | io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+    )
+}
+
+// ERROR_SHARING_VIOLATION, ERROR_LOCK_VIOLATION, ERROR_UNEXP_NET_ERR, ERROR_NETNAME_DELETED, ERROR_SEM_TIMEOUT
+#[cfg(not(unix))]
+pub fn is_transient(error: &io::Error) -> bool {
+    matches!(error.raw_os_error(), Some(32 | 33 | 59 | 64 | 121))
+        || matches!(
+            error.kind(),
+            io::ErrorKind::Interrupted | io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+        )
+}
+
+impl RepoFs for RetryFs {
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<io::Result<PathBuf>>> {
+        self.run(path, || self.inner.read_dir(path))
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        self.run(path, || self.inner.metadata(path))
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.run(path, || self.inner.read_to_string(path))
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        self.run(path, || self.inner.write(path, contents))
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.remove(path, || self.inner.remove_file(path))
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        self.remove(path, || self.inner.remove_dir(path))
+    }
+
+    fn is_case_insensitive(&self, repo_path: &Path) -> bool {
+        self.inner.is_case_insensitive(repo_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    // 依次返回预设的错误, 用完后成功, 并记录每次调用的时间
+    #[derive(Default)]
+    struct FlakyFs {
+        errors: Mutex<VecDeque<io::Error>>,
+        calls: Mutex<Vec<Instant>>,
+    }
+
+    impl FlakyFs {
+        fn failing(errors: impl IntoIterator<Item = io::Error>) -> Arc<Self> {
+            Arc::new(FlakyFs {
+                errors: Mutex::new(errors.into_iter().collect()),
+                ..Default::default()
+            })
+        }
+
+        fn call(&self) -> io::Result<()> {
+            self.calls.lock().unwrap().push(Instant::now());
+            self.errors.lock().unwrap().pop_front().map_or(Ok(()), Err)
+        }
+
+        fn calls(&self) -> Vec<Instant> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl RepoFs for FlakyFs {
+        fn read_dir(&self, _path: &Path) -> io::Result<Vec<io::Result<PathBuf>>> {
+            self.call().map(|_| Vec::new())
+        }
+
+        fn metadata(&self, _path: &Path) -> io::Result<Metadata> {
+            self.call().map(|_| Metadata {
+                is_dir: true,
+                is_symlink: false,
+                len: 0,
+                device: None,
+                modified: None,
+            })
+        }
+
+        fn read_to_string(&self, _path: &Path) -> io::Result<String> {
+            self.call().map(|_| String::new())
+        }
+
+        fn write(&self, _path: &Path, _contents: &[u8]) -> io::Result<()> {
+            self.call()
+        }
+
+        fn remove_file(&self, _path: &Path) -> io::Result<()> {
+            self.call()
+        }
+
+        fn remove_dir(&self, _path: &Path) -> io::Result<()> {
+            self.call()
+        }
+    }
+
+    fn retry_fs(inner: &Arc<FlakyFs>, attempts: usize, backoff: Duration) -> RetryFs {
+        RetryFs::new(FsHandle::from(inner.clone()), Retry { attempts, backoff })
+    }
+
+    #[cfg(unix)]
+    fn os_error(code: i32) -> io::Error {
+        io::Error::from_raw_os_error(code)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn classifies_transient_errors() {
+        for code in [
+            libc::ESTALE,
+            libc::EIO,
+            libc::EAGAIN,
+            libc::EINTR,
+            libc::ETIMEDOUT,
+            libc::EBUSY,
+        ] {
+            assert!(is_transient(&os_error(code)), "{}", code);
+        }
+        for code in [libc::ENOENT, libc::EACCES, libc::ENOSPC, libc::EROFS] {
+            assert!(!is_transient(&os_error(code)), "{}", code);
+        }
+        assert!(is_transient(&io::Error::from(io::ErrorKind::TimedOut)));
+        assert!(!is_transient(&io::Error::from(io::ErrorKind::InvalidData)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn retries_transient_errors_with_doubling_backoff() {
+        let inner = FlakyFs::failing([os_error(libc::ESTALE), os_error(libc::EIO)]);
+        let fs = retry_fs(&inner, 3, Duration::from_millis(20));
+        assert!(fs.read_to_string(Path::new("/repo/a.pom")).is_ok());
+        let calls = inner.calls();
+        assert_eq!(calls.len(), 3);
+        assert!(calls[1] - calls[0] >= Duration::from_millis(20));
+        assert!(calls[2] - calls[1] >= Duration::from_millis(40));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn gives_up_after_the_configured_attempts() {
+        let inner = FlakyFs::failing((0..5).map(|_| os_error(libc::ESTALE)));
+        let fs = retry_fs(&inner, 2, Duration::from_millis(1));
+        let error = fs.metadata(Path::new("/repo/a.jar")).unwrap_err();
+        assert_eq!(error.raw_os_error(), Some(libc::ESTALE));
+        assert_eq!(inner.calls().len(), 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn does_not_retry_permanent_errors() {
+        let inner = FlakyFs::failing([os_error(libc::EACCES)]);
+        let fs = retry_fs(&inner, 3, Duration::from_millis(1));
+        let error = fs.write(Path::new("/repo/a.xml"), b"").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(inner.calls().len(), 1);
+    }
+
+    #[test]
+    fn removals_already_applied_before_a_retry_succeed() {
+        let inner = FlakyFs::failing([
+            io::Error::from(io::ErrorKind::TimedOut),
+            io::Error::from(io::ErrorKind::NotFound),
+        ]);
+        let fs = retry_fs(&inner, 3, Duration::from_millis(1));
+        assert!(fs.remove_file(Path::new("/repo/a.jar")).is_ok());
+        assert_eq!(inner.calls().len(), 2);
+
+        // 第一次调用就不存在时照常报错
+        let inner = FlakyFs::failing([io::Error::from(io::ErrorKind::NotFound)]);
+        let fs = retry_fs(&inner, 3, Duration::from_millis(1));
+        assert!(fs.remove_dir(Path::new("/repo/a")).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::gav::{self, VersionDir};
+use crate::hash;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+static PRIMARY_EXTENSIONS: [&str; 5] = ["jar", "war", "ear", "aar", "pom"];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Bom {
+    bom_format: &'static str,
+    spec_version: &'static str,
+    version: u32,
+    metadata: Metadata,
+    components: Vec<Component>,
+}
+
+#[derive(Serialize)]
+struct Metadata {
+    tools: Tools,
+}
+
+#[derive(Serialize)]
+struct Tools {
+    components: Vec<Tool>,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: &'static str,
+    version: &'static str,
+}
+
+#[derive(Serialize)]
+struct Component {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    #[serde(rename = "bom-ref")]
+    bom_ref: String,
+    group: String,
+    name: String,
+    version: String,
+    purl: String,
+    hashes: Vec<Hash>,
+}
+
+#[derive(Serialize)]
+struct Hash {
+    alg: &'static str,
+    content: String,
+}
+
+pub fn export(repo_path: PathBuf, output: Option<&Path>) -> bool {
+    let mut components = Vec::new();
+    for version in gav::scan_versions(&repo_path) {
+        if let Some(component) = component(&version) {
+            components.push(component);
+        }
+    }
+    log::info!("Components: {}", components.len());
+
+    let bom = Bom {
+        bom_format: "CycloneDX",
+        spec_version: "1.5",
+        version: 1,
+        metadata: Metadata {
+            tools: Tools {
+                components: vec![Tool {
+                    kind: "application",
+                    name: env!("CARGO_PKG_NAME"),
+                    version: env!("CARGO_PKG_VERSION"),
+                }],
+            },
+        },
+        components,
+    };
+    let content = serde_json::to_string_pretty(&bom).expect("Failed to serialize SBOM");
+    match output {
+        Some(output) => {
+            if let Err(e) = std::fs::write(output, content) {
+                log::error!("Failed to write '{}': {}", output.display(), e);
+                return false;
+            }
+            log::info!("Written: {}", output.display());
+        }
+        None => println!("{}", content),
+    }
+    true
+}
+
+fn component(version: &VersionDir) -> Option<Component> {
+    let gav = &version.gav;
+    let (extension, path) = PRIMARY_EXTENSIONS.iter().find_map(|extension| {
+        let file_name = format!("{}-{}.{}", gav.artifact_id, gav.version, extension);
+        version
+            .files
+            .contains(&file_name)
+            .then(|| (*extension, version.path.join(file_name)))
+    })?;
+
+    let mut hashes = Vec::new();
+    match hash::sha1_file(&path) {
+        Ok(content) => hashes.push(Hash {
+            alg: "SHA-1",
+            content,
+        }),
+        Err(e) => log::error!("Failed to hash '{}': {}", path.display(), e),
+    }
+    match hash::sha256_file(&path) {
+        Ok(content) => hashes.push(Hash {
+            alg: "SHA-256",
+            content,
+        }),
+        Err(e) => log::error!("Failed to hash '{}': {}", path.display(), e),
+    }
+
+    let purl = format!(
+        "pkg:maven/{}/{}@{}?type={}",
+        gav.group_id, gav.artifact_id, gav.version, extension
+    );
+    Some(Component {
+        kind: "library",
+        bom_ref: purl.clone(),
+        group: gav.group_id.clone(),
+        name: gav.artifact_id.clone(),
+        version: gav.version.clone(),
+        purl,
+        hashes,
+    })
+}
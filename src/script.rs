@@ -0,0 +1,138 @@
+use crate::gav;
+use crate::remove::{self, Candidate};
+use crate::CleanupOptions;
+use rhai::{Dynamic, Engine, Map, Scope, AST};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+static DECIDE_FN: &str = "decide";
+
+// 用户脚本定义 fn decide(candidate), 返回 "keep", "delete" 或 "quarantine"
+pub struct Script {
+    engine: Engine,
+    ast: AST,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Keep,
+    Delete,
+    Quarantine,
+}
+
+pub fn load(path: &Path) -> Result<Script, String> {
+    let engine = Engine::new();
+    let ast = engine
+        .compile_file(path.to_path_buf())
+        .map_err(|e| e.to_string())?;
+    if !ast
+        .iter_functions()
+        .any(|f| f.name == DECIDE_FN && f.params.len() == 1)
+    {
+        return Err(format!("missing fn {}(candidate)", DECIDE_FN));
+    }
+    Ok(Script { engine, ast })
+}
+
+impl Script {
+    pub fn decide(
+        &self,
+        repo_path: &Path,
+        candidate: &Candidate,
+        options: &CleanupOptions,
+    ) -> Verdict {
+        let fs = &*options.fs;
+        let mut map = Map::new();
+        let version_dir = match candidate.action {
+            remove::Action::VersionDir => Some(candidate.path.as_path()),
+            _ => candidate.path.parent(),
+        };
+        if let Some(gav) = version_dir.and_then(|dir| gav::from_version_dir(repo_path, dir)) {
+            map.insert("group".into(), gav.group_id.into());
+            map.insert("artifact".into(), gav.artifact_id.into());
+            map.insert("version".into(), gav.version.into());
+        }
+        let size: usize = remove::planned_files(fs, std::slice::from_ref(candidate))
+            .iter()
+            .map(|(_, size)| size)
+            .sum();
+        let mtime = fs
+            .metadata(&candidate.path)
+            .ok()
+            .and_then(|metadata| metadata.modified)
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |modified| modified.as_secs() as i64);
+        map.insert("path".into(), candidate.path.display().to_string().into());
+        map.insert("size".into(), (size as i64).into());
+        map.insert("mtime".into(), mtime.into());
+        map.insert("reason".into(), candidate.reason.code().into());
+
+        let result = self.engine.call_fn::<Dynamic>(
+            &mut Scope::new(),
+            &self.ast,
+            DECIDE_FN,
+            (Dynamic::from_map(map),),
+        );
+        // 脚本出错时保守地保留文件
+        match result.map(|verdict| verdict.into_string()) {
+            Ok(Ok(verdict)) if verdict == "delete" => Verdict::Delete,
+            Ok(Ok(verdict)) if verdict == "quarantine" => Verdict::Quarantine,
+            Ok(Ok(verdict)) if verdict == "keep" => Verdict::Keep,
+            Ok(Ok(verdict)) => {
+                log::error!(
+                    "Script returned '{}' for {}, keeping",
+                    verdict,
+                    candidate.path.display()
+                );
+                Verdict::Keep
+            }
+            Ok(Err(type_name)) => {
+                log::error!(
+                    "Script returned {} for {}, keeping",
+                    type_name,
+                    candidate.path.display()
+                );
+                Verdict::Keep
+            }
+            Err(e) => {
+                log::error!(
+                    "Script failed for {}: {}, keeping",
+                    candidate.path.display(),
+                    e
+                );
+                Verdict::Keep
+            }
+        }
+    }
+
+    pub fn apply(
+        &self,
+        repo_path: &Path,
+        candidates: Vec<Candidate>,
+        options: &CleanupOptions,
+    ) -> Vec<Candidate> {
+        candidates
+            .into_iter()
+            .filter_map(
+                |mut candidate| match self.decide(repo_path, &candidate, options) {
+                    Verdict::Delete => Some(candidate),
+                    Verdict::Quarantine if options.quarantine_dir.is_some() => {
+                        candidate.quarantine = true;
+                        Some(candidate)
+                    }
+                    Verdict::Quarantine => {
+                        log::warn!(
+                            "Quarantine requested without --quarantine-dir, keeping: {}",
+                            candidate.path.display()
+                        );
+                        None
+                    }
+                    Verdict::Keep => {
+                        log::debug!("Kept by script: {}", candidate.path.display());
+                        None
+                    }
+                },
+            )
+            .collect()
+    }
+}
@@ -0,0 +1,309 @@
+use crate::xml::{child, children, text};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "remote")]
+use std::sync::OnceLock;
+#[cfg(feature = "remote")]
+use std::time::Duration;
+
+static CENTRAL_ID: &str = "central";
+
+static CENTRAL_URL: &str = "https://repo.maven.apache.org/maven2";
+
+// --proxy 指定的代理, 优先于环境变量和 settings.xml
+#[cfg(feature = "remote")]
+static PROXY: OnceLock<ureq::Proxy> = OnceLock::new();
+
+// --offline 时拒绝创建 agent, 保证不会发出任何网络请求
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone)]
+pub struct Repository {
+    pub id: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Mirror {
+    pub id: String,
+    pub url: String,
+    pub mirror_of: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Proxy {
+    pub protocol: String,
+    pub host: String,
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub non_proxy_hosts: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct Settings {
+    pub repositories: Vec<Repository>,
+    pub mirrors: Vec<Mirror>,
+    // 只包含 active 的代理, Maven 使用第一个
+    pub proxies: Vec<Proxy>,
+    pub local_repository: Option<PathBuf>,
+}
+
+impl Settings {
+    // 与 Maven 相同: 精确匹配仓库 id 的镜像优先, 否则取第一个模式匹配的镜像
+    pub fn mirror_of(&self, repository: &Repository) -> Option<&Mirror> {
+        self.mirrors
+            .iter()
+            .find(|mirror| mirror.mirror_of.trim() == repository.id)
+            .or_else(|| {
+                self.mirrors
+                    .iter()
+                    .find(|mirror| mirror_matches(&mirror.mirror_of, repository))
+            })
+    }
+
+    // 被镜像的仓库替换为镜像, 多个仓库共用一个镜像时只访问一次
+    pub fn resolved_repositories(&self) -> Vec<Repository> {
+        let mut resolved: Vec<Repository> = Vec::new();
+        for repository in &self.repositories {
+            let repository = match self.mirror_of(repository) {
+                Some(mirror) => Repository {
+                    id: mirror.id.clone(),
+                    url: mirror.url.clone(),
+                },
+                None => repository.clone(),
+            };
+            if !resolved.iter().any(|r| r.id == repository.id) {
+                resolved.push(repository);
+            }
+        }
+        resolved
+    }
+
+    // 通过镜像下载的构件在 _remote.repositories 中记录的是镜像 id
+    pub fn repository_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.repositories.iter().map(|r| r.id.clone()).collect();
+        for mirror in &self.mirrors {
+            if !ids.contains(&mirror.id) {
+                ids.push(mirror.id.clone());
+            }
+        }
+        ids
+    }
+
+    // 访问网络的功能共用此 agent. 代理依次取 --proxy、环境变量
+    // (ALL_PROXY/HTTPS_PROXY/HTTP_PROXY, 以及 NO_PROXY) 和 settings.xml
+    #[cfg(feature = "remote")]
+    pub fn agent(&self, timeout: Duration) -> Result<ureq::Agent, String> {
+        if offline() {
+            return Err("network access is disabled by --offline".to_string());
+        }
+        let proxy = PROXY
+            .get()
+            .cloned()
+            .or_else(ureq::Proxy::try_from_env)
+            .or_else(|| self.settings_proxy());
+        if let Some(proxy) = &proxy {
+            log::debug!("Using proxy: {}:{}", proxy.host(), proxy.port());
+        }
+        Ok(ureq::Agent::config_builder()
+            .timeout_global(Some(timeout))
+            .proxy(proxy)
+            .build()
+            .into())
+    }
+
+    #[cfg(feature = "remote")]
+    fn settings_proxy(&self) -> Option<ureq::Proxy> {
+        let proxy = self.proxies.first()?;
+        proxy
+            .build()
+            .inspect_err(|e| log::error!("Invalid proxy '{}': {}", proxy.host, e))
+            .ok()
+    }
+}
+
+#[cfg(feature = "remote")]
+impl Proxy {
+    fn build(&self) -> Result<ureq::Proxy, ureq::Error> {
+        let protocol = ureq::ProxyProtocol::try_from(self.protocol.as_str())?;
+        let mut builder = ureq::Proxy::builder(protocol).host(&self.host);
+        if let Some(port) = self.port {
+            builder = builder.port(port);
+        }
+        if let Some(username) = &self.username {
+            builder = builder.username(username);
+        }
+        if let Some(password) = &self.password {
+            builder = builder.password(password);
+        }
+        // nonProxyHosts 以 | 分隔, 也接受逗号
+        for host in self
+            .non_proxy_hosts
+            .iter()
+            .flat_map(|hosts| hosts.split(['|', ',']))
+        {
+            builder = builder.no_proxy(host.trim());
+        }
+        builder.build()
+    }
+}
+
+#[cfg(feature = "remote")]
+pub fn set_proxy(url: &str) -> Result<(), String> {
+    let proxy = ureq::Proxy::new(url).map_err(|e| e.to_string())?;
+    let _ = PROXY.set(proxy);
+    Ok(())
+}
+
+pub fn set_offline(enabled: bool) {
+    OFFLINE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+// 支持 *, external:*, external:http:*, 逗号分隔的 id 列表和 !id 排除
+fn mirror_matches(mirror_of: &str, repository: &Repository) -> bool {
+    let mut matched = false;
+    for pattern in mirror_of.split(',').map(str::trim) {
+        if let Some(excluded) = pattern.strip_prefix('!') {
+            if excluded == repository.id {
+                return false;
+            }
+            continue;
+        }
+        matched |= match pattern {
+            "*" => true,
+            "external:*" => is_external(&repository.url),
+            "external:http:*" => {
+                is_external(&repository.url) && repository.url.starts_with("http:")
+            }
+            id => id == repository.id,
+        };
+    }
+    matched
+}
+
+fn is_external(url: &str) -> bool {
+    let host = url
+        .split_once("://")
+        .map(|(_, rest)| rest.split(['/', ':']).next().unwrap_or_default());
+    match host {
+        Some(host) => !url.starts_with("file:") && host != "localhost" && host != "127.0.0.1",
+        None => false,
+    }
+}
+
+pub fn default_path() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".m2").join("settings.xml"))
+}
+
+// Maven 默认的本地仓库位置
+pub fn default_repository() -> Option<PathBuf> {
+    default_path().map(|settings| settings.with_file_name("repository"))
+}
+
+pub fn load(path: Option<&Path>) -> Settings {
+    let mut settings = Settings::default();
+    let path = match path.map(Path::to_path_buf).or_else(default_path) {
+        Some(path) if path.is_file() => path,
+        _ => {
+            settings.repositories.push(central());
+            return settings;
+        }
+    };
+
+    log::debug!("Loading settings: {}", path.display());
+    match std::fs::read_to_string(&path) {
+        Ok(content) => match roxmltree::Document::parse(&content) {
+            Ok(document) => settings = parse(document.root_element()),
+            Err(e) => log::error!("Failed to parse settings '{}': {}", path.display(), e),
+        },
+        Err(e) => log::error!("Failed to read settings '{}': {}", path.display(), e),
+    }
+    if !settings.repositories.iter().any(|r| r.id == CENTRAL_ID) {
+        settings.repositories.push(central());
+    }
+    settings
+}
+
+fn central() -> Repository {
+    Repository {
+        id: CENTRAL_ID.to_string(),
+        url: CENTRAL_URL.to_string(),
+    }
+}
+
+fn parse(root: roxmltree::Node) -> Settings {
+    let active_profiles: Vec<String> = child(root, "activeProfiles")
+        .map(|node| children(node, "activeProfile").map(text).collect())
+        .unwrap_or_default();
+
+    let mut repositories = Vec::new();
+    for profile in child(root, "profiles")
+        .into_iter()
+        .flat_map(|node| children(node, "profile"))
+    {
+        let id = child(profile, "id").map(text).unwrap_or_default();
+        let active_by_default = child(profile, "activation")
+            .and_then(|node| child(node, "activeByDefault"))
+            .map(|node| text(node) == "true")
+            .unwrap_or(false);
+        if !active_by_default && !active_profiles.contains(&id) {
+            continue;
+        }
+        for repository in child(profile, "repositories")
+            .into_iter()
+            .chain(child(profile, "pluginRepositories"))
+            .flat_map(|node| node.children().filter(|n| n.is_element()))
+        {
+            let id = child(repository, "id").map(text);
+            let url = child(repository, "url").map(text);
+            if let (Some(id), Some(url)) = (id, url) {
+                if !repositories.iter().any(|r: &Repository| r.id == id) {
+                    repositories.push(Repository { id, url });
+                }
+            }
+        }
+    }
+    let mirrors = child(root, "mirrors")
+        .into_iter()
+        .flat_map(|node| children(node, "mirror"))
+        .filter_map(|mirror| {
+            Some(Mirror {
+                id: child(mirror, "id").map(text)?,
+                url: child(mirror, "url").map(text)?,
+                mirror_of: child(mirror, "mirrorOf").map(text)?,
+            })
+        })
+        .collect();
+    let proxies = child(root, "proxies")
+        .into_iter()
+        .flat_map(|node| children(node, "proxy"))
+        .filter(|proxy| child(*proxy, "active").map(text).as_deref() != Some("false"))
+        .filter_map(|proxy| {
+            let optional = |name| child(proxy, name).map(text).filter(|v| !v.is_empty());
+            Some(Proxy {
+                protocol: optional("protocol").unwrap_or_else(|| "http".to_string()),
+                host: optional("host")?,
+                port: optional("port").and_then(|port| port.parse().ok()),
+                username: optional("username"),
+                password: optional("password"),
+                non_proxy_hosts: optional("nonProxyHosts"),
+            })
+        })
+        .collect();
+    Settings {
+        repositories,
+        mirrors,
+        proxies,
+        local_repository: child(root, "localRepository")
+            .map(text)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from),
+    }
+}
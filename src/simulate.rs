@@ -0,0 +1,37 @@
+use crate::policy::Policy;
+use crate::remove::planned_files;
+use crate::{human_size, i18n, Cleaner, CleanupOptions};
+use std::path::Path;
+
+// 只扫描不删除, 比较各策略可回收的空间
+pub fn simulate(
+    repo_path: &Path,
+    policies: &[Policy],
+    base: &CleanupOptions,
+    known_repositories: &[String],
+) {
+    println!(
+        "{:<30} {:>10} {:>10} {:>12} {:>14}",
+        i18n::translate("Policy"),
+        i18n::translate("Candidates"),
+        i18n::translate("Files"),
+        i18n::translate("Size"),
+        i18n::translate("Bytes")
+    );
+    for policy in policies {
+        log::info!("Simulating policy: {}", policy.name);
+        let options = policy.apply(base, known_repositories);
+        let cleaner = Cleaner::new(repo_path.to_path_buf(), options.clone());
+        let candidates = cleaner.scan();
+        let files = planned_files(&*options.fs, &candidates);
+        let size: usize = files.iter().map(|(_, size)| size).sum();
+        println!(
+            "{:<30} {:>10} {:>10} {:>12} {:>14}",
+            policy.name,
+            candidates.len(),
+            files.len(),
+            human_size(size),
+            size
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use crate::SNAPSHOT_SUFFIX;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// 部署到远程仓库的快照: 1.0-SNAPSHOT 的构件文件名为 artifact-1.0-yyyyMMdd.HHmmss-N.jar (UTC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+// 先按时间戳, 再按构建号排序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Build {
+    pub timestamp: Timestamp,
+    pub number: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotVersion<'a> {
+    // 去掉 -SNAPSHOT 或时间戳后的版本, 如 1.0
+    pub base: &'a str,
+    // 基础形式 1.0-SNAPSHOT 没有构建信息
+    pub build: Option<Build>,
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}{:02}{:02}.{:02}{:02}{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+impl fmt::Display for Build {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.timestamp, self.number)
+    }
+}
+
+fn digits<T: std::str::FromStr>(text: &str, range: std::ops::RangeInclusive<u32>) -> Option<T> {
+    if !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let value: u32 = text.parse().ok()?;
+    range.contains(&value).then(|| text.parse().ok())?
+}
+
+impl Timestamp {
+    // yyyyMMdd.HHmmss
+    pub fn parse(text: &str) -> Option<Self> {
+        let (date, time) = text.split_once('.')?;
+        // 先确认全是 ASCII 数字, 再按字节切分, 否则多字节字符会切在字符中间
+        let all_digits = |text: &str| text.bytes().all(|byte| byte.is_ascii_digit());
+        if date.len() != 8 || time.len() != 6 || !all_digits(date) || !all_digits(time) {
+            return None;
+        }
+        Some(Timestamp {
+            year: digits(&date[..4], 0..=9999)?,
+            month: digits(&date[4..6], 1..=12)?,
+            day: digits(&date[6..], 1..=31)?,
+            hour: digits(&time[..2], 0..=23)?,
+            minute: digits(&time[2..4], 0..=59)?,
+            // 允许闰秒
+            second: digits(&time[4..], 0..=60)?,
+        })
+    }
+
+    pub fn to_system_time(self) -> SystemTime {
+        // 公历日期转为 1970-01-01 起的天数
+        let (year, month) = (self.year as i64, self.month as i64);
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146097 + day_of_era - 719468;
+        let seconds =
+            days * 86400 + self.hour as i64 * 3600 + self.minute as i64 * 60 + self.second as i64;
+        match u64::try_from(seconds) {
+            Ok(seconds) => UNIX_EPOCH + Duration::from_secs(seconds),
+            Err(_) => UNIX_EPOCH - Duration::from_secs(seconds.unsigned_abs()),
+        }
+    }
+
+    // to_system_time 的逆运算, 1970-01-01 起的秒数 (UTC)
+    pub fn from_unix(seconds: u64) -> Self {
+        let days = (seconds / 86400) as i64 + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days - era * 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month + 2) / 5 + 1;
+        let month = if month < 10 { month + 3 } else { month - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        let time = seconds % 86400;
+        Timestamp {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+            hour: (time / 3600) as u8,
+            minute: (time / 60 % 60) as u8,
+            second: (time % 60) as u8,
+        }
+    }
+}
+
+impl Build {
+    // yyyyMMdd.HHmmss-N
+    pub fn parse(text: &str) -> Option<Self> {
+        match Build::split(text) {
+            Some((build, "")) => Some(build),
+            _ => None,
+        }
+    }
+
+    // 解析开头的 yyyyMMdd.HHmmss-N, 返回其后剩余的部分 (如 -sources.jar)
+    pub fn split(text: &str) -> Option<(Self, &str)> {
+        let timestamp = Timestamp::parse(text.get(..15)?)?;
+        let rest = text[15..].strip_prefix('-')?;
+        let end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let number = rest[..end].parse().ok()?;
+        Some((Build { timestamp, number }, &rest[end..]))
+    }
+}
+
+// 1.0-SNAPSHOT 或 1.0-20240101.120000-3, 非快照版本返回 None
+pub fn parse(version: &str) -> Option<SnapshotVersion<'_>> {
+    if let Some(base) = version.strip_suffix(SNAPSHOT_SUFFIX) {
+        return Some(SnapshotVersion { base, build: None });
+    }
+    // 时间戳中没有 '-', 从右侧第二个 '-' 处切分
+    let (rest, _) = version.rsplit_once('-')?;
+    let (base, _) = rest.rsplit_once('-')?;
+    let build = Build::parse(&version[base.len() + 1..])?;
+    Some(SnapshotVersion {
+        base,
+        build: Some(build),
+    })
+}
+
+// 版本目录 1.0-SNAPSHOT 中 artifact-1.0-yyyyMMdd.HHmmss-N[-classifier].ext 的构建信息
+pub fn parse_file_name(artifact_id: &str, version: &str, file_name: &str) -> Option<Build> {
+    let base = version.strip_suffix(SNAPSHOT_SUFFIX)?;
+    let rest = file_name
+        .strip_prefix(artifact_id)?
+        .strip_prefix('-')?
+        .strip_prefix(base)?
+        .strip_prefix('-')?;
+    Build::split(rest).map(|(build, _)| build)
+}
@@ -0,0 +1,332 @@
+use crate::{duration, format_size, gav, hooks, human_size, CleanupOptions};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+// 删除原因, 以稳定的代码出现在日志、报告、钩子和脚本的输入中
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reason {
+    StaleSnapshot,
+    SupersededSnapshot,
+    LocalMetadata,
+    MetadataOnly,
+    LastUpdatedMarker,
+    OrphanedChecksum,
+    OverKeepCount,
+    VersionRange,
+    Decommissioned,
+    Corrupt,
+    Vulnerable,
+    Purged,
+    StaleLock,
+    // 策略规则名或插件给出的其他原因
+    Custom(&'static str),
+}
+
+static REASON_CODES: [(Reason, &str); 13] = [
+    (Reason::StaleSnapshot, "stale-snapshot"),
+    (Reason::SupersededSnapshot, "superseded-snapshot"),
+    (Reason::LocalMetadata, "local-metadata"),
+    (Reason::MetadataOnly, "metadata-only"),
+    (Reason::LastUpdatedMarker, "last-updated-marker"),
+    (Reason::OrphanedChecksum, "orphaned-checksum"),
+    (Reason::OverKeepCount, "over-keep-count"),
+    (Reason::VersionRange, "version-range"),
+    (Reason::Decommissioned, "decommissioned-repository"),
+    (Reason::Corrupt, "corrupt"),
+    (Reason::Vulnerable, "vulnerable"),
+    (Reason::Purged, "purged"),
+    (Reason::StaleLock, "stale-lock"),
+];
+
+impl Reason {
+    pub fn code(self) -> &'static str {
+        match self {
+            Reason::Custom(code) => code,
+            reason => REASON_CODES
+                .iter()
+                .find(|(known, _)| *known == reason)
+                .map(|(_, code)| *code)
+                .unwrap_or_default(),
+        }
+    }
+
+    // 内置代码对应内置原因, 例如以 orphaned-checksum 命名的策略规则;
+    // 其他文本作为自定义原因, 同一文本只泄漏一次
+    pub fn from_code(code: &str) -> Reason {
+        static CUSTOM: OnceLock<Mutex<HashMap<String, &'static str>>> = OnceLock::new();
+        if let Some((reason, _)) = REASON_CODES.iter().find(|(_, known)| *known == code) {
+            return *reason;
+        }
+        let mut custom = CUSTOM.get_or_init(Default::default).lock().unwrap();
+        Reason::Custom(
+            custom
+                .entry(code.to_string())
+                .or_insert_with_key(|code| Box::leak(code.clone().into_boxed_str())),
+        )
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.code())
+    }
+}
+
+impl Serialize for Reason {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+// 按代码排序, 汇总中的原因按字母顺序输出
+impl Ord for Reason {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.code().cmp(other.code())
+    }
+}
+
+impl PartialOrd for Reason {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(clap::ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    #[default]
+    #[value(help = "Full summary including every deleted file")]
+    Json,
+    #[value(help = "Compact per-artifact table for PR/MR comments")]
+    Markdown,
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct Stat {
+    pub files: usize,
+    pub size: usize,
+}
+
+impl Stat {
+    fn add(&mut self, other: Stat) {
+        self.files += other.files;
+        self.size += other.size;
+    }
+}
+
+// 扫描和删除的统计, 便于在不同机器和版本之间比较性能
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct ScanStats {
+    pub dirs_visited: usize,
+    pub files_examined: usize,
+    pub scan_time: Duration,
+    pub delete_time: Duration,
+    // 进程的峰值常驻内存, 无法获取时为 None
+    pub peak_memory: Option<usize>,
+}
+
+// 每个被删除 (或移走) 的文件, 写入报告后可作为下次 diff 的清单
+#[derive(Debug, Clone, Serialize)]
+pub struct Deletion {
+    pub path: PathBuf,
+    pub reason: Reason,
+    pub size: usize,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Summary {
+    pub deleted_size: usize,
+    pub files_deleted: usize,
+    pub dirs_removed: usize,
+    pub files_skipped: usize,
+    pub errors: usize,
+    pub unreadable: Vec<PathBuf>,
+    pub aborted: bool,
+    pub by_reason: BTreeMap<Reason, Stat>,
+    pub by_extension: BTreeMap<String, Stat>,
+    pub deleted: Vec<Deletion>,
+    pub stats: ScanStats,
+}
+
+impl Summary {
+    pub fn record(&mut self, reason: Reason, path: &Path, size: usize) {
+        self.deleted_size += size;
+        self.files_deleted += 1;
+        let stat = self.by_reason.entry(reason).or_default();
+        stat.files += 1;
+        stat.size += size;
+        let extension = path
+            .extension()
+            .map(|extension| format!(".{}", extension.to_string_lossy()))
+            .unwrap_or_else(|| "(none)".to_string());
+        let stat = self.by_extension.entry(extension).or_default();
+        stat.files += 1;
+        stat.size += size;
+        self.deleted.push(Deletion {
+            path: path.to_path_buf(),
+            reason,
+            size,
+        });
+    }
+
+    pub fn merge(&mut self, other: Summary) {
+        self.deleted_size += other.deleted_size;
+        self.files_deleted += other.files_deleted;
+        self.dirs_removed += other.dirs_removed;
+        self.files_skipped += other.files_skipped;
+        self.errors += other.errors;
+        self.unreadable.extend(other.unreadable);
+        self.aborted |= other.aborted;
+        for (reason, stat) in other.by_reason {
+            self.by_reason.entry(reason).or_default().add(stat);
+        }
+        for (extension, stat) in other.by_extension {
+            self.by_extension.entry(extension).or_default().add(stat);
+        }
+        self.deleted.extend(other.deleted);
+        self.stats.dirs_visited += other.stats.dirs_visited;
+        self.stats.files_examined += other.stats.files_examined;
+    }
+
+    pub fn skip(&mut self) {
+        self.files_skipped += 1;
+    }
+
+    pub fn error(&mut self) {
+        self.errors += 1;
+    }
+
+    pub fn finish(&self, repo_path: &Path, options: &CleanupOptions) {
+        self.log();
+        if let Some(command) = &options.on_after_run {
+            hooks::after_run(command, self);
+        }
+        if let Some(report) = &options.report {
+            let result = match options.report_format {
+                ReportFormat::Json => self.write_report(report),
+                ReportFormat::Markdown => std::fs::write(report, self.markdown(repo_path)),
+            };
+            if let Err(e) = result {
+                log::error!("Failed to write report '{}': {}", report.display(), e);
+            }
+        }
+    }
+
+    fn log(&self) {
+        log::info!("Deleted size: {}", format_size(self.deleted_size));
+        log::info!("Deleted files: {}", self.files_deleted);
+        log::info!("Removed directories: {}", self.dirs_removed);
+        log::info!("Skipped files: {}", self.files_skipped);
+        log::info!("Errors: {}", self.errors);
+        if !self.unreadable.is_empty() {
+            log::info!("Unreadable (skipped): {}", self.unreadable.len());
+            for path in &self.unreadable {
+                log::info!("  {}", path.display());
+            }
+        }
+        // 只删除不扫描时 (如 Cleaner::clean) 没有统计
+        if self.stats.dirs_visited > 0 {
+            log::info!("Scanned directories: {}", self.stats.dirs_visited);
+            log::info!("Examined files: {}", self.stats.files_examined);
+            log::info!("Scan time: {}", duration::format(self.stats.scan_time));
+            log::info!("Delete time: {}", duration::format(self.stats.delete_time));
+            if let Some(peak_memory) = self.stats.peak_memory {
+                log::info!("Peak memory: {}", format_size(peak_memory));
+            }
+        }
+        for (reason, stat) in &self.by_reason {
+            log::info!(
+                "  {}: {} across {} files",
+                reason,
+                format_size(stat.size),
+                stat.files
+            );
+        }
+        for (extension, stat) in &self.by_extension {
+            log::info!(
+                "  {}: {} across {} files",
+                extension,
+                format_size(stat.size),
+                stat.files
+            );
+        }
+    }
+
+    // 每个构件一行: 删除的版本和大小
+    pub fn markdown(&self, repo_path: &Path) -> String {
+        let mut artifacts: BTreeMap<String, (BTreeSet<String>, usize, usize)> = BTreeMap::new();
+        for deletion in &self.deleted {
+            let (artifact, version) = artifact_version(repo_path, &deletion.path)
+                .unwrap_or_else(|| (deletion.path.display().to_string(), String::new()));
+            let entry = artifacts.entry(artifact).or_default();
+            if !version.is_empty() {
+                entry.0.insert(version);
+            }
+            entry.1 += 1;
+            entry.2 += deletion.size;
+        }
+        let mut rows: Vec<_> = artifacts.into_iter().collect();
+        rows.sort_by(|a, b| b.1 .2.cmp(&a.1 .2).then_with(|| a.0.cmp(&b.0)));
+
+        let mut text = format!(
+            "**Repository cleanup:** {} freed, {} files deleted, {} errors\n\n",
+            human_size(self.deleted_size),
+            self.files_deleted,
+            self.errors
+        );
+        if rows.is_empty() {
+            return text;
+        }
+        text.push_str("| Artifact | Versions | Files | Size |\n");
+        text.push_str("| --- | --- | ---: | ---: |\n");
+        for (artifact, (versions, files, size)) in rows {
+            let versions: Vec<String> = versions.into_iter().collect();
+            text.push_str(&format!(
+                "| `{}` | {} | {} | {} |\n",
+                artifact,
+                versions.join(", "),
+                files,
+                human_size(size)
+            ));
+        }
+        text
+    }
+
+    fn write_report(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json + "\n")
+    }
+}
+
+// 文件所属的 group:artifact 和版本, 构件目录下的元数据文件没有版本
+fn artifact_version(repo_path: &Path, path: &Path) -> Option<(String, String)> {
+    let gav = gav::from_version_dir(repo_path, path.parent()?)?;
+    let file_name = path.file_name()?.to_string_lossy();
+    if file_name.starts_with(&format!("{}-", gav.artifact_id))
+        || gav.version.starts_with(|c: char| c.is_ascii_digit())
+    {
+        return Some((format!("{}:{}", gav.group_id, gav.artifact_id), gav.version));
+    }
+    Some((
+        format!("{}.{}:{}", gav.group_id, gav.artifact_id, gav.version),
+        String::new(),
+    ))
+}
+
+// Linux 上读取 /proc/self/status 的 VmHWM, 其他平台返回 None
+pub fn peak_memory() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kilobytes: usize = line
+        .trim_start_matches("VmHWM:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kilobytes * 1024)
+}
@@ -0,0 +1,84 @@
+use crate::size_unit;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Limit {
+    BytesPerSecond(f64),
+    OpsPerSecond(f64),
+}
+
+// 支持: 20MiB/s, 500KiB/s, 100ops/s
+pub fn parse(text: &str) -> Result<Limit, String> {
+    let text = text.trim();
+    let rate = text
+        .strip_suffix("/s")
+        .ok_or_else(|| format!("missing '/s': {}", text))?;
+    let split = rate
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .ok_or_else(|| format!("missing unit: {}", text))?;
+    let (number, unit) = rate.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid number: {}", text))?;
+    if number <= 0.0 {
+        return Err(format!("rate must be positive: {}", text));
+    }
+    if unit == "ops" {
+        return Ok(Limit::OpsPerSecond(number));
+    }
+    let multiplier = size_unit(unit).ok_or_else(|| format!("invalid unit: {}", unit))?;
+    Ok(Limit::BytesPerSecond(number * multiplier))
+}
+
+// 限速所用的时钟, 测试中可替换为不真正睡眠的时钟
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+pub struct Throttle {
+    limit: Limit,
+    clock: Box<dyn Clock>,
+    start: Instant,
+}
+
+impl Throttle {
+    pub fn new(limit: Limit) -> Self {
+        Throttle::with_clock(limit, Box::new(SystemClock))
+    }
+
+    pub fn with_clock(limit: Limit, clock: Box<dyn Clock>) -> Self {
+        let start = clock.now();
+        Throttle {
+            limit,
+            clock,
+            start,
+        }
+    }
+
+    // 已完成的量按限速本应花费的时间, 超前多少就睡多少
+    pub fn pace(&self, ops: usize, bytes: usize) {
+        let expected = match self.limit {
+            Limit::BytesPerSecond(rate) => bytes as f64 / rate,
+            Limit::OpsPerSecond(rate) => ops as f64 / rate,
+        };
+        let expected = Duration::from_secs_f64(expected);
+        let elapsed = self.clock.now().duration_since(self.start);
+        if expected > elapsed {
+            self.clock.sleep(expected - elapsed);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use crate::fs::RepoFs;
+use crate::gav::{self, VersionDir};
+use crate::purge::Coordinate;
+use crate::{duration, xattr};
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// 保留期限记录在版本目录的扩展属性中, 不支持扩展属性的文件系统改用目录下的旁路文件
+pub static XATTR_NAME: &str = "user.mrc.keep-until";
+
+pub static SIDECAR_FILE: &str = ".mrc-keep-until";
+
+static NEVER: &str = "never";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expiry {
+    Never,
+    // Unix 时间戳, 秒
+    At(u64),
+}
+
+impl Expiry {
+    // 超出可表示范围的期限视为永不过期
+    pub fn after(ttl: Duration) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        now.checked_add(ttl)
+            .map_or(Expiry::Never, |until| Expiry::At(until.as_secs()))
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        match text.trim() {
+            text if text == NEVER => Some(Expiry::Never),
+            text => text.parse().ok().map(Expiry::At),
+        }
+    }
+
+    // 尚未到期时返回剩余时间, 永不过期为 Duration::MAX
+    pub fn remaining(&self, now: SystemTime) -> Option<Duration> {
+        match self {
+            Expiry::Never => Some(Duration::MAX),
+            Expiry::At(seconds) => match UNIX_EPOCH.checked_add(Duration::from_secs(*seconds)) {
+                Some(until) => until
+                    .duration_since(now)
+                    .ok()
+                    .filter(|left| !left.is_zero()),
+                None => Some(Duration::MAX),
+            },
+        }
+    }
+
+    pub fn is_active(&self, now: SystemTime) -> bool {
+        self.remaining(now).is_some()
+    }
+}
+
+impl fmt::Display for Expiry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.remaining(SystemTime::now()) {
+            Some(Duration::MAX) => f.write_str("pinned indefinitely"),
+            Some(left) => write!(f, "pinned for another {}", duration::format(left)),
+            None => f.write_str("pin expired"),
+        }
+    }
+}
+
+fn encode(expiry: Expiry) -> String {
+    match expiry {
+        Expiry::Never => NEVER.to_string(),
+        Expiry::At(seconds) => seconds.to_string(),
+    }
+}
+
+// 扩展属性优先; 两者都没有或内容无效时返回 None
+pub fn read(fs: &dyn RepoFs, version_dir: &Path) -> Option<Expiry> {
+    let (text, source) = match fs.xattr(version_dir, XATTR_NAME) {
+        Ok(Some(value)) => (String::from_utf8_lossy(&value).into_owned(), XATTR_NAME),
+        _ => {
+            let sidecar = version_dir.join(SIDECAR_FILE);
+            if !fs.is_file(&sidecar) {
+                return None;
+            }
+            (fs.read_to_string(&sidecar).ok()?, SIDECAR_FILE)
+        }
+    };
+    let expiry = Expiry::parse(&text);
+    if expiry.is_none() {
+        log::warn!(
+            "Ignoring invalid {} on '{}': {}",
+            source,
+            version_dir.display(),
+            text.trim()
+        );
+    }
+    expiry
+}
+
+pub fn write(version_dir: &Path, expiry: Expiry) -> io::Result<()> {
+    let value = encode(expiry);
+    match xattr::set(version_dir, XATTR_NAME, value.as_bytes()) {
+        Ok(()) => remove_sidecar(version_dir),
+        Err(e) if e.kind() == io::ErrorKind::Unsupported => {
+            log::debug!(
+                "Extended attributes unsupported on '{}', using {}",
+                version_dir.display(),
+                SIDECAR_FILE
+            );
+            std::fs::write(version_dir.join(SIDECAR_FILE), value)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+pub fn clear(version_dir: &Path) -> io::Result<()> {
+    match xattr::remove(version_dir, XATTR_NAME) {
+        Err(e) if e.kind() != io::ErrorKind::Unsupported => return Err(e),
+        _ => {}
+    }
+    remove_sidecar(version_dir)
+}
+
+fn remove_sidecar(version_dir: &Path) -> io::Result<()> {
+    match std::fs::remove_file(version_dir.join(SIDECAR_FILE)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+// 同一版本目录的文件连续到达, 只需读取一次
+#[derive(Default)]
+pub struct ExpiryCache {
+    folder: Option<PathBuf>,
+    expiry: Option<Expiry>,
+}
+
+impl ExpiryCache {
+    pub fn active(&mut self, fs: &dyn RepoFs, path: &Path) -> Option<Expiry> {
+        let folder = path.parent()?;
+        if self.folder.as_deref() != Some(folder) {
+            self.expiry = read(fs, folder);
+            self.folder = Some(folder.to_path_buf());
+        }
+        self.expiry
+            .filter(|expiry| expiry.is_active(SystemTime::now()))
+    }
+}
+
+fn matching(repo_path: &Path, coordinate: &str) -> Result<Vec<VersionDir>, String> {
+    let coordinate = Coordinate::parse(coordinate)?;
+    Ok(gav::scan_versions(repo_path)
+        .into_iter()
+        .filter(|version| coordinate.matches(version))
+        .collect())
+}
+
+// 为匹配的每个版本目录设置或清除保留期限, ttl 为 None 表示永不过期
+pub fn pin(repo_path: &Path, coordinate: &str, ttl: Option<Duration>, unpin: bool) -> bool {
+    let versions = match matching(repo_path, coordinate) {
+        Ok(versions) => versions,
+        Err(e) => {
+            log::error!("Invalid coordinate: {}", e);
+            return false;
+        }
+    };
+    if versions.is_empty() {
+        log::error!("No versions match {}", coordinate);
+        return false;
+    }
+    let expiry = ttl.map_or(Expiry::Never, Expiry::after);
+    let mut success = true;
+    for version in versions {
+        let result = match unpin {
+            true => clear(&version.path),
+            false => write(&version.path, expiry),
+        };
+        match result {
+            Ok(()) if unpin => log::info!("Unpinned: {}", version.gav),
+            Ok(()) => log::info!("Pinned: {} ({})", version.gav, expiry),
+            Err(e) => {
+                log::error!("Failed to update '{}': {}", version.path.display(), e);
+                success = false;
+            }
+        }
+    }
+    success
+}
+
+// 列出所有带保留期限的版本目录, 包括已过期的
+pub fn list(repo_path: &Path, fs: &dyn RepoFs) {
+    let mut pinned = 0;
+    for version in gav::scan_versions(repo_path) {
+        if let Some(expiry) = read(fs, &version.path) {
+            println!("{}  {}", version.gav, expiry);
+            pinned += 1;
+        }
+    }
+    log::info!("Pinned versions: {}", pinned);
+}
@@ -0,0 +1,30 @@
+// NFC 规范化: APFS/HFS+ 返回的文件名可能是 NFD, 与 POM 或目录中的 NFC 名称比较前统一形式
+use std::borrow::Cow;
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+pub fn nfc(text: &str) -> Cow<'_, str> {
+    if is_nfc_quick(text.chars()) == IsNormalized::Yes {
+        return Cow::Borrowed(text);
+    }
+    let composed: String = text.nfc().collect();
+    if composed == text {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(composed)
+    }
+}
+
+// ignore_case 时逐字符比较小写形式
+pub fn strip_prefix<'a>(text: &'a str, prefix: &str, ignore_case: bool) -> Option<&'a str> {
+    if !ignore_case {
+        return text.strip_prefix(prefix);
+    }
+    let mut rest = text.chars();
+    for expected in prefix.chars() {
+        let actual = rest.next()?;
+        if actual != expected && !actual.to_lowercase().eq(expected.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(rest.as_str())
+}
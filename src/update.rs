@@ -0,0 +1,188 @@
+use crate::settings::Settings;
+use crate::{get_file_name, hash, version};
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+static RELEASES_URL: &str =
+    "https://api.github.com/repos/arsiac/maven-repo-cleaner/releases/latest";
+
+// 也接受 sha256sum 生成的汇总文件
+static CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+#[derive(Deserialize)]
+struct Release {
+    tag_name: String,
+    assets: Vec<Asset>,
+}
+
+#[derive(Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+// 发布的程序按平台命名, 如 mrc-x86_64-linux、mrc-aarch64-macos、mrc-x86_64-windows.exe,
+// 并附带同名的 .sha256 校验文件
+pub fn asset_name() -> String {
+    format!(
+        "mrc-{}-{}{}",
+        std::env::consts::ARCH,
+        std::env::consts::OS,
+        std::env::consts::EXE_SUFFIX
+    )
+}
+
+// 校验文件可以只有摘要, 也可以是 "摘要  文件名" 的多行格式
+pub fn parse_checksum(content: &str, asset: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let digest = fields.next()?;
+        match fields.next().map(|name| name.trim_start_matches('*')) {
+            Some(name) if name != asset => None,
+            _ if digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit()) => {
+                Some(digest.to_ascii_lowercase())
+            }
+            _ => None,
+        }
+    })
+}
+
+pub fn self_update(settings: &Settings, check: bool) -> bool {
+    let agent = match settings.agent(Duration::from_secs(300)) {
+        Ok(agent) => agent,
+        Err(e) => {
+            log::error!("Failed to check for updates: {}", e);
+            return false;
+        }
+    };
+    let release: Release = match get(&agent, RELEASES_URL)
+        .and_then(|body| serde_json::from_str(&body).map_err(|e| e.to_string()))
+    {
+        Ok(release) => release,
+        Err(e) => {
+            log::error!("Failed to check for updates: {}", e);
+            return false;
+        }
+    };
+    let current = env!("CARGO_PKG_VERSION");
+    let latest = release.tag_name.trim_start_matches('v');
+    if version::compare(latest, current) != Ordering::Greater {
+        log::info!("Already up to date: {}", current);
+        return true;
+    }
+    log::info!("New version available: {} -> {}", current, latest);
+    if check {
+        return true;
+    }
+
+    let name = asset_name();
+    let find = |name: &str| release.assets.iter().find(|asset| asset.name == name);
+    let binary = match find(&name) {
+        Some(binary) => binary,
+        None => {
+            log::error!(
+                "Release {} has no binary for this platform: {}",
+                latest,
+                name
+            );
+            return false;
+        }
+    };
+    let checksum = match find(&format!("{}.sha256", name)).or_else(|| find(CHECKSUMS_ASSET)) {
+        Some(checksum) => checksum,
+        None => {
+            log::error!("Release {} has no checksum for {}", latest, name);
+            return false;
+        }
+    };
+    let expected = match get(&agent, &checksum.browser_download_url) {
+        Ok(content) => parse_checksum(&content, &name),
+        Err(e) => {
+            log::error!("Failed to download '{}': {}", checksum.name, e);
+            return false;
+        }
+    };
+    let expected = match expected {
+        Some(expected) => expected,
+        None => {
+            log::error!("Release {} has no checksum for {}", latest, name);
+            return false;
+        }
+    };
+
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => {
+            log::error!("Failed to locate the running binary: {}", e);
+            return false;
+        }
+    };
+    // 下载到同一目录, 保证最后的重命名是原子的
+    let staged = exe.with_file_name(format!(
+        ".{}.update",
+        get_file_name(&exe).unwrap_or_else(|| "mrc".to_string())
+    ));
+    log::info!("Downloading: {}", binary.browser_download_url);
+    if let Err(e) = download(&agent, &binary.browser_download_url, &staged) {
+        log::error!("Failed to download '{}': {}", binary.name, e);
+        let _ = fs::remove_file(&staged);
+        return false;
+    }
+    match hash::sha256_file(&staged) {
+        Ok(actual) if actual == expected => {}
+        Ok(actual) => {
+            log::error!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                binary.name,
+                expected,
+                actual
+            );
+            let _ = fs::remove_file(&staged);
+            return false;
+        }
+        Err(e) => {
+            log::error!("Failed to read '{}': {}", staged.display(), e);
+            let _ = fs::remove_file(&staged);
+            return false;
+        }
+    }
+    if let Err(e) = replace(&exe, &staged) {
+        log::error!("Failed to replace '{}': {}", exe.display(), e);
+        let _ = fs::remove_file(&staged);
+        return false;
+    }
+    log::info!("Updated to {}", latest);
+    true
+}
+
+fn get(agent: &ureq::Agent, url: &str) -> Result<String, String> {
+    agent
+        .get(url)
+        .call()
+        .map_err(|e| e.to_string())?
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| e.to_string())
+}
+
+fn download(agent: &ureq::Agent, url: &str, path: &Path) -> Result<(), String> {
+    let response = agent.get(url).call().map_err(|e| e.to_string())?;
+    let mut file = File::create(path).map_err(|e| e.to_string())?;
+    io::copy(&mut response.into_body().into_reader(), &mut file).map_err(|e| e.to_string())?;
+    file.sync_all().map_err(|e| e.to_string())
+}
+
+// 沿用原程序的权限; Windows 不能覆盖正在运行的程序, 但可以先把它重命名
+fn replace(exe: &Path, staged: &Path) -> io::Result<()> {
+    fs::set_permissions(staged, fs::metadata(exe)?.permissions())?;
+    if cfg!(windows) {
+        let old = exe.with_extension("old");
+        let _ = fs::remove_file(&old);
+        fs::rename(exe, &old)?;
+    }
+    fs::rename(staged, exe)
+}
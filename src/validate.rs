@@ -0,0 +1,97 @@
+use crate::fs::{RealFs, RepoFs};
+use crate::{get_file_name, is_artifact, is_walkable_dir, layout, read_dir_sorted, unicode};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::PathBuf;
+
+pub fn validate(repo_path: PathBuf) -> bool {
+    let mut anomalies: usize = 0;
+    let ignore_case = RealFs.is_case_insensitive(&repo_path);
+    let mut queue = VecDeque::new();
+    queue.push_back(repo_path.clone());
+    while let Some(path) = queue.pop_front() {
+        log::debug!("Scanning: {}", path.display());
+        let folder = match read_dir_sorted(&path) {
+            Ok(folder) => folder,
+            Err(e) => {
+                log::error!("Failed to read directory '{}': {}", path.display(), e);
+                continue;
+            }
+        };
+
+        let mut files = Vec::new();
+        let mut has_sub_folder = false;
+        let mut names: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for entry in folder {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    log::error!("Failed to read directory entry: {:?}", e);
+                    continue;
+                }
+            };
+            let entry_path = entry.path();
+            let entry_name = match get_file_name(&entry_path) {
+                Some(name) => name,
+                None => continue,
+            };
+            names
+                .entry(entry_name.to_lowercase())
+                .or_default()
+                .push(entry_name.clone());
+            if is_walkable_dir(&RealFs, &entry_path) {
+                has_sub_folder = true;
+                queue.push_back(entry_path);
+            } else {
+                files.push(entry_name);
+            }
+        }
+
+        for (_, same_names) in names.iter().filter(|(_, v)| v.len() > 1) {
+            anomalies += 1;
+            log::warn!(
+                "Mixed-case duplicates in '{}': {}",
+                path.display(),
+                same_names.join(", ")
+            );
+        }
+
+        // 相对仓库根目录的层级: group/artifact/version
+        let depth = path
+            .strip_prefix(&repo_path)
+            .map(|p| p.components().count())
+            .unwrap_or(0);
+        let artifacts: Vec<&String> = files.iter().filter(|f| is_artifact(f)).collect();
+        if has_sub_folder || depth < 3 {
+            for artifact in artifacts {
+                anomalies += 1;
+                log::warn!("File at wrong depth: {}", path.join(artifact).display());
+            }
+            continue;
+        }
+
+        if artifacts.is_empty() {
+            anomalies += 1;
+            log::warn!("Version directory has no artifacts: {}", path.display());
+            continue;
+        }
+
+        let gav = match layout::gav(&repo_path, &path) {
+            Some(gav) => gav,
+            None => continue,
+        };
+        for artifact in artifacts {
+            if layout::parse_file_name(&gav, &unicode::nfc(artifact), ignore_case).is_none() {
+                anomalies += 1;
+                log::warn!(
+                    "Artifact does not match '{}:{}': {}",
+                    &gav.artifact_id,
+                    &gav.version,
+                    path.join(artifact).display()
+                );
+            }
+        }
+    }
+
+    log::info!("Anomalies found: {}", anomalies);
+    anomalies == 0
+}
@@ -0,0 +1,129 @@
+use crate::{hash, index, xattr};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// 校验结果记录在构件的扩展属性中, 不支持扩展属性的文件系统改用仓库根目录下的数据库
+pub static XATTR_NAME: &str = "user.mrc.verified";
+
+pub static VERIFIED_FILE: &str = ".mrc-verified.json";
+
+// 大小和修改时间 (纳秒) 都未变化时, 认为内容与上次计算摘要时相同
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Stamp {
+    size: u64,
+    mtime: u128,
+    sha1: String,
+    verified_at: u64,
+}
+
+pub struct VerifiedCache {
+    repo_path: PathBuf,
+    db: HashMap<String, Stamp>,
+    dirty: bool,
+    rehash: bool,
+    dry_run: bool,
+}
+
+impl VerifiedCache {
+    // rehash 时忽略已有记录, 重新计算后覆盖; dry_run 时只读取
+    pub fn open(repo_path: &Path, rehash: bool, dry_run: bool) -> Self {
+        let db = std::fs::read_to_string(repo_path.join(VERIFIED_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        VerifiedCache {
+            repo_path: repo_path.to_path_buf(),
+            db,
+            dirty: false,
+            rehash,
+            dry_run,
+        }
+    }
+
+    pub fn sha1(&mut self, path: &Path) -> io::Result<String> {
+        let metadata = std::fs::metadata(path)?;
+        let size = metadata.len();
+        let mtime = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let key = index::key(&self.repo_path, path);
+        if !self.rehash {
+            let stamp = match xattr::get(path, XATTR_NAME) {
+                Ok(Some(value)) => serde_json::from_slice(&value).ok(),
+                _ => self.db.get(&key).cloned(),
+            };
+            if let Some(stamp) =
+                stamp.filter(|stamp: &Stamp| stamp.size == size && stamp.mtime == mtime)
+            {
+                log::trace!("Verified before: {}", path.display());
+                return Ok(stamp.sha1);
+            }
+        }
+        let sha1 = hash::sha1_file(path)?;
+        if !self.dry_run {
+            let stamp = Stamp {
+                size,
+                mtime,
+                sha1: sha1.clone(),
+                verified_at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+            };
+            self.record(path, key, stamp);
+        }
+        Ok(sha1)
+    }
+
+    fn record(&mut self, path: &Path, key: String, stamp: Stamp) {
+        let value = match serde_json::to_vec(&stamp) {
+            Ok(value) => value,
+            Err(e) => {
+                log::error!("Failed to serialize verification: {}", e);
+                return;
+            }
+        };
+        match xattr::set(path, XATTR_NAME, &value) {
+            Ok(()) => {
+                self.dirty |= self.db.remove(&key).is_some();
+            }
+            Err(e) => {
+                log::debug!(
+                    "Recording verification of '{}' in {}: {}",
+                    path.display(),
+                    VERIFIED_FILE,
+                    e
+                );
+                self.db.insert(key, stamp);
+                self.dirty = true;
+            }
+        }
+    }
+
+    // 顺带丢弃已不存在的文件的记录
+    pub fn save(mut self) {
+        if !self.dirty || self.dry_run {
+            return;
+        }
+        let repo_path = &self.repo_path;
+        self.db.retain(|key, _| repo_path.join(key).is_file());
+        let path = repo_path.join(VERIFIED_FILE);
+        let result = match self.db.is_empty() {
+            true => std::fs::remove_file(&path).or_else(|e| match e.kind() {
+                io::ErrorKind::NotFound => Ok(()),
+                _ => Err(e),
+            }),
+            false => serde_json::to_string(&self.db)
+                .map_err(io::Error::other)
+                .and_then(|content| std::fs::write(&path, content)),
+        };
+        if let Err(e) = result {
+            log::error!("Failed to write '{}': {}", path.display(), e);
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+// Maven 版本比较的简化实现: 数字按数值比较, 限定符按已知顺序, 末尾的 0 和 release 类限定符忽略
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    Number(u64),
+    Qualifier(String),
+}
+
+static QUALIFIERS: [&str; 7] = ["alpha", "beta", "milestone", "rc", "snapshot", "", "sp"];
+
+fn qualifier_rank(qualifier: &str) -> (usize, &str) {
+    let qualifier = match qualifier {
+        "a" => "alpha",
+        "b" => "beta",
+        "m" => "milestone",
+        "cr" => "rc",
+        "ga" | "final" | "release" => "",
+        other => other,
+    };
+    match QUALIFIERS.iter().position(|known| *known == qualifier) {
+        Some(rank) => (rank, ""),
+        // 未知限定符排在已知限定符之后, 彼此按字典序
+        None => (QUALIFIERS.len(), qualifier),
+    }
+}
+
+fn items(version: &str) -> Vec<Item> {
+    let version = version.to_lowercase();
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut digits = false;
+    let mut push = |current: &mut String, digits: bool| {
+        if current.is_empty() {
+            return;
+        }
+        items.push(match digits {
+            true => Item::Number(current.parse().unwrap_or(u64::MAX)),
+            false => Item::Qualifier(std::mem::take(current)),
+        });
+        current.clear();
+    };
+    for c in version.chars() {
+        if c == '.' || c == '-' || c == '_' {
+            push(&mut current, digits);
+            continue;
+        }
+        // 数字和字母之间的切换也视为分隔, 如 1.0alpha1
+        if !current.is_empty() && c.is_ascii_digit() != digits {
+            push(&mut current, digits);
+        }
+        digits = c.is_ascii_digit();
+        current.push(c);
+    }
+    push(&mut current, digits);
+    // 末尾的 0 和 release 类限定符不影响顺序: 1.0 == 1 == 1-ga
+    while items.last().is_some_and(|item| match item {
+        Item::Number(number) => *number == 0,
+        Item::Qualifier(qualifier) => qualifier_rank(qualifier).0 == 5,
+    }) {
+        items.pop();
+    }
+    items
+}
+
+fn compare_item(a: Option<&Item>, b: Option<&Item>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (Some(Item::Number(a)), Some(Item::Number(b))) => a.cmp(b),
+        (Some(Item::Number(a)), None) => a.cmp(&0),
+        (None, Some(Item::Number(b))) => 0.cmp(b),
+        (Some(Item::Number(_)), Some(Item::Qualifier(_))) => Ordering::Greater,
+        (Some(Item::Qualifier(_)), Some(Item::Number(_))) => Ordering::Less,
+        (Some(Item::Qualifier(a)), Some(Item::Qualifier(b))) => {
+            qualifier_rank(a).cmp(&qualifier_rank(b))
+        }
+        (Some(Item::Qualifier(a)), None) => qualifier_rank(a).cmp(&qualifier_rank("")),
+        (None, Some(Item::Qualifier(b))) => qualifier_rank("").cmp(&qualifier_rank(b)),
+    }
+}
+
+pub fn compare(a: &str, b: &str) -> Ordering {
+    let (a, b) = (items(a), items(b));
+    for index in 0..a.len().max(b.len()) {
+        match compare_item(a.get(index), b.get(index)) {
+            Ordering::Equal => continue,
+            ordering => return ordering,
+        }
+    }
+    Ordering::Equal
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bound {
+    version: String,
+    inclusive: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Restriction {
+    lower: Option<Bound>,
+    upper: Option<Bound>,
+}
+
+impl Restriction {
+    fn contains(&self, version: &str) -> bool {
+        let above =
+            self.lower
+                .as_ref()
+                .is_none_or(|lower| match compare(version, &lower.version) {
+                    Ordering::Greater => true,
+                    Ordering::Equal => lower.inclusive,
+                    Ordering::Less => false,
+                });
+        let below =
+            self.upper
+                .as_ref()
+                .is_none_or(|upper| match compare(version, &upper.version) {
+                    Ordering::Less => true,
+                    Ordering::Equal => upper.inclusive,
+                    Ordering::Greater => false,
+                });
+        above && below
+    }
+}
+
+// Maven 版本范围: [1.0,2.0), (,1.5], [1.2], [1,2),[3,) 等; 单独的版本号视为精确匹配
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRange {
+    text: String,
+    restrictions: Vec<Restriction>,
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl VersionRange {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let mut restrictions = Vec::new();
+        let mut rest = text;
+        if !rest.starts_with(['[', '(']) {
+            if rest.is_empty() || rest.contains([',', '[', ']', '(', ')']) {
+                return Err(format!("invalid version range: {}", text));
+            }
+            let bound = Bound {
+                version: rest.to_string(),
+                inclusive: true,
+            };
+            restrictions.push(Restriction {
+                lower: Some(bound.clone()),
+                upper: Some(bound),
+            });
+            rest = "";
+        }
+        while !rest.is_empty() {
+            let lower_inclusive = match rest.chars().next() {
+                Some('[') => true,
+                Some('(') => false,
+                _ => return Err(format!("expected '[' or '(' in version range: {}", text)),
+            };
+            let end = rest
+                .find([']', ')'])
+                .ok_or_else(|| format!("unclosed version range: {}", text))?;
+            let upper_inclusive = rest[end..].starts_with(']');
+            let inner = rest[1..end].trim();
+            let restriction = match inner.split_once(',') {
+                Some((lower, upper)) => {
+                    let bound = |version: &str, inclusive| {
+                        let version = version.trim();
+                        (!version.is_empty()).then(|| Bound {
+                            version: version.to_string(),
+                            inclusive,
+                        })
+                    };
+                    Restriction {
+                        lower: bound(lower, lower_inclusive),
+                        upper: bound(upper, upper_inclusive),
+                    }
+                }
+                // [1.0] 只匹配 1.0
+                None if lower_inclusive && upper_inclusive && !inner.is_empty() => {
+                    let bound = Bound {
+                        version: inner.to_string(),
+                        inclusive: true,
+                    };
+                    Restriction {
+                        lower: Some(bound.clone()),
+                        upper: Some(bound),
+                    }
+                }
+                None => return Err(format!("invalid version range: {}", text)),
+            };
+            restrictions.push(restriction);
+            rest = rest[end + 1..].trim_start();
+            rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+        }
+        Ok(VersionRange {
+            text: text.to_string(),
+            restrictions,
+        })
+    }
+
+    pub fn contains(&self, version: &str) -> bool {
+        self.restrictions
+            .iter()
+            .any(|restriction| restriction.contains(version))
+    }
+}
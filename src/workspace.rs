@@ -0,0 +1,398 @@
+use crate::fs::RealFs;
+use crate::gav::Gav;
+use crate::xml::{child, text};
+use crate::{get_file_name, is_walkable_dir, layout, read_dir_sorted};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+static POM_FILE: &str = "pom.xml";
+
+static GRADLE_FILES: [&str; 2] = ["build.gradle", "build.gradle.kts"];
+
+// 构建输出和工具目录中的 pom.xml 不是项目本身
+static SKIPPED_DIRS: [&str; 4] = ["target", "build", "node_modules", "out"];
+
+// 带坐标的 POM 元素; 省略 groupId 的插件属于 org.apache.maven.plugins
+static COORDINATE_ELEMENTS: [&str; 4] = ["parent", "dependency", "plugin", "extension"];
+
+static DEFAULT_PLUGIN_GROUP: &str = "org.apache.maven.plugins";
+
+// 其中的坐标为子 POM 提供省略的版本
+static MANAGEMENT_ELEMENTS: [&str; 2] = ["dependencyManagement", "pluginManagement"];
+
+static DEFAULT_PARENT_PATH: &str = "../pom.xml";
+
+// 防止属性互相引用或父 POM 形成环
+const MAX_INTERPOLATION: usize = 16;
+
+const MAX_LINEAGE: usize = 16;
+
+// 工作区中的项目声明的依赖, 这些版本在清理时始终保留
+#[derive(Debug, Default, Clone)]
+pub struct KeepSet(Arc<BTreeSet<Gav>>);
+
+impl KeepSet {
+    pub fn new(gavs: impl IntoIterator<Item = Gav>) -> Self {
+        KeepSet(Arc::new(gavs.into_iter().collect()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, gav: &Gav) -> bool {
+        self.0.contains(gav)
+    }
+
+    // 文件所在的版本目录是否被工作区引用
+    pub fn is_kept(&self, repo_path: &Path, path: &Path) -> bool {
+        !self.is_empty()
+            && path
+                .parent()
+                .and_then(|dir| layout::gav(repo_path, dir))
+                .is_some_and(|gav| self.contains(&gav))
+    }
+}
+
+// 递归查找 pom.xml 和 build.gradle(.kts), 跳过隐藏目录和构建输出.
+// POM 全部读取后再解析版本, 以便使用工作区中父 POM 的属性和 dependencyManagement
+pub fn scan(roots: &[PathBuf]) -> Result<KeepSet, String> {
+    let mut gavs = Vec::new();
+    let mut projects = Vec::new();
+    let mut queue = VecDeque::new();
+    for root in roots {
+        if !root.is_dir() {
+            return Err(format!("not a directory: {}", root.display()));
+        }
+        queue.push_back(root.clone());
+    }
+    while let Some(path) = queue.pop_front() {
+        let folder = match read_dir_sorted(&path) {
+            Ok(folder) => folder,
+            Err(e) => {
+                log::error!("Failed to read directory '{}': {}", path.display(), e);
+                continue;
+            }
+        };
+        for entry in folder.flatten() {
+            let entry_path = entry.path();
+            let name = match get_file_name(&entry_path) {
+                Some(name) => name,
+                None => continue,
+            };
+            if is_walkable_dir(&RealFs, &entry_path) {
+                if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str()) {
+                    queue.push_back(entry_path);
+                }
+                continue;
+            }
+            if name != POM_FILE && !GRADLE_FILES.contains(&name.as_str()) {
+                continue;
+            }
+            log::debug!("Reading build file: {}", entry_path.display());
+            let content = std::fs::read_to_string(&entry_path).map_err(|e| e.to_string());
+            if name != POM_FILE {
+                match content {
+                    Ok(content) => gavs.extend(parse_gradle(&content)),
+                    Err(e) => log::warn!("Skipping build file '{}': {}", entry_path.display(), e),
+                }
+                continue;
+            }
+            let path = std::fs::canonicalize(&entry_path).unwrap_or(entry_path);
+            match content.and_then(|content| Project::parse(&content, path.clone())) {
+                Ok(project) => projects.push(project),
+                Err(e) => log::warn!("Skipping build file '{}': {}", path.display(), e),
+            }
+        }
+    }
+    gavs.extend(resolve(&projects));
+    Ok(KeepSet::new(gavs))
+}
+
+// 单个 POM 的坐标, 只能使用其自身定义的属性和 dependencyManagement
+pub fn parse_pom(content: &str) -> Result<Vec<Gav>, String> {
+    Ok(resolve(&[Project::parse(
+        content,
+        PathBuf::from(POM_FILE),
+    )?]))
+}
+
+// 未解析的坐标, 各部分可能包含 ${...}; 版本可能由 dependencyManagement 提供
+#[derive(Debug, Clone)]
+struct Declared {
+    group_id: String,
+    artifact_id: String,
+    version: Option<String>,
+}
+
+#[derive(Debug)]
+struct Project {
+    path: PathBuf,
+    group_id: Option<String>,
+    artifact_id: String,
+    version: Option<String>,
+    parent: Option<Declared>,
+    // None 表示 <relativePath/>, 父 POM 只从仓库获取
+    parent_path: Option<PathBuf>,
+    properties: HashMap<String, String>,
+    managed: Vec<Declared>,
+    declared: Vec<Declared>,
+}
+
+impl Project {
+    fn parse(content: &str, path: PathBuf) -> Result<Self, String> {
+        let document = roxmltree::Document::parse(content).map_err(|e| e.to_string())?;
+        let project = document.root_element();
+        let parent = child(project, "parent");
+        let parent_path = match parent.and_then(|parent| child(parent, "relativePath")) {
+            Some(relative) if text(relative).is_empty() => None,
+            Some(relative) => Some(text(relative)),
+            None => Some(DEFAULT_PARENT_PATH.to_string()),
+        }
+        .and_then(|relative| {
+            let parent_path = path.parent()?.join(relative);
+            let parent_path = match parent_path.is_dir() {
+                true => parent_path.join(POM_FILE),
+                false => parent_path,
+            };
+            Some(std::fs::canonicalize(&parent_path).unwrap_or(parent_path))
+        });
+        let properties = child(project, "properties")
+            .map(|properties| {
+                properties
+                    .children()
+                    .filter(|node| node.is_element())
+                    .map(|node| (node.tag_name().name().to_string(), text(node)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let coordinates = |node: roxmltree::Node| {
+            let group_id = match field(node, "groupId") {
+                Some(group_id) => group_id,
+                None if node.tag_name().name() == "plugin" => DEFAULT_PLUGIN_GROUP.to_string(),
+                None => return None,
+            };
+            Some(Declared {
+                group_id,
+                artifact_id: field(node, "artifactId")?,
+                version: field(node, "version"),
+            })
+        };
+        let mut managed = Vec::new();
+        let mut declared = Vec::new();
+        for node in project.descendants().filter(|node| {
+            node.is_element() && COORDINATE_ELEMENTS.contains(&node.tag_name().name())
+        }) {
+            let in_management = node
+                .ancestors()
+                .any(|ancestor| MANAGEMENT_ELEMENTS.contains(&ancestor.tag_name().name()));
+            match coordinates(node) {
+                Some(coordinates) if in_management => managed.push(coordinates),
+                Some(coordinates) => declared.push(coordinates),
+                None => {}
+            }
+        }
+        Ok(Project {
+            path,
+            group_id: field(project, "groupId"),
+            artifact_id: field(project, "artifactId").unwrap_or_default(),
+            version: field(project, "version"),
+            parent: parent.and_then(coordinates),
+            parent_path,
+            properties,
+            managed,
+            declared,
+        })
+    }
+
+    fn parent_group(&self) -> Option<&String> {
+        self.parent.as_ref().map(|parent| &parent.group_id)
+    }
+}
+
+fn field(node: roxmltree::Node, name: &str) -> Option<String> {
+    child(node, name).map(text)
+}
+
+// 按 Maven 的继承规则合并父 POM: 子 POM 的属性和 dependencyManagement 优先.
+// 父 POM 先按 relativePath 查找, 再按坐标在工作区中查找
+fn resolve(projects: &[Project]) -> Vec<Gav> {
+    let by_path: HashMap<&Path, &Project> = projects
+        .iter()
+        .map(|project| (project.path.as_path(), project))
+        .collect();
+    let find_parent = |project: &Project| {
+        let parent = project.parent.as_ref()?;
+        project
+            .parent_path
+            .as_deref()
+            .and_then(|path| by_path.get(path).copied())
+            .filter(|found| found.artifact_id == parent.artifact_id)
+            .or_else(|| {
+                projects.iter().find(|found| {
+                    found.artifact_id == parent.artifact_id
+                        && found.group_id.as_ref().or(found.parent_group())
+                            == Some(&parent.group_id)
+                })
+            })
+    };
+    let mut gavs = Vec::new();
+    for project in projects {
+        let mut lineage = vec![project];
+        while let Some(parent) = lineage.last().and_then(|last| find_parent(last)) {
+            if lineage.len() >= MAX_LINEAGE || lineage.iter().any(|p| std::ptr::eq(*p, parent)) {
+                break;
+            }
+            lineage.push(parent);
+        }
+        let mut properties = HashMap::new();
+        for ancestor in lineage.iter().rev() {
+            properties.extend(ancestor.properties.clone());
+        }
+        let group_id = project.group_id.as_ref().or(project.parent_group());
+        let version = project.version.as_ref().or(project
+            .parent
+            .as_ref()
+            .and_then(|parent| parent.version.as_ref()));
+        let builtins = [
+            ("project.groupId", group_id),
+            ("project.artifactId", Some(&project.artifact_id)),
+            ("project.version", version),
+            ("pom.version", version),
+            ("version", version),
+            (
+                "project.parent.version",
+                project
+                    .parent
+                    .as_ref()
+                    .and_then(|parent| parent.version.as_ref()),
+            ),
+        ];
+        for (name, value) in builtins {
+            if let Some(value) = value {
+                properties.entry(name.to_string()).or_insert(value.clone());
+            }
+        }
+        let managed: Vec<&Declared> = lineage.iter().flat_map(|p| &p.managed).collect();
+        let interpolate = |text: &str| interpolate(text, &properties);
+        let mut resolved = |declared: &Declared| {
+            let group_id = interpolate(&declared.group_id)?;
+            let artifact_id = interpolate(&declared.artifact_id)?;
+            let version = match &declared.version {
+                Some(version) => version.clone(),
+                None => managed
+                    .iter()
+                    .find(|managed| {
+                        interpolate(&managed.group_id).as_ref() == Some(&group_id)
+                            && interpolate(&managed.artifact_id).as_ref() == Some(&artifact_id)
+                    })?
+                    .version
+                    .clone()?,
+            };
+            gavs.extend(literal(group_id, artifact_id, interpolate(&version)?));
+            Some(())
+        };
+        if let (Some(group_id), Some(version)) = (group_id, version) {
+            resolved(&Declared {
+                group_id: group_id.clone(),
+                artifact_id: project.artifact_id.clone(),
+                version: Some(version.clone()),
+            });
+        }
+        for declared in project.declared.iter().chain(&project.managed) {
+            resolved(declared);
+        }
+    }
+    gavs
+}
+
+// 逐层替换 ${name}, 属性可以引用其他属性; 未定义的属性返回 None
+fn interpolate(text: &str, properties: &HashMap<String, String>) -> Option<String> {
+    let mut text = text.to_string();
+    for _ in 0..MAX_INTERPOLATION {
+        let start = match text.find("${") {
+            Some(start) => start,
+            None => return Some(text),
+        };
+        let end = start + text[start..].find('}')?;
+        let value = properties.get(&text[start + 2..end])?;
+        text.replace_range(start..=end, value);
+    }
+    None
+}
+
+fn literal(group_id: String, artifact_id: String, version: String) -> Option<Gav> {
+    (!group_id.contains("${") && !artifact_id.contains("${") && is_literal_version(&version))
+        .then_some(Gav {
+            group_id,
+            artifact_id,
+            version,
+        })
+}
+
+// 区间、动态版本 (1.+, latest.release) 和插值都不是具体版本
+fn is_literal_version(version: &str) -> bool {
+    !version.is_empty()
+        && !version.starts_with("latest.")
+        && !version.contains(['$', '+', '[', ']', '(', ')', ',', ' '])
+}
+
+// 字符串字面量形式的坐标: "group:artifact:version[:classifier][@extension]"
+pub fn parse_gradle(content: &str) -> Vec<Gav> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with("//") && !line.starts_with('*'))
+        .flat_map(string_literals)
+        .filter_map(parse_coordinate)
+        .collect()
+}
+
+fn string_literals(line: &str) -> Vec<&str> {
+    let mut literals = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find(['"', '\'']) {
+        let quote = rest[start..].chars().next().unwrap_or('"');
+        let after = &rest[start + 1..];
+        match after.find(quote) {
+            Some(end) => {
+                literals.push(&after[..end]);
+                rest = &after[end + 1..];
+            }
+            None => break,
+        }
+    }
+    literals
+}
+
+fn parse_coordinate(text: &str) -> Option<Gav> {
+    let text = text
+        .split_once('@')
+        .map_or(text, |(coordinate, _)| coordinate);
+    let parts: Vec<&str> = text.split(':').collect();
+    let (group_id, artifact_id, version) = match parts[..] {
+        [group_id, artifact_id, version] | [group_id, artifact_id, version, _] => {
+            (group_id, artifact_id, version)
+        }
+        _ => return None,
+    };
+    let is_name = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+    };
+    if !is_name(group_id) || !is_name(artifact_id) {
+        return None;
+    }
+    literal(
+        group_id.to_string(),
+        artifact_id.to_string(),
+        version.to_string(),
+    )
+}
@@ -0,0 +1,174 @@
+use std::io;
+use std::path::Path;
+
+// 扩展属性; 平台或文件系统不支持时返回 Unsupported, 调用方改用旁路文件
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod imp {
+    use std::ffi::CString;
+    use std::io;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    fn c_path(path: &Path) -> io::Result<CString> {
+        CString::new(path.as_os_str().as_bytes())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    }
+
+    fn c_name(name: &str) -> io::Result<CString> {
+        CString::new(name).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    }
+
+    // ENOTSUP 统一为 Unsupported, 属性不存在 (ENODATA/ENOATTR) 返回 None
+    fn error() -> io::Error {
+        let e = io::Error::last_os_error();
+        match e.raw_os_error() {
+            Some(libc::ENOTSUP) => io::Error::new(io::ErrorKind::Unsupported, e),
+            _ => e,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    const NO_ATTRIBUTE: i32 = libc::ENODATA;
+    #[cfg(target_os = "macos")]
+    const NO_ATTRIBUTE: i32 = libc::ENOATTR;
+
+    #[cfg(target_os = "linux")]
+    unsafe fn getxattr(path: &CString, name: &CString, buf: &mut [u8]) -> isize {
+        libc::getxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            buf.as_mut_ptr().cast(),
+            buf.len(),
+        )
+    }
+
+    #[cfg(target_os = "macos")]
+    unsafe fn getxattr(path: &CString, name: &CString, buf: &mut [u8]) -> isize {
+        libc::getxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            buf.as_mut_ptr().cast(),
+            buf.len(),
+            0,
+            0,
+        )
+    }
+
+    #[cfg(target_os = "linux")]
+    unsafe fn setxattr(path: &CString, name: &CString, value: &[u8]) -> i32 {
+        libc::setxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            value.as_ptr().cast(),
+            value.len(),
+            0,
+        )
+    }
+
+    #[cfg(target_os = "macos")]
+    unsafe fn setxattr(path: &CString, name: &CString, value: &[u8]) -> i32 {
+        libc::setxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            value.as_ptr().cast(),
+            value.len(),
+            0,
+            0,
+        )
+    }
+
+    #[cfg(target_os = "linux")]
+    unsafe fn removexattr(path: &CString, name: &CString) -> i32 {
+        libc::removexattr(path.as_ptr(), name.as_ptr())
+    }
+
+    #[cfg(target_os = "macos")]
+    unsafe fn removexattr(path: &CString, name: &CString) -> i32 {
+        libc::removexattr(path.as_ptr(), name.as_ptr(), 0)
+    }
+
+    pub fn get(path: &Path, name: &str) -> io::Result<Option<Vec<u8>>> {
+        let (path, name) = (c_path(path)?, c_name(name)?);
+        // 先查询长度; 两次调用之间属性可能被改写, 此时重试
+        loop {
+            let len = unsafe { getxattr(&path, &name, &mut []) };
+            if len < 0 {
+                let e = error();
+                return match e.raw_os_error() {
+                    Some(NO_ATTRIBUTE) => Ok(None),
+                    _ => Err(e),
+                };
+            }
+            let mut value = vec![0; len as usize];
+            let read = unsafe { getxattr(&path, &name, &mut value) };
+            if read >= 0 {
+                value.truncate(read as usize);
+                return Ok(Some(value));
+            }
+            let e = error();
+            match e.raw_os_error() {
+                Some(libc::ERANGE) => continue,
+                Some(NO_ATTRIBUTE) => return Ok(None),
+                _ => return Err(e),
+            }
+        }
+    }
+
+    pub fn set(path: &Path, name: &str, value: &[u8]) -> io::Result<()> {
+        let (path, name) = (c_path(path)?, c_name(name)?);
+        match unsafe { setxattr(&path, &name, value) } {
+            0 => Ok(()),
+            _ => Err(error()),
+        }
+    }
+
+    pub fn remove(path: &Path, name: &str) -> io::Result<()> {
+        let (path, name) = (c_path(path)?, c_name(name)?);
+        if unsafe { removexattr(&path, &name) } == 0 {
+            return Ok(());
+        }
+        let e = error();
+        match e.raw_os_error() {
+            Some(NO_ATTRIBUTE) => Ok(()),
+            _ => Err(e),
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+mod imp {
+    use std::io;
+    use std::path::Path;
+
+    fn unsupported() -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            "extended attributes are not supported on this platform",
+        )
+    }
+
+    pub fn get(_path: &Path, _name: &str) -> io::Result<Option<Vec<u8>>> {
+        Err(unsupported())
+    }
+
+    pub fn set(_path: &Path, _name: &str, _value: &[u8]) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    pub fn remove(_path: &Path, _name: &str) -> io::Result<()> {
+        Err(unsupported())
+    }
+}
+
+pub fn get(path: &Path, name: &str) -> io::Result<Option<Vec<u8>>> {
+    imp::get(path, name)
+}
+
+pub fn set(path: &Path, name: &str, value: &[u8]) -> io::Result<()> {
+    imp::set(path, name, value)
+}
+
+// 属性不存在不算错误
+pub fn remove(path: &Path, name: &str) -> io::Result<()> {
+    imp::remove(path, name)
+}
@@ -0,0 +1,74 @@
+use mrc::compress;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+fn repo_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mrc-compress-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+#[cfg(unix)]
+#[test]
+fn compression_round_trip_keeps_permissions_and_times() {
+    use std::os::unix::fs::PermissionsExt;
+    let repo_path = repo_dir("attributes");
+    let pom = repo_path.join("com/foo/bar/1.0/bar-1.0.pom");
+    fs::create_dir_all(pom.parent().unwrap()).unwrap();
+    fs::write(&pom, "<project></project>\n".repeat(512)).unwrap();
+    let last_used = SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+    fs::File::options()
+        .write(true)
+        .open(&pom)
+        .unwrap()
+        .set_times(
+            fs::FileTimes::new()
+                .set_accessed(last_used)
+                .set_modified(last_used),
+        )
+        .unwrap();
+    fs::set_permissions(&pom, fs::Permissions::from_mode(0o640)).unwrap();
+    let attributes = |path: &PathBuf| {
+        let metadata = fs::metadata(path).unwrap();
+        (
+            metadata.permissions().mode() & 0o7777,
+            metadata.modified().unwrap(),
+            metadata.accessed().unwrap(),
+        )
+    };
+    let expected = (0o640, last_used, last_used);
+
+    compress::compress_older_than(&repo_path, Duration::from_secs(24 * 60 * 60));
+    let compressed = repo_path.join("com/foo/bar/1.0/bar-1.0.pom.mrc.zst");
+    assert!(!pom.exists());
+    assert_eq!(attributes(&compressed), expected);
+
+    compress::restore(&repo_path);
+    assert!(!compressed.exists());
+    assert_eq!(attributes(&pom), expected);
+}
+
+#[test]
+fn ages_beyond_the_epoch_compress_nothing() {
+    assert!(mrc::duration::parse("999999999999y").is_err());
+    let age = mrc::duration::parse("999999999y").unwrap();
+    let repo_path = repo_dir("age");
+    let pom = repo_path.join("com/foo/bar/1.0/bar-1.0.pom");
+    fs::create_dir_all(pom.parent().unwrap()).unwrap();
+    fs::write(&pom, "<project/>").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&pom)
+        .unwrap()
+        .set_times(
+            fs::FileTimes::new()
+                .set_accessed(SystemTime::UNIX_EPOCH)
+                .set_modified(SystemTime::UNIX_EPOCH),
+        )
+        .unwrap();
+    compress::compress_older_than(&repo_path, age);
+    assert!(pom.is_file());
+    fs::remove_dir_all(&repo_path).unwrap();
+}
@@ -0,0 +1,900 @@
+use mrc::fs::FsHandle;
+use mrc::memory_fs::MemoryFs;
+use mrc::remove::{self, Candidate};
+use mrc::summary::Reason;
+use mrc::{config, gav, policy, workspace};
+use mrc::{Cleaner, CleanupOptions};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+
+static REPO: &str = "/repo";
+
+fn snapshot_repo() -> MemoryFs {
+    MemoryFs::new()
+        .file("/repo/com/foo/bar/maven-metadata-local.xml", "<metadata/>")
+        .file("/repo/com/foo/bar/1.0/bar-1.0.jar", "release")
+        .file("/repo/com/foo/bar/1.0/bar-1.0.pom", "<project/>")
+        .file(
+            "/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-20230101.120000-1.jar",
+            "old",
+        )
+        .file(
+            "/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-20230101.120000-1.jar.sha1",
+            "da39a3ee",
+        )
+        .file(
+            "/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-20240101.120000-2.pom",
+            "<project/>",
+        )
+        .file(
+            "/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-SNAPSHOT.jar",
+            "latest",
+        )
+        .file(
+            "/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-SNAPSHOT.pom",
+            "<project/>",
+        )
+        .file(
+            "/repo/com/foo/bar/1.1-SNAPSHOT/maven-metadata-local.xml",
+            "<metadata/>",
+        )
+}
+
+fn decommissioned_repo() -> MemoryFs {
+    MemoryFs::new()
+        .file("/repo/org/x/y/1.0/y-1.0.jar", "jar")
+        .file("/repo/org/x/y/1.0/y-1.0.jar.sha1", "sha1")
+        .file("/repo/org/x/y/1.0/y-1.0.pom", "<project/>")
+        .file(
+            "/repo/org/x/y/1.0/_remote.repositories",
+            "y-1.0.jar>old-nexus=\ny-1.0.pom>central=\n",
+        )
+        .file("/repo/org/x/z/2.0/z-2.0.jar", "jar")
+        .file("/repo/org/x/z/2.0/z-2.0.pom", "<project/>")
+        .file(
+            "/repo/org/x/z/2.0/_remote.repositories",
+            "#NOTE: generated\nz-2.0.jar>old-nexus=\nz-2.0.pom>old-nexus=\n",
+        )
+        .file("/repo/org/x/w/3.0/w-3.0.jar", "jar")
+        .file(
+            "/repo/org/x/w/3.0/_remote.repositories",
+            "w-3.0.jar>central=\n",
+        )
+}
+
+fn scan(fs: MemoryFs, options: CleanupOptions) -> Vec<Candidate> {
+    let options = CleanupOptions {
+        fs: FsHandle::new(fs),
+        ..options
+    };
+    Cleaner::new(PathBuf::from(REPO), options).scan()
+}
+
+fn render(candidates: &[Candidate]) -> String {
+    candidates
+        .iter()
+        .map(|candidate| {
+            format!(
+                "{:<26} {:<14} {}",
+                candidate.reason,
+                format!("{:?}", candidate.action),
+                relative(&candidate.path)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_files(fs: &MemoryFs) -> String {
+    fs.files()
+        .iter()
+        .map(|path| relative(path))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn relative(path: &Path) -> String {
+    path.strip_prefix(REPO).unwrap().display().to_string()
+}
+
+#[test]
+fn stale_snapshots_and_local_metadata() {
+    let candidates = scan(snapshot_repo(), CleanupOptions::default());
+    insta::assert_snapshot!(render(&candidates));
+}
+
+#[test]
+fn candidates_carry_coordinates_and_size() {
+    let candidates = scan(snapshot_repo(), CleanupOptions::default());
+    let stale = &candidates[1];
+    assert_eq!(
+        stale.gav.as_ref().map(|gav| gav.to_string()).as_deref(),
+        Some("com.foo:bar:1.1-SNAPSHOT")
+    );
+    assert_eq!(stale.size, 3);
+}
+
+#[test]
+fn reason_filters_limit_candidates() {
+    let only = scan(
+        snapshot_repo(),
+        CleanupOptions {
+            reasons: policy::ReasonFilter {
+                only: vec![Reason::LocalMetadata],
+                skip: Vec::new(),
+            },
+            ..Default::default()
+        },
+    );
+    assert_eq!(only.len(), 2);
+    assert!(only.iter().all(|c| c.reason == Reason::LocalMetadata));
+
+    let skipped = scan(
+        snapshot_repo(),
+        CleanupOptions {
+            reasons: policy::ReasonFilter {
+                only: Vec::new(),
+                skip: vec![Reason::LocalMetadata],
+            },
+            ..Default::default()
+        },
+    );
+    assert_eq!(skipped.len(), 3);
+    assert!(skipped.iter().all(|c| c.reason == Reason::StaleSnapshot));
+}
+
+#[test]
+fn clean_keeps_releases_and_latest_snapshot() {
+    let fs = Arc::new(snapshot_repo());
+    let options = CleanupOptions {
+        fs: FsHandle::from(fs.clone()),
+        ..Default::default()
+    };
+    let cleaner = Cleaner::new(PathBuf::from(REPO), options);
+    let summary = cleaner.clean(cleaner.scan());
+    assert_eq!(summary.files_deleted, 5);
+    insta::assert_snapshot!(render_files(&fs));
+}
+
+#[test]
+fn scan_statistics() {
+    let options = CleanupOptions {
+        fs: FsHandle::new(snapshot_repo()),
+        ..Default::default()
+    };
+    let summary = mrc::cleanup(PathBuf::from(REPO), &options, None);
+    assert_eq!(summary.stats.dirs_visited, 6);
+    assert_eq!(summary.stats.files_examined, 9);
+    assert_eq!(summary.files_deleted, 5);
+}
+
+#[test]
+fn decommissioned_repositories() {
+    let candidates = scan(
+        decommissioned_repo(),
+        CleanupOptions {
+            known_repositories: Some(vec!["central".to_string()]),
+            ..Default::default()
+        },
+    );
+    insta::assert_snapshot!(render(&candidates));
+}
+
+#[test]
+fn from_repo_limits_snapshot_cleanup() {
+    let fs = snapshot_repo().file(
+        "/repo/com/foo/bar/1.1-SNAPSHOT/_remote.repositories",
+        "bar-1.1-20230101.120000-1.jar>snapshots=\nbar-1.1-20240101.120000-2.pom>other=\n",
+    );
+    let candidates = scan(
+        fs,
+        CleanupOptions {
+            from_repo: Some("snapshots".to_string()),
+            ..Default::default()
+        },
+    );
+    insta::assert_snapshot!(render(&candidates));
+}
+
+#[test]
+fn policy_rules_by_priority() {
+    let fs = snapshot_repo()
+        .file("/repo/com/foo/bar/1.1/bar-1.1.jar", "release")
+        .file("/repo/com/foo/bar/1.1/bar-1.1-sources.jar", "sources")
+        .file("/repo/com/foo/bar/2.0/bar-2.0.jar", "release")
+        .file("/repo/com/foo/bar/2.0/bar-2.0-javadoc.jar", "javadoc")
+        .file("/repo/org/keep/lib/1.0/lib-1.0.jar", "release");
+    let policies = policy::parse(
+        r#"
+        [[policy]]
+        name = "custom"
+
+        [[policy.rule]]
+        name = "pinned"
+        priority = 10
+        group = "org.keep*"
+        action = "keep"
+
+        [[policy.rule]]
+        name = "docs"
+        classifier = ["sources", "javadoc"]
+        action = "delete"
+
+        [[policy.rule]]
+        name = "old-releases"
+        snapshot = false
+        type = "jar"
+        action = "keep-latest"
+        count = 1
+        "#,
+    )
+    .unwrap();
+    let candidates = scan(
+        fs,
+        CleanupOptions {
+            rules: policies[0].rules(),
+            ..Default::default()
+        },
+    );
+    insta::assert_snapshot!(render(&candidates));
+}
+
+#[test]
+fn rules_named_after_reason_codes() {
+    let fs = MemoryFs::new()
+        .file("/repo/com/foo/bar/1.0/bar-1.0.jar", "jar")
+        .file("/repo/com/foo/bar/1.0/bar-1.0.pom.sha1", "da39a3ee")
+        .file("/repo/com/foo/bar/1.0/bar-1.0.pom.lastUpdated", "");
+    let policies = policy::parse(
+        r#"
+        [[policy]]
+        name = "markers"
+
+        [[policy.rule]]
+        name = "last-updated-marker"
+        file = "*.lastUpdated"
+        action = "delete"
+
+        [[policy.rule]]
+        name = "stray-checksums"
+        file = "*.pom.sha1"
+        action = "delete"
+        "#,
+    )
+    .unwrap();
+    let candidates = scan(
+        fs,
+        CleanupOptions {
+            rules: policies[0].rules(),
+            ..Default::default()
+        },
+    );
+    let reasons: Vec<Reason> = candidates.iter().map(|c| c.reason).collect();
+    assert_eq!(
+        reasons,
+        [Reason::LastUpdatedMarker, Reason::Custom("stray-checksums")]
+    );
+    assert_eq!(
+        Reason::from_code("orphaned-checksum"),
+        Reason::OrphanedChecksum
+    );
+    assert_eq!(Reason::OverKeepCount.to_string(), "over-keep-count");
+}
+
+#[test]
+fn decisions_name_the_deciding_rule() {
+    let fs = snapshot_repo();
+    let rules = policy::Rules::default();
+    let mut latest = policy::LatestVersions::default();
+    let mut decide = |path: &str| {
+        let (decision, rule) = rules.decide(&fs, Path::new(REPO), Path::new(path), &mut latest);
+        (decision.to_string(), rule.map(String::from))
+    };
+    assert_eq!(
+        decide("/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-20230101.120000-1.jar"),
+        (
+            "delete as stale-snapshot".to_string(),
+            Some("stale-snapshot".to_string())
+        )
+    );
+    assert_eq!(
+        decide("/repo/com/foo/bar/1.0/bar-1.0.jar"),
+        ("keep".to_string(), None)
+    );
+}
+
+#[test]
+fn trace_lists_rules_until_the_deciding_one() {
+    let fs = snapshot_repo();
+    let (decision, steps) = policy::Rules::default().trace(
+        &fs,
+        Path::new(REPO),
+        Path::new("/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-SNAPSHOT.jar"),
+        &mut policy::LatestVersions::default(),
+    );
+    assert_eq!(decision, policy::Decision::Keep);
+    let steps: Vec<(&str, Option<&str>)> = steps
+        .iter()
+        .map(|step| (step.rule.as_str(), step.mismatch))
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            ("metadata-only", Some("metadata_only")),
+            ("local-metadata", Some("file")),
+            ("stale-snapshot", Some("expected")),
+            ("stale-lock", Some("lock")),
+        ]
+    );
+}
+
+#[test]
+fn stale_locks_are_deleted_once_old_enough() {
+    let now = std::time::SystemTime::now();
+    let old = now - Duration::from_secs(2 * 60 * 60);
+    let fs = MemoryFs::new()
+        .file("/repo/.locks/artifact~com.foo~bar~1.0.lock", "")
+        .modified("/repo/.locks/artifact~com.foo~bar~1.0.lock", old)
+        .file("/repo/.locks/c0ffee", "")
+        .modified("/repo/.locks/c0ffee", old)
+        .file("/repo/.locks/recent", "")
+        .modified("/repo/.locks/recent", now - Duration::from_secs(45 * 60))
+        .file("/repo/com/foo/bar/1.0/bar-1.0.jar", "jar")
+        .modified("/repo/com/foo/bar/1.0/bar-1.0.jar", old)
+        .file("/repo/com/foo/bar/1.0/bar-1.0.jar.lock", "")
+        .modified("/repo/com/foo/bar/1.0/bar-1.0.jar.lock", old);
+    let candidates = scan(fs, CleanupOptions::default());
+    let found: Vec<(String, Reason)> = candidates
+        .iter()
+        .map(|c| (relative(&c.path), c.reason))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            (
+                ".locks/artifact~com.foo~bar~1.0.lock".to_string(),
+                Reason::StaleLock
+            ),
+            (".locks/c0ffee".to_string(), Reason::StaleLock),
+            (
+                "com/foo/bar/1.0/bar-1.0.jar.lock".to_string(),
+                Reason::StaleLock
+            ),
+        ]
+    );
+    // --stale-locks 在内置的一小时之外追加更短的阈值
+    let fs = MemoryFs::new()
+        .file("/repo/.locks/recent", "")
+        .modified("/repo/.locks/recent", now - Duration::from_secs(45 * 60));
+    let candidates = scan(
+        fs,
+        CleanupOptions {
+            rules: policy::Rules::default().with(vec![policy::Rule::stale_locks(
+                Duration::from_secs(30 * 60),
+            )]),
+            ..Default::default()
+        },
+    );
+    let paths: Vec<String> = candidates.iter().map(|c| relative(&c.path)).collect();
+    assert_eq!(paths, [".locks/recent"]);
+}
+
+#[test]
+fn version_directories_with_active_downloads_are_skipped() {
+    let now = std::time::SystemTime::now();
+    let fs = MemoryFs::new()
+        .file("/repo/com/foo/bar/1.0/bar-1.0.pom", "<project/>")
+        .file("/repo/com/foo/bar/1.0/bar-1.0.jar.part", "half")
+        .file("/repo/com/foo/bar/2.0/bar-2.0.pom", "<project/>")
+        .file("/repo/com/foo/bar/2.0/bar-2.0.jar.part", "abandoned")
+        .modified(
+            "/repo/com/foo/bar/2.0/bar-2.0.jar.part",
+            now - Duration::from_secs(60 * 60),
+        );
+    let candidates = scan(
+        fs,
+        CleanupOptions {
+            rules: policy::Rules::default()
+                .with(vec![
+                    policy::Rule::delete_versions("com.foo:bar:[0,)").unwrap()
+                ]),
+            ..Default::default()
+        },
+    );
+    let paths: Vec<String> = candidates.iter().map(|c| relative(&c.path)).collect();
+    assert_eq!(
+        paths,
+        [
+            "com/foo/bar/2.0/bar-2.0.jar.part",
+            "com/foo/bar/2.0/bar-2.0.pom"
+        ]
+    );
+}
+
+#[test]
+fn nfd_folder_names_match_nfc_files() {
+    // APFS 返回 NFD 目录名, 文件名仍为 NFC
+    let fs = MemoryFs::new()
+        .file("/repo/com/foo/cafe\u{301}/1.0-e\u{301}te\u{301}-SNAPSHOT/caf\u{e9}-1.0-\u{e9}t\u{e9}-SNAPSHOT.jar", "latest")
+        .file("/repo/com/foo/cafe\u{301}/1.0-e\u{301}te\u{301}-SNAPSHOT/caf\u{e9}-1.0-\u{e9}t\u{e9}-SNAPSHOT.pom", "<project/>");
+    assert!(scan(fs, CleanupOptions::default()).is_empty());
+}
+
+#[test]
+fn case_insensitive_names_match_folders() {
+    let fs = || {
+        MemoryFs::new()
+            .file(
+                "/repo/org/apache/Log4J/1.0-SNAPSHOT/log4j-1.0-snapshot.jar",
+                "latest",
+            )
+            .file(
+                "/repo/org/apache/Log4J/1.0-SNAPSHOT/log4j-1.0-snapshot.pom",
+                "<project/>",
+            )
+    };
+    assert_eq!(scan(fs(), CleanupOptions::default()).len(), 2);
+    assert!(scan(fs().case_insensitive(), CleanupOptions::default()).is_empty());
+}
+
+#[test]
+fn default_protected_plugin_groups() {
+    let fs = || {
+        MemoryFs::new()
+            .file(
+                "/repo/org/apache/maven/plugins/maven-compiler-plugin/maven-metadata-local.xml",
+                "<metadata/>",
+            )
+            .file(
+                "/repo/org/codehaus/plexus/plexus-utils/4.0-SNAPSHOT/plexus-utils-4.0-20240101.120000-1.jar",
+                "old",
+            )
+            .file("/repo/com/foo/bar/maven-metadata-local.xml", "<metadata/>")
+            .file("/repo/com/foo/bar/1.0/bar-1.0.jar", "release")
+    };
+    let candidates = scan(fs(), CleanupOptions::default());
+    let paths: Vec<String> = candidates.iter().map(|c| relative(&c.path)).collect();
+    assert_eq!(paths, ["com/foo/bar/maven-metadata-local.xml"]);
+    let unprotected = CleanupOptions {
+        protected_groups: policy::ProtectedGroups::new(Vec::new()),
+        ..Default::default()
+    };
+    assert_eq!(scan(fs(), unprotected).len(), 3);
+}
+
+#[test]
+fn version_range_deletes_older_versions_except_kept_line() {
+    let mut fs = MemoryFs::new();
+    for version in ["1.0", "1.9.1", "2.0-beta-1", "2.0", "10.0"] {
+        fs = fs.file(
+            format!("/repo/com/foo/bar/{version}/bar-{version}.jar"),
+            "jar",
+        );
+    }
+    let rules = vec![
+        policy::Rule::keep_versions("com.foo:bar:1.9.*").unwrap(),
+        policy::Rule::delete_versions("com.foo:b*:[,2.0)").unwrap(),
+    ];
+    let candidates = scan(
+        fs,
+        CleanupOptions {
+            rules: policy::Rules::default().with(rules),
+            ..Default::default()
+        },
+    );
+    let paths: Vec<String> = candidates.iter().map(|c| relative(&c.path)).collect();
+    assert_eq!(
+        paths,
+        [
+            "com/foo/bar/1.0/bar-1.0.jar",
+            "com/foo/bar/2.0-beta-1/bar-2.0-beta-1.jar",
+        ]
+    );
+}
+
+#[test]
+fn workspace_build_files_keep_declared_versions() {
+    let mut fs = MemoryFs::new();
+    for (artifact, version) in [
+        ("bar", "1.0"),
+        ("bar", "2.0"),
+        ("baz", "3.1"),
+        ("qux", "4.0"),
+    ] {
+        fs = fs.file(
+            format!("/repo/com/foo/{artifact}/{version}/{artifact}-{version}.jar"),
+            "jar",
+        );
+    }
+    let pom = workspace::parse_pom(
+        r#"<project>
+            <groupId>com.app</groupId><artifactId>app</artifactId><version>1.0</version>
+            <dependencies>
+                <dependency><groupId>com.foo</groupId><artifactId>bar</artifactId><version>2.0</version></dependency>
+                <dependency><groupId>com.foo</groupId><artifactId>qux</artifactId><version>${qux.version}</version></dependency>
+            </dependencies>
+        </project>"#,
+    )
+    .unwrap();
+    let gradle = workspace::parse_gradle(
+        r#"
+        dependencies {
+            implementation("com.foo:baz:3.1:tests@jar")
+            // implementation 'com.foo:bar:1.0'
+            testImplementation 'com.foo:qux:4.+'
+            runtimeOnly "com.foo:qux:$quxVersion"
+        }
+        "#,
+    );
+    let keep = workspace::KeepSet::new(pom.into_iter().chain(gradle));
+    assert_eq!(keep.len(), 3);
+    let candidates = scan(
+        fs,
+        CleanupOptions {
+            rules: policy::Rules::default()
+                .with(vec![
+                    policy::Rule::delete_versions("com.foo:*:[0,)").unwrap()
+                ]),
+            workspace: keep,
+            ..Default::default()
+        },
+    );
+    let paths: Vec<String> = candidates.iter().map(|c| relative(&c.path)).collect();
+    assert_eq!(
+        paths,
+        ["com/foo/bar/1.0/bar-1.0.jar", "com/foo/qux/4.0/qux-4.0.jar"]
+    );
+}
+
+#[test]
+fn pins_file_keeps_coordinates_alongside_command_line_keeps() {
+    let mut fs = MemoryFs::new().file(
+        "/repo/.mvnclean-pins",
+        "# shared pins\ncom.foo:bar:1.*\n\ncom.foo:baz  # every version\nnot-a-coordinate\n",
+    );
+    for (artifact, version) in [
+        ("bar", "1.0"),
+        ("bar", "2.0"),
+        ("baz", "3.0"),
+        ("qux", "4.0"),
+    ] {
+        fs = fs.file(
+            format!("/repo/com/foo/{artifact}/{version}/{artifact}-{version}.jar"),
+            "jar",
+        );
+    }
+    let rules = vec![
+        policy::Rule::keep_versions("com.foo:qux:4.0").unwrap(),
+        policy::Rule::delete_versions("com.foo:*:[0,)").unwrap(),
+    ];
+    let candidates = scan(
+        fs,
+        CleanupOptions {
+            rules: policy::Rules::default().with(rules),
+            ..Default::default()
+        },
+    );
+    let paths: Vec<String> = candidates.iter().map(|c| relative(&c.path)).collect();
+    assert_eq!(paths, ["com/foo/bar/2.0/bar-2.0.jar"]);
+}
+
+#[test]
+fn pins_file_wins_over_decommissioned_repositories() {
+    let fs = MemoryFs::new()
+        .file("/repo/.mvnclean-pins", "com.foo:bar\n")
+        .file("/repo/com/foo/bar/1.0/bar-1.0.jar", "jar")
+        .file(
+            "/repo/com/foo/bar/1.0/_remote.repositories",
+            "bar-1.0.jar>removed=\n",
+        )
+        .file("/repo/com/foo/baz/1.0/baz-1.0.jar", "jar")
+        .file(
+            "/repo/com/foo/baz/1.0/_remote.repositories",
+            "baz-1.0.jar>removed=\n",
+        );
+    let candidates = scan(
+        fs,
+        CleanupOptions {
+            known_repositories: Some(vec!["central".to_string()]),
+            ..Default::default()
+        },
+    );
+    let paths: Vec<String> = candidates.iter().map(|c| relative(&c.path)).collect();
+    assert_eq!(paths, ["com/foo/baz/1.0"]);
+}
+
+#[test]
+fn pinned_versions_are_kept_until_they_expire() {
+    let now = std::time::SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let fs = MemoryFs::new()
+        .file("/repo/com/foo/bar/1.0/bar-1.0.jar", "jar")
+        .file("/repo/com/foo/bar/1.0/.mrc-keep-until", "never")
+        .file("/repo/com/foo/bar/2.0/bar-2.0.jar", "jar")
+        .file(
+            "/repo/com/foo/bar/2.0/.mrc-keep-until",
+            (now + 3600).to_string(),
+        )
+        .file("/repo/com/foo/bar/3.0/bar-3.0.jar", "jar")
+        .file(
+            "/repo/com/foo/bar/3.0/.mrc-keep-until",
+            (now - 3600).to_string(),
+        )
+        .file("/repo/com/foo/bar/4.0/bar-4.0.jar", "jar")
+        .file("/repo/com/foo/bar/4.0/.mrc-keep-until", "garbage");
+    let candidates = scan(
+        fs,
+        CleanupOptions {
+            rules: policy::Rules::default()
+                .with(vec![
+                    policy::Rule::delete_versions("com.foo:bar:[0,)").unwrap()
+                ]),
+            ..Default::default()
+        },
+    );
+    let paths: Vec<String> = candidates.iter().map(|c| relative(&c.path)).collect();
+    assert_eq!(
+        paths,
+        [
+            "com/foo/bar/3.0/.mrc-keep-until",
+            "com/foo/bar/3.0/bar-3.0.jar",
+            "com/foo/bar/4.0/.mrc-keep-until",
+            "com/foo/bar/4.0/bar-4.0.jar",
+        ]
+    );
+}
+
+#[test]
+fn superseded_snapshots_predate_newest_release() {
+    let day = |days: u64| UNIX_EPOCH + Duration::from_secs(days * 86400);
+    let fs = MemoryFs::new()
+        .file("/repo/com/foo/bar/1.0/bar-1.0.jar", "release")
+        .file("/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-SNAPSHOT.jar", "old")
+        .file(
+            "/repo/com/foo/bar/1.2-SNAPSHOT/bar-1.2-20240101.120000-1.jar",
+            "rebuilt",
+        )
+        .file("/repo/com/foo/bar/1.2/bar-1.2.jar", "release")
+        .file(
+            "/repo/com/foo/bar/2.0-SNAPSHOT/bar-2.0-SNAPSHOT.jar",
+            "next",
+        )
+        .modified("/repo/com/foo/bar/1.0", day(30))
+        .modified("/repo/com/foo/bar/1.1-SNAPSHOT", day(10))
+        // 目录较旧, 但部署时间晚于正式版
+        .modified("/repo/com/foo/bar/1.2-SNAPSHOT", day(10))
+        .modified("/repo/com/foo/bar/1.2", day(19000))
+        .modified("/repo/com/foo/bar/2.0-SNAPSHOT", day(10));
+    let candidates = scan(
+        fs,
+        CleanupOptions {
+            rules: policy::Rules::new(vec![policy::Rule::superseded_snapshots()]),
+            ..Default::default()
+        },
+    );
+    let paths: Vec<String> = candidates.iter().map(|c| relative(&c.path)).collect();
+    assert_eq!(paths, ["com/foo/bar/1.1-SNAPSHOT/bar-1.1-SNAPSHOT.jar"]);
+}
+
+#[test]
+fn keep_latest_keeps_the_highest_versions_of_each_kind() {
+    let day = |days: u64| UNIX_EPOCH + Duration::from_secs(days * 86400);
+    let mut fs = MemoryFs::new();
+    // 修改时间与版本顺序相反, 排名只按 Maven 版本顺序
+    for (index, version) in [
+        "1.9",
+        "1.10",
+        "2.0",
+        "10.0",
+        "1.0-SNAPSHOT",
+        "2.1-SNAPSHOT",
+        "3.0-SNAPSHOT",
+        "11.0-SNAPSHOT",
+    ]
+    .iter()
+    .enumerate()
+    {
+        let version_dir = format!("/repo/com/foo/bar/{version}");
+        fs = fs
+            .file(format!("{version_dir}/bar-{version}.jar"), "jar")
+            .modified(&version_dir, day(100 - index as u64));
+    }
+    let policies = policy::parse(
+        r#"
+        [[policy]]
+        name = "latest"
+
+        [[policy.rule]]
+        name = "old-versions"
+        action = "keep-latest"
+        count = 2
+        "#,
+    )
+    .unwrap();
+    let candidates = scan(
+        fs,
+        CleanupOptions {
+            rules: policies[0].rules(),
+            ..Default::default()
+        },
+    );
+    let mut paths: Vec<String> = candidates.iter().map(|c| relative(&c.path)).collect();
+    paths.sort();
+    // 快照不会挤掉正式版
+    assert_eq!(
+        paths,
+        [
+            "com/foo/bar/1.0-SNAPSHOT/bar-1.0-SNAPSHOT.jar",
+            "com/foo/bar/1.10/bar-1.10.jar",
+            "com/foo/bar/1.9/bar-1.9.jar",
+            "com/foo/bar/2.1-SNAPSHOT/bar-2.1-SNAPSHOT.jar",
+        ]
+    );
+}
+
+#[test]
+fn metadata_only_version_dirs() {
+    let fs = MemoryFs::new()
+        .file("/repo/com/foo/bar/1.0/bar-1.0.jar.sha1", "da39a3ee")
+        .file("/repo/com/foo/bar/1.0/bar-1.0.jar.lastUpdated", "")
+        .file("/repo/com/foo/bar/1.0/_remote.repositories", "")
+        .file("/repo/com/foo/bar/1.1/bar-1.1.jar", "jar")
+        .file("/repo/com/foo/bar/1.1/bar-1.1.jar.sha1", "da39a3ee")
+        .file("/repo/com/foo/bar/1.2/bar-1.2.txt", "notes")
+        .file("/repo/com/foo/bar/1.2/bar-1.2.txt.sha1", "da39a3ee")
+        .file(
+            "/repo/com/foo/bar/maven-metadata-central.xml",
+            "<metadata/>",
+        );
+    let candidates = scan(fs, CleanupOptions::default());
+    insta::assert_snapshot!(render(&candidates), @"metadata-only              VersionDir     com/foo/bar/1.0");
+}
+
+#[test]
+fn planned_sizes_per_version_dir() {
+    let fs = snapshot_repo();
+    let candidates = scan(snapshot_repo(), CleanupOptions::default());
+    let files = remove::planned_files(&fs, &candidates);
+    let versions: Vec<(String, usize, usize)> = remove::planned_versions(&files)
+        .into_iter()
+        .map(|(dir, stat)| (gav::label(Path::new(REPO), &dir), stat.files, stat.size))
+        .collect();
+    assert_eq!(
+        versions,
+        [
+            ("com.foo:bar:1.1-SNAPSHOT".to_string(), 4, 32),
+            ("com/foo/bar".to_string(), 1, 11),
+        ]
+    );
+}
+
+#[test]
+fn timestamped_builds_listed_in_snapshot_metadata_are_kept() {
+    let fs = snapshot_repo().file(
+        "/repo/com/foo/bar/1.1-SNAPSHOT/maven-metadata-central.xml",
+        "<metadata><versioning><snapshotVersions><snapshotVersion>\
+         <extension>pom</extension><value>1.1-20240101.120000-2</value>\
+         </snapshotVersion></snapshotVersions></versioning></metadata>",
+    );
+    let candidates = scan(fs, CleanupOptions::default());
+    let paths: Vec<String> = candidates.iter().map(|c| relative(&c.path)).collect();
+    assert!(paths.contains(&"com/foo/bar/1.1-SNAPSHOT/bar-1.1-20230101.120000-1.jar".to_string()));
+    assert!(!paths.contains(&"com/foo/bar/1.1-SNAPSHOT/bar-1.1-20240101.120000-2.pom".to_string()));
+}
+
+#[test]
+fn stale_snapshot_files_outside_expected_names() {
+    let fs = MemoryFs::new()
+        .file(
+            "/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-SNAPSHOT.jar",
+            "latest",
+        )
+        .file(
+            "/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-SNAPSHOT.pom",
+            "<project/>",
+        )
+        .file(
+            "/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-SNAPSHOT.jar.asc",
+            "sig",
+        )
+        .file(
+            "/repo/com/foo/bar/1.1-SNAPSHOT/baz-1.1-SNAPSHOT.jar",
+            "foreign",
+        )
+        .file(
+            "/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-nightly.jar",
+            "misnamed",
+        );
+    let candidates = scan(fs, CleanupOptions::default());
+    let paths: Vec<String> = candidates.iter().map(|c| relative(&c.path)).collect();
+    assert_eq!(
+        paths,
+        [
+            "com/foo/bar/1.1-SNAPSHOT/bar-1.1-nightly.jar",
+            "com/foo/bar/1.1-SNAPSHOT/baz-1.1-SNAPSHOT.jar",
+        ]
+    );
+}
+
+#[test]
+fn config_profiles_carry_their_own_rules() {
+    let fs = MemoryFs::new()
+        .file("/repo/com/foo/bar/1.0/bar-1.0.jar", "jar")
+        .file("/repo/com/foo/bar/1.0/bar-1.0.pom.lastUpdated", "");
+    let config = config::parse(
+        r#"
+        always_keep = ["com.mycorp"]
+
+        [profile.ci]
+        paths = ["/cache/m2"]
+        interval = "6h"
+
+        [[profile.ci.rule]]
+        name = "markers"
+        file = "*.lastUpdated"
+        action = "delete"
+
+        [profile.laptop]
+        paths = ["/home/dev/.m2/repository"]
+        "#,
+    )
+    .unwrap();
+    let ci = config.profile("ci").unwrap();
+    assert_eq!(ci.paths, ["/cache/m2"]);
+    assert_eq!(ci.interval.as_deref(), Some("6h"));
+    // 规则名在解析配置时就已成为删除原因
+    let policy = ci.policy("ci").unwrap();
+    let candidates = scan(
+        fs,
+        CleanupOptions {
+            rules: policy.rules(),
+            ..Default::default()
+        },
+    );
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].reason, Reason::Custom("markers"));
+    assert!(config.profile("laptop").unwrap().policy("laptop").is_none());
+    assert!(config.profile("nightly").is_err());
+    assert!(
+        config::parse("[profile.ci]\nrule = [{name = \"x\", action = \"keep-latest\"}]").is_err()
+    );
+}
+
+#[test]
+fn config_skeleton_parses_with_and_without_example_rules() {
+    let skeleton = config::skeleton(Path::new("/home/dev/.m2/repository"));
+    let config = config::parse(&skeleton).unwrap();
+    let profile = config.profile("default").unwrap();
+    assert_eq!(profile.paths, ["/home/dev/.m2/repository"]);
+    assert!(profile.policy("default").is_none());
+    // 取消注释后的示例规则也必须有效
+    let uncommented: String = skeleton
+        .lines()
+        .map(|line| match line.strip_prefix("# ") {
+            Some(rest) if rest.starts_with("[[") || rest.contains(" = ") => rest,
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let config = config::parse(&uncommented).unwrap();
+    let policy = config
+        .profile("default")
+        .unwrap()
+        .policy("default")
+        .unwrap();
+    assert_eq!(policy.rule.len(), 2);
+}
+
+#[test]
+fn walkable_dirs_are_checked_through_the_repository_filesystem() {
+    let fs = snapshot_repo();
+    assert!(mrc::is_walkable_dir(&fs, Path::new("/repo/com/foo/bar")));
+    assert!(!mrc::is_walkable_dir(
+        &fs,
+        Path::new("/repo/com/foo/bar/1.0/bar-1.0.jar")
+    ));
+    assert!(!mrc::is_walkable_dir(&fs, Path::new("/repo/missing")));
+}
@@ -0,0 +1,59 @@
+use mrc::dedup;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn repo_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mrc-dedup-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[test]
+fn identical_jars_are_grouped_across_coordinates() {
+    let repo_path = repo_dir("identical");
+    write(&repo_path.join("old/lib/1.0/lib-1.0.jar"), "relocated");
+    write(&repo_path.join("com/new/lib/1.0/lib-1.0.jar"), "relocated");
+    write(&repo_path.join("com/new/lib/1.0/lib-1.0.pom"), "relocated");
+    write(&repo_path.join("com/app/uber/2.0/uber-2.0.jar"), "shaded!");
+    write(&repo_path.join("com/util/part/3.0/part-3.0.jar"), "shaded!");
+    write(
+        &repo_path.join("com/util/part/3.0/part-3.0-all.jar"),
+        "shaded!",
+    );
+    // 同一坐标下的快照副本不算
+    let snapshot = repo_path.join("com/foo/bar/1.0-SNAPSHOT");
+    write(&snapshot.join("bar-1.0-20240101.120000-1.jar"), "snapshot");
+    write(&snapshot.join("bar-1.0-SNAPSHOT.jar"), "snapshot");
+
+    let sets = dedup::identical_artifacts(&repo_path);
+    let summary: Vec<(u64, Vec<String>)> = sets
+        .iter()
+        .map(|set| {
+            let coordinates = set.files.iter().map(|(gav, _)| gav.to_string()).collect();
+            (set.waste(), coordinates)
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (
+                14,
+                vec![
+                    "com.app:uber:2.0".to_string(),
+                    "com.util:part:3.0".to_string(),
+                    "com.util:part:3.0".to_string(),
+                ]
+            ),
+            (
+                9,
+                vec!["com.new:lib:1.0".to_string(), "old:lib:1.0".to_string()]
+            ),
+        ]
+    );
+}
@@ -0,0 +1,122 @@
+use mrc::doctor::{self, Problem};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+fn repo_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mrc-doctor-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[test]
+fn findings_cover_each_kind_of_problem() {
+    let repo_path = repo_dir("kinds");
+    let version = repo_path.join("com/foo/bar/1.0");
+    write(
+        &version.join("_remote.repositories"),
+        "bar-1.0.jar>central=\nbar-1.0.pom>=\n",
+    );
+    // 下载的构件既损坏又没有校验文件; 本地安装的 POM 没有校验文件是正常的
+    write(&version.join("bar-1.0.jar"), "not a zip");
+    write(&version.join("bar-1.0.pom"), "<project/>");
+    write(&version.join("bar-1.0.jar.lastUpdated"), "");
+    write(&version.join("bar-0.9.jar.sha1"), "0");
+    write(&repo_path.join("com/foo/gone/2.0/_remote.repositories"), "");
+    write(&repo_path.join(".locks/fresh.lock"), "");
+    write(&repo_path.join(".locks/stale.lock"), "");
+    fs::File::options()
+        .write(true)
+        .open(repo_path.join(".locks/stale.lock"))
+        .unwrap()
+        .set_modified(SystemTime::now() - Duration::from_secs(2 * 60 * 60))
+        .unwrap();
+
+    let mut findings: Vec<(String, Problem)> = doctor::diagnose(&repo_path)
+        .into_iter()
+        .map(|finding| {
+            let path = finding.path.strip_prefix(&repo_path).unwrap();
+            (path.to_string_lossy().replace('\\', "/"), finding.problem)
+        })
+        .map(|(path, problem)| match problem {
+            Problem::Corrupt(_) => (path, Problem::Corrupt(String::new())),
+            problem => (path, problem),
+        })
+        .collect();
+    findings.sort();
+    assert_eq!(
+        findings,
+        [
+            (".locks/stale.lock".to_string(), Problem::AbandonedLock),
+            (
+                "com/foo/bar/1.0/bar-0.9.jar.sha1".to_string(),
+                Problem::Dangling
+            ),
+            (
+                "com/foo/bar/1.0/bar-1.0.jar".to_string(),
+                Problem::MissingChecksum
+            ),
+            (
+                "com/foo/bar/1.0/bar-1.0.jar".to_string(),
+                Problem::Corrupt(String::new())
+            ),
+            (
+                "com/foo/bar/1.0/bar-1.0.jar.lastUpdated".to_string(),
+                Problem::Dangling
+            ),
+            (
+                "com/foo/gone/2.0/_remote.repositories".to_string(),
+                Problem::Dangling
+            ),
+        ]
+    );
+    assert!(!doctor::doctor(&repo_path));
+    // 只诊断, 不删除
+    assert!(version.join("bar-1.0.jar.lastUpdated").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn files_the_owner_cannot_write_are_reported() {
+    use std::os::unix::fs::PermissionsExt;
+    let repo_path = repo_dir("permissions");
+    let pom = repo_path.join("com/foo/bar/1.0/bar-1.0.pom");
+    write(&pom, "<project/>");
+    fs::set_permissions(&pom, fs::Permissions::from_mode(0o444)).unwrap();
+    let findings = doctor::diagnose(&repo_path);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].path, pom);
+    assert_eq!(
+        findings[0].problem,
+        Problem::Permissions("mode 444".to_string())
+    );
+}
+
+#[test]
+fn snapshot_builds_missing_from_metadata_are_stale() {
+    let repo_path = repo_dir("stale");
+    let version = repo_path.join("com/foo/bar/1.0-SNAPSHOT");
+    write(
+        &version.join("maven-metadata-central.xml"),
+        "<metadata><versioning><snapshotVersions><snapshotVersion>\
+         <extension>pom</extension><value>1.0-20240101.120000-2</value>\
+         </snapshotVersion></snapshotVersions></versioning></metadata>",
+    );
+    write(&version.join("bar-1.0-20240101.120000-2.pom"), "<project/>");
+    write(&version.join("bar-1.0-20230101.120000-1.pom"), "<project/>");
+    let findings = doctor::diagnose(&repo_path);
+    assert_eq!(
+        findings,
+        [doctor::Finding {
+            path: version.join("bar-1.0-20230101.120000-1.pom"),
+            problem: Problem::StaleSnapshot,
+        }]
+    );
+    fs::remove_dir_all(&repo_path).unwrap();
+}
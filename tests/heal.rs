@@ -0,0 +1,105 @@
+use mrc::heal::{self, Healed};
+use mrc::{doctor, hash};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn repo_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mrc-heal-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[test]
+fn repairs_checksums_metadata_and_markers() {
+    let repo_path = repo_dir("repair");
+    let artifact = repo_path.join("com/foo/bar");
+    write(
+        &artifact.join("maven-metadata-local.xml"),
+        "<metadata><versioning><latest>0.9</latest><versions><version>0.9</version><version>1.0</version><version>2.0</version></versions></versioning></metadata>",
+    );
+    write(
+        &artifact.join("1.0/_remote.repositories"),
+        "bar-1.0.pom>central=\nbar-1.0.jar>central=\n",
+    );
+    write(&artifact.join("1.0/bar-1.0.pom"), "<project/>");
+    write(&artifact.join("1.0/bar-1.0.jar"), "not a zip");
+    write(&artifact.join("1.0/bar-1.0.jar.sha1"), "0000");
+    write(&artifact.join("1.0/bar-1.0.jar.lastUpdated"), "");
+    // 本地安装的构件: 错误的 .sha1 会被修正, 但不会新建
+    write(&artifact.join("2.0/bar-2.0.pom"), "<project/>");
+    write(&artifact.join("2.0/bar-2.0.pom.sha1"), "deadbeef");
+    write(&artifact.join("2.0/bar-2.0.module"), "{}");
+
+    let expected = Healed {
+        checksums: 2,
+        metadata: 1,
+        markers: 1,
+        errors: 0,
+    };
+    assert_eq!(heal::heal(&repo_path, true, false), expected);
+    assert!(artifact.join("1.0/bar-1.0.jar.lastUpdated").exists());
+    assert_eq!(heal::heal(&repo_path, false, false), expected);
+
+    let pom = artifact.join("1.0/bar-1.0.pom");
+    assert_eq!(
+        fs::read_to_string(artifact.join("1.0/bar-1.0.pom.sha1")).unwrap(),
+        hash::sha1_file(&pom).unwrap()
+    );
+    assert_eq!(
+        fs::read_to_string(artifact.join("2.0/bar-2.0.pom.sha1")).unwrap(),
+        hash::sha1_file(&artifact.join("2.0/bar-2.0.pom")).unwrap()
+    );
+    // 损坏的构件保留原有校验文件
+    assert_eq!(
+        fs::read_to_string(artifact.join("1.0/bar-1.0.jar.sha1")).unwrap(),
+        "0000"
+    );
+    assert!(!artifact.join("2.0/bar-2.0.module.sha1").exists());
+    assert!(!artifact.join("1.0/bar-1.0.jar.lastUpdated").exists());
+    assert_eq!(
+        fs::read_to_string(artifact.join("maven-metadata-local.xml")).unwrap(),
+        "<metadata><versioning><latest>2.0</latest><versions><version>1.0</version><version>2.0</version></versions></versioning></metadata>",
+    );
+    assert_eq!(heal::heal(&repo_path, false, false), Healed::default());
+    // 剩下的只有 heal 无法修复的损坏构件
+    let remaining = doctor::diagnose(&repo_path);
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].path, artifact.join("1.0/bar-1.0.jar"));
+}
+
+#[test]
+fn unchanged_artifacts_are_not_hashed_again() {
+    let repo_path = repo_dir("verified");
+    let version = repo_path.join("com/foo/bar/1.0");
+    let pom = version.join("bar-1.0.pom");
+    write(&pom, "<project/>");
+    write(&version.join("bar-1.0.pom.sha1"), "deadbeef");
+    assert_eq!(heal::heal(&repo_path, false, false).checksums, 1);
+    let verified = fs::read_to_string(version.join("bar-1.0.pom.sha1")).unwrap();
+
+    // 同样大小和修改时间的改动被视为未变化, 只有 rehash 会发现
+    let modified = fs::metadata(&pom).unwrap().modified().unwrap();
+    fs::write(&pom, "<pRoject/>").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&pom)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+    assert_eq!(heal::heal(&repo_path, false, false), Healed::default());
+    assert_eq!(
+        fs::read_to_string(version.join("bar-1.0.pom.sha1")).unwrap(),
+        verified
+    );
+    assert_eq!(heal::heal(&repo_path, false, true).checksums, 1);
+    assert_eq!(
+        fs::read_to_string(version.join("bar-1.0.pom.sha1")).unwrap(),
+        hash::sha1_file(&pom).unwrap()
+    );
+}
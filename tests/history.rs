@@ -0,0 +1,68 @@
+use mrc::history::{self, Run};
+use mrc::summary::Summary;
+use std::path::PathBuf;
+
+fn repo_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mrc-history-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&path);
+    std::fs::create_dir_all(&path).unwrap();
+    path
+}
+
+#[test]
+fn runs_are_appended_in_order() {
+    let repo_path = repo_dir("append");
+    let summary = Summary {
+        deleted_size: 4096,
+        files_deleted: 3,
+        errors: 1,
+        ..Default::default()
+    };
+    history::record(&repo_path, &Run::new("default", &summary, 1 << 20));
+    history::record(
+        &repo_path,
+        &Run::new("nightly", &Summary::default(), 2 << 20),
+    );
+    // 写入一半的行被跳过
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(repo_path.join(history::HISTORY_FILE))
+        .and_then(|mut file| std::io::Write::write_all(&mut file, b"{\"date\":"))
+        .unwrap();
+
+    let runs = history::load(&repo_path).unwrap();
+    std::fs::remove_dir_all(&repo_path).unwrap();
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0].policy, "default");
+    assert_eq!(runs[0].deleted_size, 4096);
+    assert_eq!(runs[0].files_deleted, 3);
+    assert_eq!(runs[0].errors, 1);
+    assert_eq!(runs[1].policy, "nightly");
+    assert_eq!(runs[1].repo_size, 2 << 20);
+}
+
+#[test]
+fn trend_separates_growth_from_cleanups() {
+    const DAY: u64 = 24 * 60 * 60;
+    let run = |day: u64, deleted_size: usize, repo_size: usize| Run {
+        date: 1_700_000_000 + day * DAY,
+        policy: "default".to_string(),
+        deleted_size,
+        files_deleted: 0,
+        errors: 0,
+        repo_size,
+    };
+    // 每 10 天清理一次, 期间下载 300, 清理 200, 净增 100
+    let runs = [run(0, 0, 1000), run(10, 200, 1100), run(20, 200, 1200)];
+    let trend = history::trend(&runs).unwrap();
+    assert_eq!(trend.days, 20.0);
+    assert_eq!(trend.net, 10.0);
+    assert_eq!(trend.gross, 30.0);
+    assert_eq!(trend.freed, 20.0);
+    assert_eq!(history::days_until_full(&trend, 500), Some(50.0));
+
+    let shrinking = [run(0, 0, 1000), run(10, 500, 800)];
+    let trend = history::trend(&shrinking).unwrap();
+    assert_eq!(history::days_until_full(&trend, 500), None);
+    assert_eq!(history::trend(&runs[..1]), None);
+}
@@ -0,0 +1,53 @@
+use mrc::index::{self, INDEX_FILE};
+use mrc::{pipeline, CleanupOptions};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mrc-index-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+fn incremental_run(repo_path: &Path) -> Vec<PathBuf> {
+    let options = CleanupOptions {
+        incremental: true,
+        ..Default::default()
+    };
+    let mut found = Vec::new();
+    let (_, index) = pipeline::run(repo_path, &options, None, &mut |candidate| {
+        found.push(candidate.path)
+    });
+    index.unwrap().save(repo_path);
+    found
+}
+
+#[test]
+fn unchanged_directories_are_revisited_when_decisions_depend_on_time() {
+    let repo_path = temp_dir("time");
+    let locked = repo_path.join("com/foo/bar/1.0");
+    let plain = repo_path.join("com/foo/baz/1.0");
+    fs::create_dir_all(&locked).unwrap();
+    fs::create_dir_all(&plain).unwrap();
+    fs::write(locked.join("bar-1.0.jar"), "jar").unwrap();
+    let lock = locked.join("bar-1.0.jar.lock");
+    fs::write(&lock, "").unwrap();
+    fs::write(plain.join("baz-1.0.jar"), "jar").unwrap();
+
+    assert!(incremental_run(&repo_path).is_empty());
+    let dirs = index::load(&repo_path.join(INDEX_FILE)).dirs;
+    assert!(dirs["com/foo/bar/1.0"].revisit);
+    assert!(!dirs["com/foo/baz/1.0"].revisit);
+
+    // 修改文件时间不会改变目录的修改时间
+    fs::File::options()
+        .write(true)
+        .open(&lock)
+        .unwrap()
+        .set_modified(SystemTime::now() - Duration::from_secs(2 * 3600))
+        .unwrap();
+    assert_eq!(incremental_run(&repo_path), [lock]);
+    fs::remove_dir_all(&repo_path).unwrap();
+}
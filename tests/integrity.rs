@@ -0,0 +1,46 @@
+use mrc::integrity;
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mrc-integrity-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+#[test]
+fn poms_in_other_encodings_and_with_doctype_are_well_formed() {
+    let dir = temp_dir("pom");
+    let poms: [(&str, Vec<u8>); 4] = [
+        (
+            "latin1.pom",
+            b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>\n<project><name>Caf\xe9</name></project>\n"
+                .to_vec(),
+        ),
+        (
+            "undeclared.pom",
+            b"<project><name>Caf\xe9</name></project>\n".to_vec(),
+        ),
+        (
+            "doctype.pom",
+            b"<?xml version=\"1.0\"?>\n<!DOCTYPE project>\n<project/>\n".to_vec(),
+        ),
+        (
+            "utf16.pom",
+            [0xFF, 0xFE]
+                .into_iter()
+                .chain("<project>Café</project>".encode_utf16().flat_map(u16::to_le_bytes))
+                .collect(),
+        ),
+    ];
+    for (name, content) in poms {
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        assert!(integrity::check_pom(&path).is_ok(), "{}", name);
+    }
+    let truncated = dir.join("truncated.pom");
+    fs::write(&truncated, "<?xml version=\"1.0\"?>\n<project><name>x</na").unwrap();
+    assert!(integrity::check_pom(&truncated).is_err());
+    fs::remove_dir_all(&dir).unwrap();
+}
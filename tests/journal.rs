@@ -0,0 +1,85 @@
+use mrc::fs::{FsHandle, RepoFs};
+use mrc::journal::{self, Journal};
+use mrc::memory_fs::MemoryFs;
+use mrc::remove::{Action, Candidate};
+use mrc::summary::Reason;
+use mrc::{Cleaner, CleanupOptions};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+static REPO: &str = "/repo";
+
+fn repo() -> MemoryFs {
+    MemoryFs::new()
+        .file("/repo/com/foo/bar/1.0/bar-1.0.jar", "release")
+        .file(
+            "/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-20230101.120000-1.jar",
+            "old",
+        )
+        .file(
+            "/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-20230101.120000-1.jar.sha1",
+            "da39a3ee",
+        )
+        .file(
+            "/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-SNAPSHOT.jar",
+            "latest",
+        )
+}
+
+fn journal_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mrc-{}-{}.jsonl", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn completed_batches_record_deleted_files() {
+    let path = journal_path("completed");
+    let fs = Arc::new(repo());
+    let options = CleanupOptions {
+        fs: FsHandle::from(fs.clone()),
+        journal: Some(Arc::new(Journal::open(&path, Path::new(REPO)).unwrap())),
+        ..Default::default()
+    };
+    let cleaner = Cleaner::new(PathBuf::from(REPO), options);
+    let summary = cleaner.clean(cleaner.scan());
+
+    let replay = journal::replay(&path, &*fs).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(replay.repo_path, Path::new(REPO));
+    assert_eq!(replay.interrupted, 0);
+    assert_eq!(replay.summary.files_deleted, summary.files_deleted);
+    assert_eq!(replay.summary.deleted_size, summary.deleted_size);
+}
+
+#[test]
+fn interrupted_batches_count_only_missing_files() {
+    let path = journal_path("interrupted");
+    let fs = repo();
+    let jar = PathBuf::from("/repo/com/foo/bar/1.1-SNAPSHOT/bar-1.1-20230101.120000-1.jar");
+    let journal = Journal::open(&path, Path::new(REPO)).unwrap();
+    let candidate = Candidate::new(jar.clone(), Action::WithChecksums, Reason::StaleSnapshot);
+    let mut checksum = jar.as_os_str().to_owned();
+    checksum.push(".sha1");
+    journal
+        .begin(
+            &candidate,
+            vec![(jar.clone(), 3), (PathBuf::from(checksum), 8)],
+        )
+        .unwrap();
+    // 删除了第一个文件后崩溃, 没有写入 done
+    fs.remove_file(&jar).unwrap();
+    // 最后一行只写了一半
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| std::io::Write::write_all(&mut file, b"{\"record\":\"int"))
+        .unwrap();
+
+    let replay = journal::replay(&path, &fs).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(replay.interrupted, 1);
+    assert_eq!(replay.summary.files_deleted, 1);
+    assert_eq!(replay.summary.deleted[0].path, jar);
+    assert_eq!(replay.summary.deleted_size, 3);
+}
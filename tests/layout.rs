@@ -0,0 +1,228 @@
+use mrc::gav::Gav;
+use mrc::layout::{self, ArtifactFile, Published};
+use mrc::memory_fs::MemoryFs;
+use mrc::snapshot::Build;
+use std::path::Path;
+
+fn gav(version: &str) -> Gav {
+    Gav {
+        group_id: "com.foo".to_string(),
+        artifact_id: "bar".to_string(),
+        version: version.to_string(),
+    }
+}
+
+fn parse(version: &str, file_name: &str) -> Option<ArtifactFile> {
+    layout::parse_file_name(&gav(version), file_name, false)
+}
+
+#[test]
+fn locates_coordinates_from_paths() {
+    let entry = layout::locate(
+        Path::new("/repo"),
+        Path::new("/repo/com/foo/bar/1.0/bar-1.0-sources.jar.sha1"),
+        false,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(entry.gav, Some(gav("1.0")));
+    assert_eq!(entry.file_name, "bar-1.0-sources.jar.sha1");
+    assert_eq!(
+        entry.artifact,
+        Some(ArtifactFile {
+            classifier: Some("sources".to_string()),
+            extension: "jar".to_string(),
+            build: None,
+            checksum: Some(".sha1"),
+            signature: false,
+        })
+    );
+
+    let shallow = layout::locate(Path::new("/repo"), Path::new("/repo/com/x.jar"), false, &[]);
+    assert_eq!(shallow.unwrap().gav, None);
+}
+
+#[test]
+fn parses_artifact_file_names() {
+    let plain = parse("1.0", "bar-1.0.tar.gz").unwrap();
+    assert_eq!(plain.classifier, None);
+    assert_eq!(plain.extension, "tar.gz");
+
+    let timestamped = parse("1.0-SNAPSHOT", "bar-1.0-20240101.120000-3-tests.jar").unwrap();
+    assert_eq!(
+        timestamped.build,
+        Some(Build::parse("20240101.120000-3").unwrap())
+    );
+    assert_eq!(timestamped.classifier.as_deref(), Some("tests"));
+
+    let snapshot = parse("1.0-SNAPSHOT", "bar-1.0-SNAPSHOT.pom").unwrap();
+    assert_eq!(snapshot.build, None);
+    assert_eq!(snapshot.extension, "pom");
+}
+
+#[test]
+fn rejects_foreign_file_names() {
+    for file_name in [
+        "_remote.repositories",
+        "maven-metadata-local.xml",
+        "bar-1.0",
+        "bar-1.0.",
+        "bar-1.01.jar",
+        "baz-1.0.jar",
+    ] {
+        assert_eq!(parse("1.0", file_name), None, "{}", file_name);
+    }
+    assert_eq!(parse("1.0-SNAPSHOT", "bar-1.0-nightly.jar"), None);
+    assert_eq!(parse("1.0-SNAPSHOT", "bar-1.0-20240101.120000-x.jar"), None);
+    assert!(layout::parse_file_name(&gav("1.0"), "BAR-1.0.jar", true).is_some());
+}
+
+#[test]
+fn expected_file_names_of_version_dir() {
+    let snapshot = gav("1.0-SNAPSHOT");
+    for file_name in [
+        "bar-1.0-SNAPSHOT.jar",
+        "bar-1.0-SNAPSHOT-sources.jar.sha256",
+        "bar-1.0-SNAPSHOT.pom.asc",
+        "bar-1.0-SNAPSHOT.pom.asc.md5",
+        "maven-metadata-local.xml",
+        "maven-metadata-central.xml.sha1",
+        "_remote.repositories",
+    ] {
+        assert!(
+            layout::is_expected(&snapshot, file_name, false, &[]),
+            "{}",
+            file_name
+        );
+    }
+    for file_name in [
+        "bar-1.0-20240101.120000-1.jar",
+        "baz-1.0-SNAPSHOT.jar",
+        "copy-of-bar-1.0-SNAPSHOT.jar",
+        "bar-1.0-SNAPSHOT.jar.lastUpdated",
+        "bar-1.0-SNAPSHOT.jar.part",
+    ] {
+        assert!(
+            !layout::is_expected(&snapshot, file_name, false, &[]),
+            "{}",
+            file_name
+        );
+    }
+}
+
+#[test]
+fn enumerates_expected_file_names() {
+    let metadata = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>com.foo</groupId>
+  <artifactId>bar</artifactId>
+  <version>1.0-SNAPSHOT</version>
+  <versioning>
+    <snapshotVersions>
+      <snapshotVersion>
+        <extension>pom</extension>
+        <value>1.0-20240101.120000-3</value>
+      </snapshotVersion>
+      <snapshotVersion>
+        <classifier>sources</classifier>
+        <extension>jar</extension>
+        <value>1.0-20240101.120000-3</value>
+      </snapshotVersion>
+    </snapshotVersions>
+  </versioning>
+</metadata>"#;
+    let published = layout::read_snapshot_versions(metadata).unwrap();
+    assert_eq!(
+        published,
+        [
+            Published {
+                classifier: None,
+                extension: "pom".to_string(),
+                version: "1.0-20240101.120000-3".to_string(),
+            },
+            Published {
+                classifier: Some("sources".to_string()),
+                extension: "jar".to_string(),
+                version: "1.0-20240101.120000-3".to_string(),
+            },
+        ]
+    );
+    let names = layout::expected_file_names(&gav("1.0-SNAPSHOT"), &published);
+    assert_eq!(names.len(), 40);
+    for name in [
+        "bar-1.0-20240101.120000-3.pom",
+        "bar-1.0-20240101.120000-3.pom.sha1",
+        "bar-1.0-20240101.120000-3-sources.jar.asc.sha512",
+        "bar-1.0-SNAPSHOT.pom",
+        "bar-1.0-SNAPSHOT-sources.jar.md5",
+    ] {
+        assert!(names.contains(name), "{}", name);
+    }
+    // 枚举出的名称都能解析回原构件
+    for name in &names {
+        let parsed = layout::parse_file_name(&gav("1.0-SNAPSHOT"), name, false).unwrap();
+        assert!(
+            ["pom", "jar"].contains(&parsed.extension.as_str()),
+            "{}",
+            name
+        );
+    }
+
+    let release = layout::expected_file_names(
+        &gav("1.0"),
+        &[Published {
+            classifier: None,
+            extension: "jar".to_string(),
+            version: "1.0".to_string(),
+        }],
+    );
+    assert_eq!(release.len(), 10);
+    assert!(release.contains("bar-1.0.jar.asc"));
+    assert_eq!(layout::read_snapshot_versions("<metadata/>").unwrap(), []);
+    assert!(layout::read_snapshot_versions("<metadata>").is_err());
+}
+
+#[test]
+fn timestamped_builds_listed_in_metadata_are_expected() {
+    let fs = MemoryFs::new()
+        .file(
+            "/repo/com/foo/bar/1.0-SNAPSHOT/maven-metadata-central.xml",
+            "<metadata><versioning><snapshotVersions><snapshotVersion>\
+             <extension>jar</extension><value>1.0-20240101.120000-3</value>\
+             </snapshotVersion></snapshotVersions></versioning></metadata>",
+        )
+        .file(
+            "/repo/com/foo/bar/1.0-SNAPSHOT/maven-metadata-central.xml.sha1",
+            "x",
+        );
+    let published = layout::published(&fs, Path::new("/repo/com/foo/bar/1.0-SNAPSHOT"));
+    assert_eq!(published.len(), 1);
+    let snapshot = gav("1.0-SNAPSHOT");
+    for (file_name, expected) in [
+        ("bar-1.0-20240101.120000-3.jar", true),
+        ("bar-1.0-20240101.120000-3.jar.sha1", true),
+        ("BAR-1.0-20240101.120000-3.JAR", false),
+        ("bar-1.0-20240101.120000-2.jar", false),
+        ("bar-1.0-20240101.120000-3.pom", false),
+    ] {
+        assert_eq!(
+            layout::is_expected(&snapshot, file_name, false, &published),
+            expected,
+            "{}",
+            file_name
+        );
+        assert_eq!(
+            layout::is_stale_build(&snapshot, file_name, false, &published),
+            !expected && file_name.starts_with("bar-"),
+            "{}",
+            file_name
+        );
+    }
+    assert!(layout::is_expected(
+        &snapshot,
+        "BAR-1.0-20240101.120000-3.JAR",
+        true,
+        &published
+    ));
+    assert!(layout::published(&fs, Path::new("/repo/com/foo/bar/1.0")).is_empty());
+}
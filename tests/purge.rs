@@ -0,0 +1,145 @@
+use mrc::purge::{self, Coordinate};
+use mrc::CleanupOptions;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn repo_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mrc-purge-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn artifact(repo_path: &Path, artifact: &str, versions: &[&str]) -> PathBuf {
+    let artifact_dir = repo_path.join("com/foo").join(artifact);
+    for version in versions {
+        write(
+            &artifact_dir
+                .join(version)
+                .join(format!("{artifact}-{version}.jar")),
+            "jar",
+        );
+    }
+    let listed: String = versions
+        .iter()
+        .map(|version| format!("<version>{version}</version>"))
+        .collect();
+    let last = versions.last().unwrap();
+    write(
+        &artifact_dir.join("maven-metadata-local.xml"),
+        &format!("<metadata><versioning><latest>{last}</latest><release>{last}</release><versions>{listed}</versions></versioning></metadata>"),
+    );
+    write(&artifact_dir.join("maven-metadata-local.xml.sha1"), "0");
+    artifact_dir
+}
+
+fn purge(repo_path: &Path, coordinates: &[&str]) -> bool {
+    let coordinates: Vec<Coordinate> = coordinates
+        .iter()
+        .map(|text| Coordinate::parse(text).unwrap())
+        .collect();
+    purge::purge(
+        repo_path,
+        &coordinates,
+        &CleanupOptions::default(),
+        false,
+        true,
+    )
+}
+
+#[test]
+fn coordinates_without_version_remove_the_artifact_directory() {
+    let repo_path = repo_dir("artifact");
+    let bar = artifact(&repo_path, "bar", &["1.0", "2.0"]);
+    let baz = artifact(&repo_path, "baz", &["1.0"]);
+    assert!(purge(&repo_path, &["com.foo:bar"]));
+    assert!(!bar.exists());
+    assert!(baz.join("1.0/baz-1.0.jar").is_file());
+    fs::remove_dir_all(&repo_path).unwrap();
+}
+
+#[test]
+fn purged_versions_are_dropped_from_metadata() {
+    let repo_path = repo_dir("versions");
+    let bar = artifact(&repo_path, "bar", &["1.0", "2.0"]);
+    assert!(purge(&repo_path, &["com.foo:bar:2.*"]));
+    assert!(bar.join("1.0").is_dir());
+    assert!(!bar.join("2.0").exists());
+    assert_eq!(
+        fs::read_to_string(bar.join("maven-metadata-local.xml")).unwrap(),
+        "<metadata><versioning><latest>1.0</latest><release>1.0</release><versions><version>1.0</version></versions></versioning></metadata>"
+    );
+    // 校验和已失效
+    assert!(!bar.join("maven-metadata-local.xml.sha1").exists());
+
+    // 最后一个版本被清除时, 构件目录连同元数据一起删除
+    assert!(purge(&repo_path, &["com.foo:bar:1.0"]));
+    assert!(!bar.exists());
+    assert!(!purge(&repo_path, &["com.foo:bar:1.0"]));
+    fs::remove_dir_all(&repo_path).unwrap();
+}
+
+#[test]
+fn purge_group_removes_the_group_unless_dry_run() {
+    let repo_path = repo_dir("group");
+    let bar = artifact(&repo_path, "bar", &["1.0"]);
+    let options = CleanupOptions::default();
+    assert!(purge::purge_group(
+        &repo_path, "com.foo", &options, true, true
+    ));
+    assert!(bar.join("1.0/bar-1.0.jar").is_file());
+
+    assert!(!purge::purge_group(
+        &repo_path,
+        "com.missing",
+        &options,
+        false,
+        true
+    ));
+    assert!(!purge::purge_group(
+        &repo_path, "com..foo", &options, false, true
+    ));
+    assert!(!purge::purge_group(
+        &repo_path, "../etc", &options, false, true
+    ));
+    assert!(bar.is_dir());
+
+    assert!(purge::purge_group(
+        &repo_path, "com.foo", &options, false, true
+    ));
+    assert!(!repo_path.join("com/foo").exists());
+    assert!(repo_path.join("com").is_dir());
+    fs::remove_dir_all(&repo_path).unwrap();
+}
+
+#[test]
+fn pinned_versions_survive_purges() {
+    let repo_path = repo_dir("pinned");
+    let bar = artifact(&repo_path, "bar", &["1.0", "2.0"]);
+    write(&repo_path.join(".mvnclean-pins"), "com.foo:bar:1.*\n");
+
+    // 只删除未固定的版本, 构件目录和元数据保留
+    assert!(purge(&repo_path, &["com.foo:bar"]));
+    assert!(bar.join("1.0/bar-1.0.jar").is_file());
+    assert!(!bar.join("2.0").exists());
+    assert!(fs::read_to_string(bar.join("maven-metadata-local.xml"))
+        .unwrap()
+        .contains("<versions><version>1.0</version></versions>"));
+    assert!(!purge(&repo_path, &["com.foo:bar:1.0"]));
+
+    let options = CleanupOptions::default();
+    assert!(!purge::purge_group(
+        &repo_path, "com", &options, false, true
+    ));
+    assert!(bar.join("1.0/bar-1.0.jar").is_file());
+
+    fs::remove_file(repo_path.join(".mvnclean-pins")).unwrap();
+    assert!(purge::purge_group(&repo_path, "com", &options, false, true));
+    assert!(!repo_path.join("com").exists());
+    fs::remove_dir_all(&repo_path).unwrap();
+}
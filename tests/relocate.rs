@@ -0,0 +1,38 @@
+use mrc::relocate;
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mrc-relocate-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+#[cfg(unix)]
+#[test]
+fn quarantined_paths_keep_the_permissions_of_their_parents() {
+    use std::os::unix::fs::PermissionsExt;
+    let root = temp_dir("parents");
+    let repo_path = root.join("repo");
+    let version = repo_path.join("com/foo/bar/1.0");
+    fs::create_dir_all(&version).unwrap();
+    fs::write(version.join("bar-1.0.pom"), "<project/>").unwrap();
+    fs::set_permissions(
+        repo_path.join("com/foo"),
+        fs::Permissions::from_mode(0o2770),
+    )
+    .unwrap();
+
+    let quarantine = root.join("quarantine");
+    let target = relocate::move_path(&repo_path, &version, &quarantine).unwrap();
+    assert_eq!(target, quarantine.join("com/foo/bar/1.0"));
+    assert!(target.join("bar-1.0.pom").is_file());
+    assert!(!version.exists());
+    let mode = |path: PathBuf| fs::metadata(path).unwrap().permissions().mode() & 0o7777;
+    assert_eq!(mode(quarantine.join("com/foo")), 0o2770);
+    assert_eq!(
+        mode(quarantine.join("com/foo/bar")),
+        mode(repo_path.join("com/foo/bar"))
+    );
+}
@@ -0,0 +1,138 @@
+use mrc::fs::FsHandle;
+use mrc::memory_fs::MemoryFs;
+use mrc::remote_repositories;
+use mrc::settings::{self, Repository};
+use mrc::CleanupOptions;
+use std::path::{Path, PathBuf};
+
+static SETTINGS: &str = r#"<settings>
+  <mirrors>
+    <mirror>
+      <id>internal</id>
+      <url>https://nexus.example.com/repository/public</url>
+      <mirrorOf>external:*,!snapshots</mirrorOf>
+    </mirror>
+    <mirror>
+      <id>releases-mirror</id>
+      <url>https://releases.example.com/maven</url>
+      <mirrorOf>releases</mirrorOf>
+    </mirror>
+  </mirrors>
+  <proxies>
+    <proxy>
+      <active>false</active>
+      <host>disabled.example.com</host>
+    </proxy>
+    <proxy>
+      <protocol>http</protocol>
+      <host>proxy.example.com</host>
+      <port>3128</port>
+      <nonProxyHosts>localhost|*.example.com</nonProxyHosts>
+    </proxy>
+  </proxies>
+  <profiles>
+    <profile>
+      <id>default</id>
+      <activation><activeByDefault>true</activeByDefault></activation>
+      <repositories>
+        <repository><id>releases</id><url>https://old.example.com/releases</url></repository>
+        <repository><id>snapshots</id><url>https://old.example.com/snapshots</url></repository>
+        <repository><id>local</id><url>http://localhost:8081/maven</url></repository>
+      </repositories>
+    </profile>
+  </profiles>
+</settings>"#;
+
+fn load() -> settings::Settings {
+    let path: PathBuf =
+        std::env::temp_dir().join(format!("mrc-settings-{}.xml", std::process::id()));
+    std::fs::write(&path, SETTINGS).unwrap();
+    let settings = settings::load(Some(&path));
+    std::fs::remove_file(&path).unwrap();
+    settings
+}
+
+#[test]
+fn repositories_resolve_through_mirrors() {
+    let settings = load();
+    let resolved: Vec<(String, String)> = settings
+        .resolved_repositories()
+        .into_iter()
+        .map(|Repository { id, url }| (id, url))
+        .collect();
+    assert_eq!(
+        resolved,
+        vec![
+            // 精确匹配优先于 external:*
+            (
+                "releases-mirror".to_string(),
+                "https://releases.example.com/maven".to_string()
+            ),
+            (
+                "snapshots".to_string(),
+                "https://old.example.com/snapshots".to_string()
+            ),
+            (
+                "local".to_string(),
+                "http://localhost:8081/maven".to_string()
+            ),
+            // central 被 external:* 镜像
+            (
+                "internal".to_string(),
+                "https://nexus.example.com/repository/public".to_string()
+            ),
+        ]
+    );
+    assert!(settings
+        .repository_ids()
+        .contains(&"releases-mirror".to_string()));
+}
+
+#[test]
+fn only_active_proxies_are_used() {
+    let settings = load();
+    assert_eq!(settings.proxies.len(), 1);
+    let proxy = &settings.proxies[0];
+    assert_eq!(proxy.host, "proxy.example.com");
+    assert_eq!(proxy.port, Some(3128));
+    assert_eq!(
+        proxy.non_proxy_hosts.as_deref(),
+        Some("localhost|*.example.com")
+    );
+}
+
+#[cfg(feature = "remote")]
+#[test]
+fn offline_mode_refuses_network_access() {
+    settings::set_offline(true);
+    let agent = settings::Settings::default().agent(std::time::Duration::from_secs(1));
+    settings::set_offline(false);
+    assert!(agent.is_err());
+}
+
+#[test]
+fn artifacts_downloaded_through_mirrors_are_not_decommissioned() {
+    let known_repositories = load().repository_ids();
+    let fs = MemoryFs::new()
+        .file("/repo/com/foo/bar/1.0/bar-1.0.jar", "jar")
+        .file("/repo/com/foo/bar/1.0/bar-1.0.pom", "pom")
+        .file("/repo/com/foo/bar/1.0/bar-1.0-sources.jar", "jar")
+        .file(
+            "/repo/com/foo/bar/1.0/_remote.repositories",
+            "bar-1.0.jar>internal=\nbar-1.0.pom>releases-mirror=\nbar-1.0-sources.jar>retired=\n",
+        );
+    let options = CleanupOptions {
+        fs: FsHandle::new(fs),
+        ..Default::default()
+    };
+    let candidates = remote_repositories::plan_decommissioned(
+        Path::new("/repo/com/foo/bar/1.0/_remote.repositories"),
+        &known_repositories,
+        &options,
+    );
+    let paths: Vec<&Path> = candidates.iter().map(|c| c.path.as_path()).collect();
+    assert_eq!(
+        paths,
+        [Path::new("/repo/com/foo/bar/1.0/bar-1.0-sources.jar")]
+    );
+}
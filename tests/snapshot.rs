@@ -0,0 +1,130 @@
+use mrc::snapshot::{self, Build, SnapshotVersion, Timestamp};
+use std::time::{Duration, UNIX_EPOCH};
+
+fn build(text: &str) -> Build {
+    Build::parse(text).unwrap()
+}
+
+#[test]
+fn parses_timestamped_builds() {
+    let parsed = build("20240229.235960-17");
+    assert_eq!(
+        parsed.timestamp,
+        Timestamp {
+            year: 2024,
+            month: 2,
+            day: 29,
+            hour: 23,
+            minute: 59,
+            second: 60,
+        }
+    );
+    assert_eq!(parsed.number, 17);
+    assert_eq!(parsed.to_string(), "20240229.235960-17");
+}
+
+#[test]
+fn rejects_malformed_builds() {
+    for text in [
+        "",
+        "20240101.120000",
+        "20240101.120000-",
+        "20240101-120000-1",
+        "2024011.120000-1",
+        "20241301.120000-1",
+        "20240100.120000-1",
+        "20240101.250000-1",
+        "20240101.12000a-1",
+        "20240101.120000-1x",
+        "+2024101.120000-1",
+        "202é010.120000-1",
+        "20240101.1é000-1",
+    ] {
+        assert_eq!(Build::parse(text), None, "{}", text);
+    }
+}
+
+#[test]
+fn orders_by_timestamp_then_build_number() {
+    let mut builds = [
+        build("20240101.120000-2"),
+        build("20231231.235959-9"),
+        build("20240101.120000-10"),
+        build("20240101.115959-11"),
+    ];
+    builds.sort();
+    let texts: Vec<String> = builds.iter().map(|build| build.to_string()).collect();
+    assert_eq!(
+        texts,
+        [
+            "20231231.235959-9",
+            "20240101.115959-11",
+            "20240101.120000-2",
+            "20240101.120000-10",
+        ]
+    );
+}
+
+#[test]
+fn converts_utc_timestamps() {
+    let time = |text: &str| Timestamp::parse(text).unwrap().to_system_time();
+    assert_eq!(time("19700101.000000"), UNIX_EPOCH);
+    assert_eq!(
+        time("20240229.120000"),
+        UNIX_EPOCH + Duration::from_secs(1709208000)
+    );
+    assert_eq!(time("19691231.235959"), UNIX_EPOCH - Duration::from_secs(1));
+    for text in ["19700101.000000", "20240229.120000", "21000301.235959"] {
+        let seconds = time(text).duration_since(UNIX_EPOCH).unwrap().as_secs();
+        assert_eq!(Timestamp::from_unix(seconds).to_string(), text);
+    }
+}
+
+#[test]
+fn parses_snapshot_versions() {
+    assert_eq!(
+        snapshot::parse("1.0-SNAPSHOT"),
+        Some(SnapshotVersion {
+            base: "1.0",
+            build: None,
+        })
+    );
+    assert_eq!(
+        snapshot::parse("2.1.0-rc-1-20240101.120000-3"),
+        Some(SnapshotVersion {
+            base: "2.1.0-rc-1",
+            build: Some(build("20240101.120000-3")),
+        })
+    );
+    assert_eq!(snapshot::parse("1.0"), None);
+    assert_eq!(snapshot::parse("1.0-rc-1"), None);
+    assert_eq!(snapshot::parse("20240101.120000-3"), None);
+}
+
+#[test]
+fn parses_file_names_in_snapshot_directories() {
+    assert_eq!(
+        snapshot::parse_file_name("bar", "1.1-SNAPSHOT", "bar-1.1-20230101.120000-1.jar"),
+        Some(build("20230101.120000-1"))
+    );
+    assert_eq!(
+        snapshot::parse_file_name(
+            "bar",
+            "1.1-SNAPSHOT",
+            "bar-1.1-20230101.120000-12-sources.jar.sha1"
+        ),
+        Some(build("20230101.120000-12"))
+    );
+    assert_eq!(
+        snapshot::parse_file_name("bar", "1.1-SNAPSHOT", "bar-1.1-SNAPSHOT.jar"),
+        None
+    );
+    assert_eq!(
+        snapshot::parse_file_name("bar", "1.1", "bar-1.1-20230101.120000-1.jar"),
+        None
+    );
+    assert_eq!(
+        snapshot::parse_file_name("bar", "1.1-SNAPSHOT", "bar-1.1-202é010.120000-1.jar"),
+        None
+    );
+}
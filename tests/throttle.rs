@@ -0,0 +1,104 @@
+use mrc::throttle::{self, Clock, Limit, Throttle};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// 睡眠只推进时间并记录时长, 克隆共享同一时间
+#[derive(Clone)]
+struct FakeClock {
+    start: Instant,
+    elapsed: Arc<Mutex<Duration>>,
+    sleeps: Arc<Mutex<Vec<Duration>>>,
+}
+
+impl FakeClock {
+    fn new() -> Self {
+        FakeClock {
+            start: Instant::now(),
+            elapsed: Arc::default(),
+            sleeps: Arc::default(),
+        }
+    }
+
+    fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap() += duration;
+    }
+
+    fn sleeps(&self) -> Vec<Duration> {
+        self.sleeps.lock().unwrap().clone()
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        self.start + *self.elapsed.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.sleeps.lock().unwrap().push(duration);
+        self.advance(duration);
+    }
+}
+
+#[test]
+fn parses_byte_and_operation_rates() {
+    let rate = |text: &str| match throttle::parse(text) {
+        Ok(Limit::BytesPerSecond(rate)) => rate,
+        other => panic!("{}: {:?}", text, other),
+    };
+    assert_eq!(rate("20MiB/s"), 20.0 * 1024.0 * 1024.0);
+    assert_eq!(rate("1.5MB/s"), 1_500_000.0);
+    assert_eq!(rate(" 500KiB/s "), 500.0 * 1024.0);
+    assert_eq!(rate("4096B/s"), 4096.0);
+    assert!(matches!(
+        throttle::parse("100ops/s"),
+        Ok(Limit::OpsPerSecond(rate)) if rate == 100.0
+    ));
+}
+
+#[test]
+fn rejects_invalid_and_zero_rates() {
+    for text in [
+        "20MiB",
+        "20/s",
+        "MiB/s",
+        "1.2.3MiB/s",
+        "20XB/s",
+        "20 MiB/s",
+        "0MiB/s",
+        "0ops/s",
+        "0.0ops/s",
+        "",
+    ] {
+        assert!(throttle::parse(text).is_err(), "{}", text);
+    }
+    assert!(throttle::parse("0ops/s")
+        .unwrap_err()
+        .contains("must be positive"));
+}
+
+#[test]
+fn paces_bytes_to_the_limit() {
+    let clock = FakeClock::new();
+    let throttle = Throttle::with_clock(Limit::BytesPerSecond(1000.0), Box::new(clock.clone()));
+    // 2000 字节本应花费 2 秒, 实际 0.5 秒
+    clock.advance(Duration::from_millis(500));
+    throttle.pace(1, 2000);
+    assert_eq!(clock.sleeps(), [Duration::from_millis(1500)]);
+
+    // 落后于限速时不睡眠
+    clock.advance(Duration::from_secs(3));
+    throttle.pace(2, 3000);
+    assert_eq!(clock.sleeps().len(), 1);
+}
+
+#[test]
+fn paces_operations_to_the_limit() {
+    let clock = FakeClock::new();
+    let throttle = Throttle::with_clock(Limit::OpsPerSecond(10.0), Box::new(clock.clone()));
+    for ops in 1..=5 {
+        throttle.pace(ops, 1 << 30);
+    }
+    let slept: Duration = clock.sleeps().iter().sum();
+    assert_eq!(clock.sleeps().len(), 5);
+    assert!(slept.abs_diff(Duration::from_millis(500)) < Duration::from_micros(1));
+}
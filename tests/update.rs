@@ -0,0 +1,21 @@
+#![cfg(feature = "remote")]
+
+use mrc::update::{asset_name, parse_checksum};
+
+static DIGEST: &str = "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08";
+
+#[test]
+fn checksum_files_with_and_without_names() {
+    let name = asset_name();
+    let expected = Some(DIGEST.to_ascii_lowercase());
+    assert_eq!(parse_checksum(&format!("{}\n", DIGEST), &name), expected);
+    let sums = format!(
+        "{}  mrc-other-platform\n{} *{}\n",
+        "0".repeat(64),
+        DIGEST,
+        name
+    );
+    assert_eq!(parse_checksum(&sums, &name), expected);
+    assert_eq!(parse_checksum("not a digest", &name), None);
+    assert_eq!(parse_checksum(&format!("{}  other", DIGEST), &name), None);
+}
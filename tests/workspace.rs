@@ -0,0 +1,85 @@
+use mrc::gav::Gav;
+use mrc::workspace;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn workspace_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mrc-workspace-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn gav(text: &str) -> Gav {
+    let parts: Vec<&str> = text.split(':').collect();
+    Gav {
+        group_id: parts[0].to_string(),
+        artifact_id: parts[1].to_string(),
+        version: parts[2].to_string(),
+    }
+}
+
+#[test]
+fn versions_resolve_through_properties_and_parent_management() {
+    let root = workspace_dir("inherit");
+    write(
+        &root.join("pom.xml"),
+        r#"<project>
+            <groupId>com.app</groupId>
+            <artifactId>parent</artifactId>
+            <version>${revision}</version>
+            <properties>
+                <revision>1.4.0-SNAPSHOT</revision>
+                <lib.version>2.1</lib.version>
+                <guava.version>33.0-${guava.flavor}</guava.version>
+                <guava.flavor>jre</guava.flavor>
+            </properties>
+            <dependencyManagement>
+                <dependencies>
+                    <dependency>
+                        <groupId>com.google.guava</groupId>
+                        <artifactId>guava</artifactId>
+                        <version>${guava.version}</version>
+                    </dependency>
+                </dependencies>
+            </dependencyManagement>
+        </project>"#,
+    );
+    write(
+        &root.join("core/pom.xml"),
+        r#"<project>
+            <parent>
+                <groupId>com.app</groupId>
+                <artifactId>parent</artifactId>
+                <version>${revision}</version>
+            </parent>
+            <artifactId>core</artifactId>
+            <properties>
+                <lib.version>2.2</lib.version>
+            </properties>
+            <dependencies>
+                <dependency><groupId>com.google.guava</groupId><artifactId>guava</artifactId></dependency>
+                <dependency><groupId>com.foo</groupId><artifactId>lib</artifactId><version>${lib.version}</version></dependency>
+                <dependency><groupId>${project.groupId}</groupId><artifactId>api</artifactId><version>${project.version}</version></dependency>
+                <dependency><groupId>com.foo</groupId><artifactId>missing</artifactId><version>${undefined}</version></dependency>
+            </dependencies>
+        </project>"#,
+    );
+    let keep = workspace::scan(&[root]).unwrap();
+    for expected in [
+        "com.app:parent:1.4.0-SNAPSHOT",
+        "com.app:core:1.4.0-SNAPSHOT",
+        "com.app:api:1.4.0-SNAPSHOT",
+        "com.google.guava:guava:33.0-jre",
+        "com.foo:lib:2.2",
+    ] {
+        assert!(keep.contains(&gav(expected)), "{} not kept", expected);
+    }
+    // 子 POM 覆盖的属性值不再使用; 未定义的属性无法解析
+    assert_eq!(keep.len(), 5);
+}